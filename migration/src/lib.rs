@@ -1,5 +1,8 @@
 use migrations::{
-    admins, coupons, goods, image_classes, images, notices, roles, rules, skus, user_levels, users,
+    admin_email, admins, audit_logs, coupons, foreign_keys, goods, hash_admin_passwords,
+    hash_user_passwords, image_classes, images, login_attempts, money_columns, notices,
+    password_reset_tokens, role_rule_grants, role_rule_ids_json, role_rules, roles, rule_methods,
+    rules, sessions, skus, timestamp_columns, user_levels, users,
 };
 pub use sea_orm_migration::prelude::*;
 
@@ -22,6 +25,20 @@ impl MigratorTrait for Migrator {
             Box::new(images::Migration),
             Box::new(image_classes::Migration),
             Box::new(goods::Migration),
+            Box::new(hash_admin_passwords::Migration),
+            Box::new(hash_user_passwords::Migration),
+            Box::new(sessions::Migration),
+            Box::new(login_attempts::Migration),
+            Box::new(audit_logs::Migration),
+            Box::new(admin_email::Migration),
+            Box::new(password_reset_tokens::Migration),
+            Box::new(role_rule_ids_json::Migration),
+            Box::new(timestamp_columns::Migration),
+            Box::new(money_columns::Migration),
+            Box::new(foreign_keys::Migration),
+            Box::new(role_rules::Migration),
+            Box::new(rule_methods::Migration),
+            Box::new(role_rule_grants::Migration),
         ]
     }
 }
@@ -0,0 +1,142 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLogs::Id)
+                            .integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                            .comment("日志 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::AdminId)
+                            .integer()
+                            .not_null()
+                            .comment("操作的管理员 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Method)
+                            .string()
+                            .not_null()
+                            .comment("请求方法"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Route)
+                            .string()
+                            .not_null()
+                            .comment("路由模板，如 /api/goods/{id}"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Path)
+                            .string()
+                            .not_null()
+                            .comment("实际请求路径"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Target)
+                            .string()
+                            .not_null()
+                            .comment("操作的数据表，如 goods"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::TargetIds)
+                            .json()
+                            .not_null()
+                            .comment("操作的数据 ID 列表"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Before)
+                            .json()
+                            .null()
+                            .comment("操作前的数据"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::After)
+                            .json()
+                            .null()
+                            .comment("操作后的数据"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Ip)
+                            .string()
+                            .null()
+                            .comment("客户端 IP"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::StatusCode)
+                            .integer()
+                            .not_null()
+                            .comment("HTTP 状态码"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::Outcome)
+                            .string()
+                            .not_null()
+                            .comment("操作结果：success 成功；failure 失败"),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogs::CreateTime)
+                            .string()
+                            .not_null()
+                            .comment("创建时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_admin_id")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::AdminId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_target")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::Target)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLogs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLogs {
+    Table,
+    Id,
+    AdminId,
+    Method,
+    Route,
+    Path,
+    Target,
+    TargetIds,
+    Before,
+    After,
+    Ip,
+    StatusCode,
+    Outcome,
+    CreateTime,
+}
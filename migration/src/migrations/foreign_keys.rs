@@ -0,0 +1,200 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, DatabaseTransaction, Statement, TransactionTrait},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 引用其他表的列
+struct Reference {
+    table: &'static str,
+    column: &'static str,
+    parent: &'static str,
+    on_delete: ForeignKeyAction,
+}
+
+/// 原来均为 `integer NOT NULL`，以 0 或 -1 表示未关联；改为可空后，未关联或关联的数据已不存在时置为 NULL
+///
+/// - 删除角色、会员等级时，相应管理员、用户的关联置为 NULL
+/// - 图库中仍有图片时不允许删除
+///
+/// `goods.category_id` 没有对应的分类表，暂不添加外键
+const REFERENCES: &[Reference] = &[
+    Reference {
+        table: "admins",
+        column: "role_id",
+        parent: "roles",
+        on_delete: ForeignKeyAction::SetNull,
+    },
+    Reference {
+        table: "users",
+        column: "user_level_id",
+        parent: "user_levels",
+        on_delete: ForeignKeyAction::SetNull,
+    },
+    Reference {
+        table: "images",
+        column: "image_class_id",
+        parent: "image_classes",
+        on_delete: ForeignKeyAction::Restrict,
+    },
+];
+
+impl Reference {
+    fn name(&self) -> String {
+        format!("fk_{}_{}", self.table, self.column)
+    }
+
+    fn on_delete_sql(&self) -> &'static str {
+        match self.on_delete {
+            ForeignKeyAction::SetNull => "SET NULL",
+            _ => "RESTRICT",
+        }
+    }
+
+    /// SQLite 建表语句中的列定义
+    fn sqlite_legacy_column(&self) -> String {
+        format!(r#""{}" integer NOT NULL"#, self.column)
+    }
+
+    fn sqlite_column(&self) -> String {
+        format!(
+            r#""{}" integer NULL REFERENCES "{}" ("id") ON DELETE {}"#,
+            self.column,
+            self.parent,
+            self.on_delete_sql()
+        )
+    }
+
+    fn clear_dangling_sql(&self) -> String {
+        let Reference {
+            table,
+            column,
+            parent,
+            ..
+        } = self;
+        format!(
+            "UPDATE {table} SET {column} = NULL WHERE {column} NOT IN (SELECT id FROM {parent})"
+        )
+    }
+}
+
+/// SQLite 不支持为已有的表添加外键，按替换了列定义的建表语句重建表
+///
+/// 所有语句在同一个事务中执行，以使用同一个连接
+async fn sqlite_rebuild_table(
+    txn: &DatabaseTransaction,
+    table: &str,
+    from: &str,
+    to: &str,
+) -> Result<(), DbErr> {
+    let row = txn
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table.into()],
+        ))
+        .await?
+        .ok_or_else(|| DbErr::Custom(format!("表 {table} 不存在")))?;
+    let sql: String = row.try_get("", "sql")?;
+    if !sql.contains(from) {
+        return Err(DbErr::Custom(format!("表 {table} 中没有列定义 {from}")));
+    }
+
+    let tmp = format!("{table}_new");
+    let create = sql
+        .replacen(
+            &format!(r#"CREATE TABLE "{table}""#),
+            &format!(r#"CREATE TABLE "{tmp}""#),
+            1,
+        )
+        .replacen(from, to, 1);
+    for sql in [
+        create,
+        format!(r#"INSERT INTO "{tmp}" SELECT * FROM "{table}""#),
+        format!(r#"DROP TABLE "{table}""#),
+        format!(r#"ALTER TABLE "{tmp}" RENAME TO "{table}""#),
+    ] {
+        txn.execute_unprepared(&sql).await?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let txn = manager.get_connection().begin().await?;
+        if backend == DatabaseBackend::Sqlite {
+            // 重建表与修正数据之间会短暂存在无效的关联，推迟到提交时再检查外键
+            txn.execute_unprepared("PRAGMA defer_foreign_keys = ON")
+                .await?;
+        }
+        for reference in REFERENCES {
+            let Reference { table, column, .. } = reference;
+            if backend == DatabaseBackend::Postgres {
+                txn.execute_unprepared(&format!(
+                    "ALTER TABLE {table} ALTER COLUMN {column} DROP NOT NULL"
+                ))
+                .await?;
+                txn.execute_unprepared(&reference.clear_dangling_sql())
+                    .await?;
+                let foreign_key = ForeignKey::create()
+                    .name(reference.name())
+                    .from(Alias::new(*table), Alias::new(*column))
+                    .to(Alias::new(reference.parent), Alias::new("id"))
+                    .on_delete(reference.on_delete)
+                    .to_owned();
+                txn.execute(backend.build(&foreign_key)).await?;
+            } else {
+                sqlite_rebuild_table(
+                    &txn,
+                    table,
+                    &reference.sqlite_legacy_column(),
+                    &reference.sqlite_column(),
+                )
+                .await?;
+                txn.execute_unprepared(&reference.clear_dangling_sql())
+                    .await?;
+            }
+        }
+        txn.commit().await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let txn = manager.get_connection().begin().await?;
+        if backend == DatabaseBackend::Sqlite {
+            txn.execute_unprepared("PRAGMA defer_foreign_keys = ON")
+                .await?;
+        }
+        for reference in REFERENCES {
+            let Reference { table, column, .. } = reference;
+            let restore_zero_sql =
+                format!("UPDATE {table} SET {column} = 0 WHERE {column} IS NULL");
+            if backend == DatabaseBackend::Postgres {
+                let foreign_key = ForeignKey::drop()
+                    .name(reference.name())
+                    .table(Alias::new(*table))
+                    .to_owned();
+                txn.execute(backend.build(&foreign_key)).await?;
+                txn.execute_unprepared(&restore_zero_sql).await?;
+                txn.execute_unprepared(&format!(
+                    "ALTER TABLE {table} ALTER COLUMN {column} SET NOT NULL"
+                ))
+                .await?;
+            } else {
+                txn.execute_unprepared(&restore_zero_sql).await?;
+                sqlite_rebuild_table(
+                    &txn,
+                    table,
+                    &reference.sqlite_column(),
+                    &reference.sqlite_legacy_column(),
+                )
+                .await?;
+            }
+        }
+        txn.commit().await
+    }
+}
@@ -0,0 +1,56 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    Argon2,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    /// 将 `users.password` 中的明文密码改写为 Argon2 哈希，已是 PHC 格式的记录保持不变
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let builder = db.get_database_backend();
+
+        let select = Query::select()
+            .columns([Users::Id, Users::Password])
+            .from(Users::Table)
+            .to_owned();
+        let rows = db.query_all(builder.build(&select)).await?;
+
+        for row in rows {
+            let id: i32 = row.try_get("", "id")?;
+            let password: String = row.try_get("", "password")?;
+            if password.starts_with("$argon2") {
+                continue;
+            }
+            let salt = SaltString::generate(&mut OsRng);
+            let password_hash = Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|err| DbErr::Migration(err.to_string()))?
+                .to_string();
+            let update = Query::update()
+                .table(Users::Table)
+                .value(Users::Password, password_hash)
+                .and_where(Expr::col(Users::Id).eq(id))
+                .to_owned();
+            db.execute(builder.build(&update)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 哈希不可逆，回滚时不做处理
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    Password,
+}
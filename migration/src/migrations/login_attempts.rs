@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginAttempts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LoginAttempts::Id)
+                            .integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                            .comment("记录 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::SubjectType)
+                            .string()
+                            .not_null()
+                            .comment("登录主体类型：admin 管理员；user 商城用户"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::Scope)
+                            .string()
+                            .not_null()
+                            .comment("限制维度：username 用户名；ip 客户端 IP"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::Key)
+                            .string()
+                            .not_null()
+                            .comment("用户名或客户端 IP"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::Failures)
+                            .integer()
+                            .not_null()
+                            .default(0)
+                            .comment("连续失败次数"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::LastFailureAt)
+                            .big_integer()
+                            .not_null()
+                            .comment("最近一次失败时间（Unix 时间戳）"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::LockedUntil)
+                            .big_integer()
+                            .not_null()
+                            .default(0)
+                            .comment("在此时间之前禁止登录（Unix 时间戳）"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::CreateTime)
+                            .string()
+                            .not_null()
+                            .comment("创建时间"),
+                    )
+                    .col(
+                        ColumnDef::new(LoginAttempts::UpdateTime)
+                            .string()
+                            .not_null()
+                            .comment("更新时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_attempts_key")
+                    .table(LoginAttempts::Table)
+                    .col(LoginAttempts::SubjectType)
+                    .col(LoginAttempts::Scope)
+                    .col(LoginAttempts::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginAttempts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginAttempts {
+    Table,
+    Id,
+    SubjectType,
+    Scope,
+    Key,
+    Failures,
+    LastFailureAt,
+    LockedUntil,
+    CreateTime,
+    UpdateTime,
+}
@@ -1,11 +1,25 @@
+pub mod admin_email;
 pub mod admins;
+pub mod audit_logs;
 pub mod coupons;
+pub mod foreign_keys;
 pub mod goods;
+pub mod hash_admin_passwords;
+pub mod hash_user_passwords;
 pub mod image_classes;
 pub mod images;
+pub mod login_attempts;
+pub mod money_columns;
 pub mod notices;
+pub mod password_reset_tokens;
+pub mod role_rule_grants;
+pub mod role_rule_ids_json;
+pub mod role_rules;
 pub mod roles;
+pub mod rule_methods;
 pub mod rules;
+pub mod sessions;
 pub mod skus;
+pub mod timestamp_columns;
 pub mod user_levels;
 pub mod users;
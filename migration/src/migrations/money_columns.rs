@@ -0,0 +1,118 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, TransactionTrait},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 金额列原来的类型
+#[derive(Clone, Copy)]
+enum Legacy {
+    /// 字符串，如 `"12.50"`
+    Text,
+    /// 浮点数
+    Double,
+    /// 整数
+    Integer,
+}
+
+const COLUMNS: &[(&str, &str, Legacy)] = &[
+    ("goods", "min_price", Legacy::Text),
+    ("goods", "min_ori_price", Legacy::Text),
+    ("coupons", "value", Legacy::Double),
+    ("coupons", "min_price", Legacy::Double),
+    ("user_levels", "max_price", Legacy::Integer),
+];
+
+impl Legacy {
+    fn postgres_type(self) -> &'static str {
+        match self {
+            Legacy::Text => "varchar",
+            Legacy::Double => "double precision",
+            Legacy::Integer => "integer",
+        }
+    }
+
+    fn sqlite_type(self) -> &'static str {
+        match self {
+            Legacy::Text => "varchar",
+            Legacy::Double => "double",
+            Legacy::Integer => "integer",
+        }
+    }
+
+    /// SQLite 中由金额转换回原类型的表达式
+    fn sqlite_value(self, name: &str) -> String {
+        match self {
+            Legacy::Text => format!("printf('%.2f', {name})"),
+            Legacy::Double => format!("CAST({name} AS REAL)"),
+            Legacy::Integer => format!("CAST(ROUND({name}) AS INTEGER)"),
+        }
+    }
+}
+
+/// SQLite 不支持修改列类型，新建一列写入转换后的数据后替换原列
+fn sqlite_replace_column(table: &str, name: &str, column_type: &str, value: &str) -> [String; 4] {
+    let tmp = format!("{name}_tmp");
+    [
+        format!("ALTER TABLE {table} ADD COLUMN {tmp} {column_type} NOT NULL DEFAULT 0"),
+        format!("UPDATE {table} SET {tmp} = {value}"),
+        format!("ALTER TABLE {table} DROP COLUMN {name}"),
+        format!("ALTER TABLE {table} RENAME COLUMN {tmp} TO {name}"),
+    ]
+}
+
+/// 在同一个事务中依次执行
+///
+/// SQLite 替换列的几条语句必须使用同一个连接，否则连接池中的其他连接可能还未看到新增的列
+async fn execute_all(manager: &SchemaManager<'_>, sqls: Vec<String>) -> Result<(), DbErr> {
+    let txn = manager.get_connection().begin().await?;
+    for sql in sqls {
+        txn.execute_unprepared(&sql).await?;
+    }
+    txn.commit().await
+}
+
+/// 金额列统一改为定点小数 `numeric(10, 2)`
+///
+/// SeaORM 在 SQLite 中将定点小数建为 `real(10, 2)`，按浮点数读写，由 `Money` 在读取时舍入到两位小数
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let mut sqls = Vec::new();
+        for &(table, name, _) in COLUMNS {
+            if backend == DatabaseBackend::Postgres {
+                sqls.push(format!(
+                    "ALTER TABLE {table} ALTER COLUMN {name} TYPE numeric(10, 2) USING {name}::numeric(10, 2)"
+                ));
+            } else {
+                let value = format!("ROUND(CAST({name} AS REAL), 2)");
+                sqls.extend(sqlite_replace_column(table, name, "real(10, 2)", &value));
+            }
+        }
+        execute_all(manager, sqls).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let mut sqls = Vec::new();
+        for &(table, name, legacy) in COLUMNS {
+            if backend == DatabaseBackend::Postgres {
+                let column_type = legacy.postgres_type();
+                sqls.push(format!(
+                    "ALTER TABLE {table} ALTER COLUMN {name} TYPE {column_type} USING {name}::{column_type}"
+                ));
+            } else {
+                sqls.extend(sqlite_replace_column(
+                    table,
+                    name,
+                    legacy.sqlite_type(),
+                    &legacy.sqlite_value(name),
+                ));
+            }
+        }
+        execute_all(manager, sqls).await
+    }
+}
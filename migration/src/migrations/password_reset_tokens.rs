@@ -0,0 +1,92 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PasswordResetTokens::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::Id)
+                            .integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                            .comment("令牌 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::SubjectType)
+                            .string()
+                            .not_null()
+                            .comment("账号类型：admin 管理员；user 商城用户"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::SubjectId)
+                            .integer()
+                            .not_null()
+                            .comment("账号 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::TokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key()
+                            .comment("重置令牌的 SHA-256 哈希"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::ExpiresAt)
+                            .big_integer()
+                            .not_null()
+                            .comment("过期时间（Unix 时间戳）"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::UsedAt)
+                            .big_integer()
+                            .null()
+                            .comment("使用时间（Unix 时间戳），未使用为空"),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetTokens::CreateTime)
+                            .string()
+                            .not_null()
+                            .comment("创建时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_password_reset_tokens_subject")
+                    .table(PasswordResetTokens::Table)
+                    .col(PasswordResetTokens::SubjectType)
+                    .col(PasswordResetTokens::SubjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PasswordResetTokens::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PasswordResetTokens {
+    Table,
+    Id,
+    SubjectType,
+    SubjectId,
+    TokenHash,
+    ExpiresAt,
+    UsedAt,
+    CreateTime,
+}
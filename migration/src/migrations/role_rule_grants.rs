@@ -0,0 +1,109 @@
+use sea_orm_migration::{prelude::*, sea_orm::TransactionTrait};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 新增规则的名称、接口别名与请求方式
+const NEW_RULES: &[(&str, &str, &str)] = &[
+    ("给角色授予单条权限", "grantRoleRule", "POST"),
+    ("撤销角色的单条权限", "revokeRoleRule", "DELETE"),
+];
+
+/// 单独授予、撤销角色权限的接口原先使用 `setRoleRules` 规则，改为使用独立的规则
+///
+/// 已导入规则时，在 `setRoleRules` 所在的菜单下新增这两条规则，并授予已拥有 `setRoleRules` 的角色，
+/// 使升级前后的权限保持一致。规则表为空时不做处理，之后导入的初始数据中已包含这两条规则
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let txn = manager.get_connection().begin().await?;
+        for (name, condition, method) in NEW_RULES {
+            let exists = Query::select()
+                .expr(Expr::val(1))
+                .from(Rules::Table)
+                .and_where(Expr::col(Rules::Condition).eq(*condition))
+                .to_owned();
+            let source = Query::select()
+                .expr(Expr::val(*name))
+                .column(Rules::RuleId)
+                .column(Rules::Status)
+                .expr(Expr::current_timestamp())
+                .expr(Expr::current_timestamp())
+                .expr(Expr::val(""))
+                .expr(Expr::val(*condition))
+                .column(Rules::Menu)
+                .column(Rules::Order)
+                .expr(Expr::val(""))
+                .expr(Expr::val(*method))
+                .from(Rules::Table)
+                .and_where(Expr::col(Rules::Condition).eq("setRoleRules"))
+                .and_where(Expr::exists(exists).not())
+                .limit(1)
+                .to_owned();
+            let insert = Query::insert()
+                .into_table(Rules::Table)
+                .columns([
+                    Rules::Name,
+                    Rules::RuleId,
+                    Rules::Status,
+                    Rules::CreateTime,
+                    Rules::UpdateTime,
+                    Rules::FrontPath,
+                    Rules::Condition,
+                    Rules::Menu,
+                    Rules::Order,
+                    Rules::Icon,
+                    Rules::Method,
+                ])
+                .select_from(source)
+                .map_err(|err| DbErr::Migration(err.to_string()))?
+                .to_owned();
+            txn.execute(backend.build(&insert)).await?;
+        }
+        txn.execute_unprepared(
+            "INSERT INTO role_rules (role_id, rule_id)
+            SELECT DISTINCT role_rules.role_id, granted.id
+            FROM role_rules
+                INNER JOIN rules AS source ON source.id = role_rules.rule_id
+                INNER JOIN rules AS granted
+                    ON granted.condition IN ('grantRoleRule', 'revokeRoleRule')
+            WHERE source.condition = 'setRoleRules'
+                AND NOT EXISTS (
+                    SELECT 1 FROM role_rules AS existing
+                    WHERE existing.role_id = role_rules.role_id AND existing.rule_id = granted.id
+                )",
+        )
+        .await?;
+        txn.commit().await
+    }
+
+    /// 删除这两条规则，角色与规则的关联随之删除
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conditions = NEW_RULES.iter().map(|(_, condition, _)| *condition);
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Rules::Table)
+                    .and_where(Expr::col(Rules::Condition).is_in(conditions))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Rules {
+    Table,
+    Name,
+    RuleId,
+    Status,
+    CreateTime,
+    UpdateTime,
+    FrontPath,
+    Condition,
+    Menu,
+    Order,
+    Icon,
+    Method,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// `roles.rule_ids` 由整数数组改为 JSON 数组，以便同时支持 SQLite
+///
+/// SQLite 在创建 `roles` 表时已使用 JSON，只需转换 PostgreSQL
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE roles ALTER COLUMN rule_ids TYPE jsonb USING to_jsonb(rule_ids)",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        // `USING` 中不能使用子查询，先写入新列再替换
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE roles ADD COLUMN rule_ids_array integer[]")
+            .await?;
+        db.execute_unprepared(
+            "UPDATE roles SET rule_ids_array = ARRAY(
+                SELECT jsonb_array_elements_text(rule_ids)::integer
+            ) WHERE rule_ids IS NOT NULL",
+        )
+        .await?;
+        db.execute_unprepared("ALTER TABLE roles DROP COLUMN rule_ids")
+            .await?;
+        db.execute_unprepared("ALTER TABLE roles RENAME COLUMN rule_ids_array TO rule_ids")
+            .await?;
+        Ok(())
+    }
+}
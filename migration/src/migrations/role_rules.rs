@@ -0,0 +1,130 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{DatabaseBackend, TransactionTrait},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 角色与规则的多对多关系由 `roles.rule_ids` 改为 `role_rules` 关联表
+///
+/// 删除角色或规则时一并删除关联。原有数据中已不存在的规则 ID 与重复的 ID 在迁移时丢弃
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let create_table = Table::create()
+            .table(RoleRules::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(RoleRules::RoleId)
+                    .integer()
+                    .not_null()
+                    .comment("角色 ID"),
+            )
+            .col(
+                ColumnDef::new(RoleRules::RuleId)
+                    .integer()
+                    .not_null()
+                    .comment("规则 ID"),
+            )
+            .primary_key(
+                Index::create()
+                    .col(RoleRules::RoleId)
+                    .col(RoleRules::RuleId),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_role_rules_role_id")
+                    .from(RoleRules::Table, RoleRules::RoleId)
+                    .to(Roles::Table, Roles::Id)
+                    .on_delete(ForeignKeyAction::Cascade),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name("fk_role_rules_rule_id")
+                    .from(RoleRules::Table, RoleRules::RuleId)
+                    .to(Rules::Table, Rules::Id)
+                    .on_delete(ForeignKeyAction::Cascade),
+            )
+            .to_owned();
+        let create_index = Index::create()
+            .name("idx_role_rules_rule_id")
+            .table(RoleRules::Table)
+            .col(RoleRules::RuleId)
+            .to_owned();
+        let copy_rule_ids = if backend == DatabaseBackend::Postgres {
+            "INSERT INTO role_rules (role_id, rule_id)
+            SELECT DISTINCT roles.id, rule_id::integer
+            FROM roles, jsonb_array_elements_text(roles.rule_ids) AS rule_id
+            WHERE roles.rule_ids IS NOT NULL
+                AND rule_id::integer IN (SELECT id FROM rules)"
+        } else {
+            "INSERT INTO role_rules (role_id, rule_id)
+            SELECT DISTINCT roles.id, CAST(json_each.value AS INTEGER)
+            FROM roles, json_each(roles.rule_ids)
+            WHERE roles.rule_ids IS NOT NULL
+                AND CAST(json_each.value AS INTEGER) IN (SELECT id FROM rules)"
+        };
+
+        // SQLite 中多条语句需使用同一个连接
+        let txn = manager.get_connection().begin().await?;
+        txn.execute(backend.build(&create_table)).await?;
+        txn.execute(backend.build(&create_index)).await?;
+        txn.execute_unprepared(copy_rule_ids).await?;
+        txn.execute_unprepared("ALTER TABLE roles DROP COLUMN rule_ids")
+            .await?;
+        txn.commit().await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let (column_type, copy_rule_ids) = if backend == DatabaseBackend::Postgres {
+            (
+                "jsonb",
+                "UPDATE roles SET rule_ids = COALESCE(
+                    (SELECT jsonb_agg(rule_id ORDER BY rule_id) FROM role_rules WHERE role_id = roles.id),
+                    '[]'::jsonb
+                )",
+            )
+        } else {
+            (
+                "jsonb_text",
+                "UPDATE roles SET rule_ids = (
+                    SELECT json_group_array(rule_id) FROM (
+                        SELECT rule_id FROM role_rules WHERE role_id = roles.id ORDER BY rule_id
+                    )
+                )",
+            )
+        };
+
+        let txn = manager.get_connection().begin().await?;
+        txn.execute_unprepared(&format!(
+            "ALTER TABLE roles ADD COLUMN rule_ids {column_type} NULL"
+        ))
+        .await?;
+        txn.execute_unprepared(copy_rule_ids).await?;
+        txn.execute(backend.build(&Table::drop().table(RoleRules::Table).to_owned()))
+            .await?;
+        txn.commit().await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RoleRules {
+    Table,
+    RoleId,
+    RuleId,
+}
+
+#[derive(DeriveIden)]
+enum Roles {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Rules {
+    Table,
+    Id,
+}
@@ -1,4 +1,4 @@
-use sea_orm_migration::prelude::*;
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
 
 #[derive(DeriveMigrationName)]
 pub struct Migration;
@@ -6,6 +6,13 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut rule_ids = ColumnDef::new(Roles::RuleIds);
+        // SQLite 不支持数组，直接建为 JSON；PostgreSQL 由 `role_rule_ids_json` 迁移转换
+        match manager.get_database_backend() {
+            DatabaseBackend::Sqlite => rule_ids.json_binary(),
+            _ => rule_ids.array(ColumnType::Integer),
+        };
+
         manager
             .create_table(
                 Table::create()
@@ -37,12 +44,7 @@ impl MigrationTrait for Migration {
                             .not_null()
                             .comment("角色描述"),
                     )
-                    .col(
-                        ColumnDef::new(Roles::RuleIds)
-                            .array(ColumnType::Integer)
-                            .null()
-                            .comment("权限 ID 列表"),
-                    )
+                    .col(rule_ids.null().comment("权限 ID 列表"))
                     .col(
                         ColumnDef::new(Roles::CreateTime)
                             .string()
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 各规则修改后的请求方式，修改前均为 `POST`
+const RULE_METHODS: &[(&str, &str)] = &[
+    ("updateRule", "PUT"),
+    ("deleteRule", "DELETE"),
+    ("updateRuleStatus", "PATCH"),
+    ("updateRole", "PUT"),
+    ("deleteRole", "DELETE"),
+    ("updateRoleStatus", "PATCH"),
+    ("setRoleRules", "PATCH"),
+    ("updateImageClass", "PUT"),
+    ("deleteImageClass", "DELETE"),
+    ("deleteImage", "DELETE"),
+    ("updateImage", "PUT"),
+    ("deleteSkus", "DELETE"),
+    ("updateSkus", "PUT"),
+    ("updateSkusStatus", "PATCH"),
+    ("updateUser", "PUT"),
+    ("updateUserStatus", "PATCH"),
+    ("deleteUser", "DELETE"),
+    ("updateUserLevel", "PUT"),
+    ("updateUserLevelStatus", "PATCH"),
+    ("deleteUserLevel", "DELETE"),
+    ("deleteManager", "DELETE"),
+    ("updateManager", "PUT"),
+    ("updateManagerStatus", "PATCH"),
+    ("restoreGoods", "PATCH"),
+    ("destroyGoods", "DELETE"),
+    ("deleteGoods", "PATCH"),
+    ("updateGoodsStatus", "PATCH"),
+    ("updateGoods", "PUT"),
+    ("checkGoods", "PATCH"),
+    ("updateNotice", "PUT"),
+    ("deleteNotice", "DELETE"),
+    ("updateCoupon", "PUT"),
+    ("updateCouponStatus", "PATCH"),
+    ("deleteCoupon", "DELETE"),
+];
+
+const OLD_METHOD: &str = "POST";
+
+/// 接口改为按语义使用 `PUT`、`PATCH`、`DELETE` 后，已有规则的请求方式随之修改，
+/// 否则权限校验按请求方式匹配规则时，已分配这些规则的管理员会被拒绝访问
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (condition, method) in RULE_METHODS {
+            set_method(manager, condition, OLD_METHOD, method).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (condition, method) in RULE_METHODS {
+            set_method(manager, condition, method, OLD_METHOD).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 只修改请求方式仍为 `from` 的规则，保留管理员手动调整过的规则
+async fn set_method(
+    manager: &SchemaManager<'_>,
+    condition: &str,
+    from: &str,
+    to: &str,
+) -> Result<(), DbErr> {
+    manager
+        .exec_stmt(
+            Query::update()
+                .table(Rules::Table)
+                .value(Rules::Method, to)
+                .and_where(Expr::col(Rules::Condition).eq(condition))
+                .and_where(Expr::col(Rules::Method).eq(from))
+                .to_owned(),
+        )
+        .await
+}
+
+#[derive(DeriveIden)]
+enum Rules {
+    Table,
+    Condition,
+    Method,
+}
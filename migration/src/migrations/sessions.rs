@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Sessions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Sessions::Id)
+                            .integer()
+                            .auto_increment()
+                            .primary_key()
+                            .not_null()
+                            .comment("会话 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::SubjectType)
+                            .string()
+                            .not_null()
+                            .comment("会话主体类型：admin 管理员；user 商城用户"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::SubjectId)
+                            .integer()
+                            .not_null()
+                            .comment("会话主体 ID"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::FamilyId)
+                            .string()
+                            .not_null()
+                            .comment("令牌家族 ID，同一次登录轮换出的会话共用"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::RefreshTokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key()
+                            .comment("刷新令牌的 SHA-256 哈希"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::ExpiresAt)
+                            .big_integer()
+                            .not_null()
+                            .comment("刷新令牌过期时间（Unix 时间戳）"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::RevokeReason)
+                            .string()
+                            .null()
+                            .comment("吊销原因：rotated 已轮换；logout 退出登录；reuse_detected 检测到重用；admin_revoked 被管理员吊销；account_disabled 账号被禁用"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::UserAgent)
+                            .string()
+                            .null()
+                            .comment("客户端 User-Agent"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::Ip)
+                            .string()
+                            .null()
+                            .comment("客户端 IP"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::CreateTime)
+                            .string()
+                            .not_null()
+                            .comment("创建时间"),
+                    )
+                    .col(
+                        ColumnDef::new(Sessions::UpdateTime)
+                            .string()
+                            .not_null()
+                            .comment("更新时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sessions_subject")
+                    .table(Sessions::Table)
+                    .col(Sessions::SubjectType)
+                    .col(Sessions::SubjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Sessions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Sessions {
+    Table,
+    Id,
+    SubjectType,
+    SubjectId,
+    FamilyId,
+    RefreshTokenHash,
+    ExpiresAt,
+    RevokeReason,
+    UserAgent,
+    Ip,
+    CreateTime,
+    UpdateTime,
+}
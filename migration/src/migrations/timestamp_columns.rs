@@ -0,0 +1,91 @@
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 保存为 `YYYY-MM-DD HH:MM:SS` 字符串（UTC）的时间列
+const COLUMNS: &[(&str, &[&str])] = &[
+    ("admins", &["create_time", "update_time"]),
+    ("audit_logs", &["create_time"]),
+    (
+        "coupons",
+        &["start_time", "end_time", "create_time", "update_time"],
+    ),
+    ("goods", &["create_time", "update_time", "delete_time"]),
+    ("image_classes", &["create_time", "update_time"]),
+    ("images", &["create_time", "update_time"]),
+    ("login_attempts", &["create_time", "update_time"]),
+    ("notices", &["create_time", "update_time"]),
+    ("password_reset_tokens", &["create_time"]),
+    ("roles", &["create_time", "update_time"]),
+    ("rules", &["create_time", "update_time"]),
+    ("sessions", &["create_time", "update_time"]),
+    ("skus", &["create_time", "update_time"]),
+    ("user_levels", &["create_time", "update_time"]),
+    ("users", &["create_time", "update_time"]),
+];
+
+/// 为每张表生成一条语句，`column` 为每列生成一个子句
+fn statements(
+    table_sql: impl Fn(&str, String) -> String,
+    column: impl Fn(&str) -> String,
+) -> Vec<String> {
+    COLUMNS
+        .iter()
+        .map(|(table, columns)| {
+            let clauses: Vec<String> = columns.iter().map(|name| column(name)).collect();
+            table_sql(table, clauses.join(", "))
+        })
+        .collect()
+}
+
+/// 时间列由字符串改为带时区的时间戳
+///
+/// - PostgreSQL：列类型改为 `timestamptz`，原有数据按 UTC 转换
+/// - SQLite：列类型不变，数据改写为 RFC 3339 格式，与 SeaORM 写入的格式一致，以便按时间比较
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sqls = match manager.get_database_backend() {
+            DatabaseBackend::Postgres => statements(
+                |table, clauses| format!("ALTER TABLE {table} {clauses}"),
+                |name| {
+                    format!(
+                        "ALTER COLUMN {name} TYPE timestamptz USING {name}::timestamp AT TIME ZONE 'UTC'"
+                    )
+                },
+            ),
+            _ => statements(
+                |table, clauses| format!("UPDATE {table} SET {clauses}"),
+                |name| format!("{name} = strftime('%Y-%m-%dT%H:%M:%S+00:00', {name})"),
+            ),
+        };
+        let db = manager.get_connection();
+        for sql in sqls {
+            db.execute_unprepared(&sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sqls = match manager.get_database_backend() {
+            DatabaseBackend::Postgres => statements(
+                |table, clauses| format!("ALTER TABLE {table} {clauses}"),
+                |name| {
+                    format!(
+                        "ALTER COLUMN {name} TYPE varchar USING to_char({name} AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS')"
+                    )
+                },
+            ),
+            _ => statements(
+                |table, clauses| format!("UPDATE {table} SET {clauses}"),
+                |name| format!("{name} = strftime('%Y-%m-%d %H:%M:%S', {name})"),
+            ),
+        };
+        let db = manager.get_connection();
+        for sql in sqls {
+            db.execute_unprepared(&sql).await?;
+        }
+        Ok(())
+    }
+}
@@ -2,9 +2,9 @@ use serde::Serialize;
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{openapi, Modify, OpenApi};
 
-use crate::entities::admins::Model as AdminModel;
 use crate::entities::rules::Model as RuleModel;
-use crate::models::admins::CreateAdmin;
+use crate::models::admins::{AdminInfo, CreateAdmin};
+use crate::models::users::UserInfo;
 
 use crate::handlers;
 
@@ -17,37 +17,59 @@ use crate::handlers;
         handlers::rules::update_rule,
         handlers::rules::update_rule_status,
         handlers::rules::delete_rule,
+        handlers::admins::auth::login,
+        handlers::admins::auth::refresh,
+        handlers::admins::auth::logout,
+        handlers::admins::auth::request_password_reset,
+        handlers::admins::auth::confirm_password_reset,
+        handlers::admins::auth::get_current_admin,
+        handlers::admins::auth::get_current_admin_menus,
+        handlers::admins::get_admin_detail,
         handlers::admins::create_admin,
         handlers::admins::update_admin,
         handlers::admins::delete_admin,
         handlers::admins::update_admin_status,
+        handlers::admins::unlock_admin,
+        handlers::admins::sessions::get_admin_sessions,
+        handlers::admins::sessions::revoke_admin_session,
+        handlers::admins::sessions::revoke_admin_sessions,
         handlers::notices::get_notice_list,
         handlers::notices::create_notice,
+        handlers::notices::get_notice,
         handlers::notices::update_notice,
         handlers::notices::delete_notice,
         handlers::roles::get_role_list,
         handlers::roles::create_role,
+        handlers::roles::get_role,
         handlers::roles::update_role,
         handlers::roles::delete_role,
         handlers::roles::update_role_status,
         handlers::roles::update_role_rule_ids,
+        handlers::roles::get_role_rules,
+        handlers::roles::grant_role_rule,
+        handlers::roles::revoke_role_rule,
         handlers::users::get_user_list,
+        handlers::users::get_user_detail,
         handlers::users::create_user,
         handlers::users::update_user,
         handlers::users::update_user_status,
+        handlers::users::unlock_user,
         handlers::users::delete_user,
         handlers::skus::get_sku_list,
         handlers::skus::create_sku,
+        handlers::skus::get_sku,
         handlers::skus::update_sku,
         handlers::skus::update_sku_status,
         handlers::skus::delete_all_sku,
         handlers::coupons::get_coupon_list,
         handlers::coupons::create_coupon,
+        handlers::coupons::get_coupon,
         handlers::coupons::update_coupon,
         handlers::coupons::update_coupon_status,
         handlers::coupons::delete_coupon,
         handlers::user_levels::get_user_level_list,
         handlers::user_levels::create_user_level,
+        handlers::user_levels::get_user_level,
         handlers::user_levels::update_user_level,
         handlers::user_levels::update_user_level_status,
         handlers::user_levels::delete_user_level,
@@ -57,6 +79,7 @@ use crate::handlers;
         handlers::image_classes::get_image_class_list,
         handlers::image_classes::get_image_list_by_image_class_id,
         handlers::image_classes::create_image_class,
+        handlers::image_classes::get_image_class,
         handlers::image_classes::update_image_class,
         handlers::image_classes::delete_image_class,
         handlers::goods::create_good,
@@ -68,9 +91,20 @@ use crate::handlers;
         handlers::goods::batch_restore_good,
         handlers::goods::update_good_is_check,
         handlers::goods::batch_delete_good,
+        handlers::shop::users::register,
+        handlers::shop::users::login,
+        handlers::shop::users::refresh,
+        handlers::shop::users::logout,
+        handlers::shop::users::update_password,
+        handlers::shop::users::request_password_reset,
+        handlers::shop::users::confirm_password_reset,
+        handlers::audit_logs::get_audit_log_list,
+        handlers::health::healthz,
+        handlers::health::readyz,
+        handlers::metrics::get_metrics,
     ), 
     components(
-        schemas(RuleModel, CreateAdmin, AdminModel),
+        schemas(RuleModel, CreateAdmin, AdminInfo, UserInfo),
     ),
     tags(
         (name = "admins", description = "管理员管理 API"),
@@ -90,13 +124,16 @@ use crate::handlers;
         (name = "orders", description = "订单管理 API"),
         (name = "sys_configs", description = "系统配置管理 API"),
         (name = "agents", description = "分销管理 API"),
+        (name = "shop", description = "商城用户 API"),
+        (name = "audit_logs", description = "操作日志 API"),
+        (name = "health", description = "健康检查 API"),
+        (name = "metrics", description = "监控指标 API"),
     ),
     modifiers(&Foo),
     security(
         ("api_key1" = ["edit:items", "read:items"], "api_key2" = ["edit:items", "read:items"]),
     )
 )]
-
 pub struct ApiDoc;
 
 
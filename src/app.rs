@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    middleware::from_fn,
+    web, App, Error,
+};
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{
+    api_docs::index::ApiDoc,
+    config::{AppConfig, LoginGuardConfig, UploadConfig},
+    middlewares::{
+        audit::audit, metrics::metrics, permission::permission, request_id::request_id,
+    },
+    routers::{
+        admins::build_admin_router, audit_logs::build_audit_log_router,
+        coupons::build_coupon_router, goods::build_good_router, health::build_health_router,
+        image_classes::build_image_class_router, images::build_image_router,
+        metrics::build_metrics_router, notices::build_notice_router, roles::build_role_router,
+        rules::build_rule_router, shop::build_shop_router, skus::build_sku_router,
+        user_levels::build_user_level_router, users::build_user_router,
+    },
+    utils::{
+        mailer::Mailer,
+        metrics::Metrics,
+        token::{AdminTokenKeys, TokenKeys, UserTokenKeys},
+        validation::{json_error_handler, path_error_handler, query_error_handler},
+    },
+};
+
+/// 各个 worker 共享的应用状态
+///
+/// `serve` 命令与集成测试都通过 [`build_app`] 使用同一份状态构建应用
+#[derive(Clone)]
+pub struct AppState {
+    pub db: web::Data<DatabaseConnection>,
+    pub metrics: web::Data<Metrics>,
+    pub admin_token_keys: web::Data<AdminTokenKeys>,
+    pub user_token_keys: web::Data<UserTokenKeys>,
+    pub upload_config: web::Data<UploadConfig>,
+    pub login_guard_config: web::Data<LoginGuardConfig>,
+    pub mailer: web::Data<dyn Mailer>,
+}
+
+impl AppState {
+    pub fn new(
+        config: &AppConfig,
+        mut db: DatabaseConnection,
+        mailer: Arc<dyn Mailer>,
+    ) -> Result<Self, prometheus::Error> {
+        let metrics = web::Data::new(Metrics::new()?);
+        let query_metrics = metrics.clone();
+        db.set_metric_callback(move |info| query_metrics.observe_query(info));
+
+        Ok(AppState {
+            db: web::Data::new(db),
+            metrics,
+            admin_token_keys: web::Data::new(AdminTokenKeys(TokenKeys::new(
+                config.token.admin_secret.as_bytes(),
+                config.token.admin_expires_in_secs,
+                config.token.admin_refresh_expires_in_secs,
+            ))),
+            user_token_keys: web::Data::new(UserTokenKeys(TokenKeys::new(
+                config.token.user_secret.as_bytes(),
+                config.token.user_expires_in_secs,
+                config.token.user_refresh_expires_in_secs,
+            ))),
+            upload_config: web::Data::new(config.upload.clone()),
+            login_guard_config: web::Data::new(config.login_guard.clone()),
+            mailer: web::Data::from(mailer),
+        })
+    }
+}
+
+/// 构建包含所有路由与中间件的应用
+pub fn build_app(
+    state: &AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .wrap(from_fn(metrics))
+        .wrap(from_fn(request_id))
+        .app_data(state.db.clone())
+        .app_data(state.metrics.clone())
+        .app_data(state.admin_token_keys.clone())
+        .app_data(state.user_token_keys.clone())
+        .app_data(state.login_guard_config.clone())
+        .app_data(state.mailer.clone())
+        .app_data(state.upload_config.clone())
+        // 请求参数解析失败时同样返回统一的错误格式
+        .app_data(web::JsonConfig::default().error_handler(json_error_handler))
+        .app_data(web::QueryConfig::default().error_handler(query_error_handler))
+        .app_data(web::PathConfig::default().error_handler(path_error_handler))
+        .configure(build_health_router)
+        .configure(build_metrics_router)
+        // 需注册在 `/api` 之前，否则会被后台权限中间件拦截
+        .service(build_shop_router())
+        .service(
+            web::scope("/api")
+                // 后注册的中间件先执行：先校验权限，再记录审计日志
+                .wrap(from_fn(audit))
+                .wrap(from_fn(permission))
+                .service(build_rule_router())
+                .service(build_admin_router())
+                .service(build_notice_router())
+                .service(build_role_router())
+                .service(build_user_router())
+                .service(build_sku_router())
+                .service(build_coupon_router())
+                .service(build_user_level_router())
+                .service(build_image_router())
+                .service(build_image_class_router())
+                .service(build_good_router())
+                .service(build_audit_log_router()),
+        )
+        .service(
+            SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi()),
+        )
+}
@@ -0,0 +1,60 @@
+use clap::Subcommand;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::DatabaseConnection;
+
+use super::CommandResult;
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// 应用未执行的迁移
+    Up {
+        /// 最多应用的迁移数，默认全部
+        #[arg(short = 'n', long)]
+        num: Option<u32>,
+    },
+    /// 回滚已执行的迁移
+    Down {
+        /// 回滚的迁移数
+        #[arg(short = 'n', long, default_value_t = 1)]
+        num: u32,
+    },
+    /// 列出所有迁移及其状态
+    Status,
+    /// 删除所有数据表后重新执行全部迁移
+    Fresh {
+        /// 确认删除所有数据
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+impl MigrateCommand {
+    pub async fn run(self, db: &DatabaseConnection) -> CommandResult {
+        match self {
+            MigrateCommand::Up { num } => {
+                let pending = Migrator::get_pending_migrations(db).await?.len();
+                Migrator::up(db, num).await?;
+                let applied = num.map_or(pending, |num| pending.min(num as usize));
+                println!("已应用 {applied} 个迁移");
+            }
+            MigrateCommand::Down { num } => {
+                let applied = Migrator::get_applied_migrations(db).await?.len();
+                Migrator::down(db, Some(num)).await?;
+                println!("已回滚 {} 个迁移", applied.min(num as usize));
+            }
+            MigrateCommand::Status => {
+                for migration in Migrator::get_migration_with_status(db).await? {
+                    println!("{:<8} {}", migration.status().to_string(), migration.name());
+                }
+            }
+            MigrateCommand::Fresh { yes } => {
+                if !yes {
+                    return Err("该命令会删除所有数据，确认执行请加上 --yes".into());
+                }
+                Migrator::fresh(db).await?;
+                println!("已重建数据库");
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use std::error::Error;
+
+use clap::{Parser, Subcommand};
+use sea_orm::DatabaseConnection;
+
+use crate::config::AppConfig;
+
+mod migrate;
+mod seed;
+mod serve;
+mod superadmin;
+
+pub type CommandResult = Result<(), Box<dyn Error>>;
+
+/// 商城后台服务
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// 未指定子命令时等同于 `serve`
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 启动 HTTP 服务
+    Serve {
+        /// 启动前不执行未应用的迁移
+        #[arg(long)]
+        no_migrate: bool,
+    },
+    /// 管理数据库迁移
+    #[command(subcommand)]
+    Migrate(migrate::MigrateCommand),
+    /// 导入初始数据
+    #[command(subcommand)]
+    Seed(seed::SeedCommand),
+    /// 创建超级管理员
+    CreateSuperadmin(superadmin::CreateSuperadminArgs),
+}
+
+impl Cli {
+    pub async fn run(self, config: AppConfig) -> CommandResult {
+        match self.command {
+            None => serve::run(config, false).await,
+            Some(Command::Serve { no_migrate }) => serve::run(config, no_migrate).await,
+            Some(Command::Migrate(command)) => command.run(&connect(&config).await?).await,
+            Some(Command::Seed(command)) => command.run(&connect(&config).await?).await,
+            Some(Command::CreateSuperadmin(args)) => args.run(&connect(&config).await?).await,
+        }
+    }
+}
+
+async fn connect(config: &AppConfig) -> Result<DatabaseConnection, Box<dyn Error>> {
+    sea_orm::Database::connect(config.database.connect_options())
+        .await
+        .map_err(|err| format!("无法连接数据库：{err}").into())
+}
@@ -0,0 +1,239 @@
+use chrono::Duration;
+use clap::Subcommand;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    PaginatorTrait, TransactionTrait,
+};
+
+use crate::{
+    entities::{
+        coupons, goods, image_classes, notices, roles,
+        sea_orm_active_enums::{
+            CheckStatus, CouponType, GoodStatus, SkuType, Status, StockDisplay,
+        },
+        skus, user_levels, users,
+    },
+    handlers::rules::utils::seed_rules,
+    utils::{money::Money, password::hash_password, time},
+};
+
+use super::CommandResult;
+
+/// 演示用户的密码
+const DEMO_USER_PASSWORD: &str = "demo123456";
+
+#[derive(Debug, Subcommand)]
+pub enum SeedCommand {
+    /// 导入内置的权限规则（编译时嵌入的 `src/assets/rule.json`），已有规则时跳过
+    Rules,
+    /// 导入演示数据：角色、会员等级、用户、公告、规格、图库、商品与优惠券，已有数据的表会被跳过
+    Demo,
+}
+
+impl SeedCommand {
+    pub async fn run(self, db: &DatabaseConnection) -> CommandResult {
+        match self {
+            SeedCommand::Rules => match seed_rules(db).await? {
+                Some(inserted) => println!("已导入 {inserted} 条规则"),
+                None => println!("规则已存在，跳过"),
+            },
+            SeedCommand::Demo => seed_demo(db).await?,
+        }
+        Ok(())
+    }
+}
+
+/// 表为空时插入数据，否则跳过
+async fn seed_table<E, A>(
+    db: &impl TransactionTrait,
+    table: &str,
+    rows: Vec<A>,
+) -> Result<(), DbErr>
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<A> + Sync,
+    A: ActiveModelTrait<Entity = E> + Send,
+{
+    let txn = db.begin().await?;
+    if E::find().count(&txn).await? > 0 {
+        println!("{table}：已有数据，跳过");
+        return Ok(());
+    }
+    let count = rows.len();
+    E::insert_many(rows).exec_without_returning(&txn).await?;
+    txn.commit().await?;
+    println!("{table}：已插入 {count} 条");
+    Ok(())
+}
+
+async fn seed_demo(db: &DatabaseConnection) -> CommandResult {
+    // insert_many 不会调用 before_save，时间需要显式设置
+    let now = time::now();
+    let time = || Set(now);
+
+    seed_table(
+        db,
+        "roles",
+        vec![roles::ActiveModel {
+            name: Set(String::from("运营")),
+            status: Set(Status::Enabled),
+            desc: Set(String::from("演示角色")),
+            create_time: time(),
+            update_time: time(),
+            ..Default::default()
+        }],
+    )
+    .await?;
+
+    seed_table(
+        db,
+        "user_levels",
+        [("普通会员", 100, 10), ("黄金会员", 200, 9)]
+            .into_iter()
+            .map(|(name, level, discount)| user_levels::ActiveModel {
+                name: Set(String::from(name)),
+                level: Set(level),
+                status: Set(Status::Enabled),
+                discount: Set(discount),
+                max_price: Set(Money::new(1000, 0)),
+                max_time: Set(500),
+                create_time: time(),
+                update_time: time(),
+                ..Default::default()
+            })
+            .collect(),
+    )
+    .await?;
+
+    let password_hash = hash_password(DEMO_USER_PASSWORD).map_err(|err| err.to_string())?;
+    let user_level_id = user_levels::Entity::find()
+        .one(db)
+        .await?
+        .map(|user_level| user_level.id);
+    seed_table(
+        db,
+        "users",
+        vec![users::ActiveModel {
+            username: Set(String::from("demo")),
+            password: Set(password_hash),
+            status: Set(Status::Enabled),
+            user_level_id: Set(user_level_id),
+            nickname: Set(Some(String::from("演示用户"))),
+            phone: Set(None),
+            email: Set(Some(String::from("demo@example.com"))),
+            avatar: Set(None),
+            create_time: time(),
+            update_time: time(),
+            ..Default::default()
+        }],
+    )
+    .await?;
+
+    seed_table(
+        db,
+        "notices",
+        vec![notices::ActiveModel {
+            title: Set(String::from("欢迎使用")),
+            content: Set(String::from("这是一条演示公告")),
+            create_time: time(),
+            update_time: time(),
+            ..Default::default()
+        }],
+    )
+    .await?;
+
+    seed_table(
+        db,
+        "skus",
+        [("颜色", "红色,黑色,白色"), ("尺码", "S,M,L,XL")]
+            .into_iter()
+            .map(|(name, default)| skus::ActiveModel {
+                name: Set(String::from(name)),
+                r#type: Set(SkuType::Text),
+                status: Set(Status::Enabled),
+                order: Set(50),
+                default: Set(String::from(default)),
+                create_time: time(),
+                update_time: time(),
+                ..Default::default()
+            })
+            .collect(),
+    )
+    .await?;
+
+    seed_table(
+        db,
+        "image_classes",
+        vec![image_classes::ActiveModel {
+            name: Set(String::from("商品图片")),
+            order: Set(50),
+            create_time: time(),
+            update_time: time(),
+            ..Default::default()
+        }],
+    )
+    .await?;
+
+    seed_table(
+        db,
+        "goods",
+        [
+            ("演示商品 T 恤", "件", Money::new(59, 0), Money::new(99, 0)),
+            (
+                "演示商品 咖啡豆",
+                "kg",
+                Money::new(88, 0),
+                Money::new(128, 0),
+            ),
+        ]
+        .into_iter()
+        .map(
+            |(title, unit, min_price, min_ori_price)| goods::ActiveModel {
+                title: Set(String::from(title)),
+                category_id: Set(0),
+                cover: Set(String::new()),
+                desc: Set(String::from("演示商品描述")),
+                unit: Set(String::from(unit)),
+                stock: Set(200),
+                min_stock: Set(10),
+                status: Set(GoodStatus::Listed),
+                stock_display: Set(StockDisplay::Shown),
+                min_price: Set(min_price),
+                min_ori_price: Set(min_ori_price),
+                is_check: Set(CheckStatus::Approved),
+                create_time: time(),
+                update_time: time(),
+                delete_time: Set(None),
+                ..Default::default()
+            },
+        )
+        .collect(),
+    )
+    .await?;
+
+    let end_time = now + Duration::days(30);
+    seed_table(
+        db,
+        "coupons",
+        vec![coupons::ActiveModel {
+            name: Set(String::from("满 100 减 20")),
+            r#type: Set(CouponType::Reduction),
+            value: Set(Money::new(20, 0)),
+            total: Set(100),
+            used: Set(0),
+            min_price: Set(Money::new(100, 0)),
+            start_time: time(),
+            end_time: Set(end_time),
+            status: Set(Status::Enabled),
+            order: Set(50),
+            desc: Set(String::from("演示优惠券")),
+            create_time: time(),
+            update_time: time(),
+            ..Default::default()
+        }],
+    )
+    .await?;
+
+    println!("演示用户 demo 的密码为 {DEMO_USER_PASSWORD}");
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use actix_web::HttpServer;
+use migration::{Migrator, MigratorTrait};
+
+use crate::{
+    app::{build_app, AppState},
+    config::AppConfig,
+    utils::mailer::{FileMailer, Mailer, SmtpMailer},
+};
+
+use super::{connect, CommandResult};
+
+/// 启动 HTTP 服务，默认先应用未执行的迁移
+pub async fn run(config: AppConfig, no_migrate: bool) -> CommandResult {
+    std::fs::create_dir_all(&config.upload.dir)?;
+
+    let connect = connect(&config).await?;
+    if !no_migrate {
+        Migrator::up(&connect, None).await?;
+    }
+
+    // 配置了 SMTP 时通过 SMTP 发送邮件，否则写入发件箱目录
+    let mailer: Arc<dyn Mailer> = if config.mail.smtp_url.is_empty() {
+        Arc::new(FileMailer::new(&config.mail.outbox_dir))
+    } else {
+        Arc::new(SmtpMailer::new(&config.mail.smtp_url, &config.mail.from)?)
+    };
+
+    let state = AppState::new(&config, connect, mailer)?;
+
+    tracing::info!("http://{}/swagger-ui/", config.server.bind);
+    let mut server = HttpServer::new(move || build_app(&state));
+    if let Some(workers) = config.server.workers {
+        server = server.workers(workers);
+    }
+    server.bind(&config.server.bind)?.run().await?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::io::BufRead;
+
+use clap::Args;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use validator::Validate;
+
+use crate::{
+    entities::{admins, sea_orm_active_enums::Status},
+    utils::password::hash_password,
+};
+
+use super::CommandResult;
+
+#[derive(Debug, Args)]
+pub struct CreateSuperadminArgs {
+    /// 用户名
+    #[arg(short, long)]
+    username: String,
+    /// 密码，未指定时从标准输入读取一行，避免密码出现在命令历史中
+    #[arg(short, long)]
+    password: Option<String>,
+    /// 邮箱，用于找回密码
+    #[arg(short, long)]
+    email: Option<String>,
+}
+
+/// 要创建的超级管理员，校验规则与创建管理员接口的
+/// [`CreateAdmin`](crate::models::admins::CreateAdmin) 一致
+#[derive(Debug, Validate)]
+struct NewSuperadmin {
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    username: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    password: String,
+    #[validate(email(message = "邮箱格式不正确"))]
+    email: Option<String>,
+}
+
+impl CreateSuperadminArgs {
+    pub async fn run(self, db: &DatabaseConnection) -> CommandResult {
+        let password = match self.password {
+            Some(password) => password,
+            None => {
+                eprintln!("请输入密码：");
+                let mut line = String::new();
+                std::io::stdin().lock().read_line(&mut line)?;
+                line.trim_end_matches(['\r', '\n']).to_string()
+            }
+        };
+        let admin_data = NewSuperadmin {
+            username: self.username,
+            password,
+            email: self.email,
+        };
+        admin_data
+            .validate()
+            .map_err(|errors| format!("参数不合法：\n{errors}"))?;
+
+        let exists = admins::Entity::find()
+            .filter(admins::Column::Username.eq(admin_data.username.clone()))
+            .one(db)
+            .await?
+            .is_some();
+        if exists {
+            return Err(format!("管理员 {} 已存在", admin_data.username).into());
+        }
+
+        let password_hash = hash_password(&admin_data.password).map_err(|err| err.to_string())?;
+        let admin = admins::ActiveModel {
+            username: Set(admin_data.username),
+            password: Set(password_hash),
+            // 超级管理员拥有全部权限，不属于任何角色
+            role_id: Set(None),
+            status: Set(Status::Enabled),
+            avatar: Set(None),
+            is_super: Set(1),
+            email: Set(admin_data.email),
+            ..Default::default()
+        };
+        let admin = admin.insert(db).await?;
+        println!("已创建超级管理员 {}（ID {}）", admin.username, admin.id);
+        Ok(())
+    }
+}
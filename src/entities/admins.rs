@@ -1,25 +1,55 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use super::sea_orm_active_enums::Status;
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "admins")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub username: String,
+    #[serde(skip_serializing)]
     pub password: String,
-    pub role_id: i32,
-    pub create_time: String,
-    pub update_time: String,
-    pub status: i32,
+    pub role_id: Option<i32>,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+    pub status: Status,
     pub avatar: Option<String>,
     pub is_super: i32,
+    pub email: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roles::Entity",
+        from = "Column::RoleId",
+        to = "super::roles::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Roles,
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::roles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Roles.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
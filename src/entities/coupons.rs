@@ -1,32 +1,45 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, ToSchema, Serialize)]
+use super::sea_orm_active_enums::{CouponType, Status};
+use crate::utils::{money::Money, time::set_timestamps};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "coupons")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
-    pub r#type: i32,
+    pub r#type: CouponType,
     #[sea_orm(column_type = "Double")]
-    pub value: f64,
+    pub value: Money,
     pub total: i32,
     pub used: i32,
     #[sea_orm(column_type = "Double")]
-    pub min_price: f64,
-    pub start_time: String,
-    pub end_time: String,
-    pub status: i32,
+    pub min_price: Money,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub status: Status,
     pub order: i32,
     pub desc: String,
-    pub create_time: String,
-    pub update_time: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
@@ -1,9 +1,13 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use super::sea_orm_active_enums::{CheckStatus, GoodStatus, StockDisplay};
+use crate::utils::{money::Money, time::set_timestamps};
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "goods")]
 pub struct Model {
@@ -16,17 +20,26 @@ pub struct Model {
     pub unit: String,
     pub stock: i32,
     pub min_stock: i32,
-    pub status: i32,
-    pub stock_display: i32,
-    pub min_price: String,
-    pub min_ori_price: String,
-    pub is_check: i32,
-    pub create_time: String,
-    pub update_time: String,
-    pub delete_time: Option<String>,
+    pub status: GoodStatus,
+    pub stock_display: StockDisplay,
+    pub min_price: Money,
+    pub min_ori_price: Money,
+    pub is_check: CheckStatus,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+    pub delete_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
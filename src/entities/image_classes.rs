@@ -1,9 +1,12 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "image_classes")]
 pub struct Model {
@@ -11,11 +14,29 @@ pub struct Model {
     pub id: i32,
     pub name: String,
     pub order: i32,
-    pub create_time: String,
-    pub update_time: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::images::Entity")]
+    Images,
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::images::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Images.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
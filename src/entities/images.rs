@@ -1,9 +1,12 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "images")]
 pub struct Model {
@@ -12,12 +15,36 @@ pub struct Model {
     pub name: String,
     pub url: String,
     pub path: String,
-    pub image_class_id: i32,
-    pub create_time: String,
-    pub update_time: String,
+    pub image_class_id: Option<i32>,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::image_classes::Entity",
+        from = "Column::ImageClassId",
+        to = "super::image_classes::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    ImageClasses,
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::image_classes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ImageClasses.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
@@ -3,13 +3,19 @@
 pub mod prelude;
 
 pub mod admins;
+pub mod audit_logs;
 pub mod coupons;
 pub mod goods;
 pub mod image_classes;
 pub mod images;
+pub mod login_attempts;
 pub mod notices;
+pub mod password_reset_tokens;
+pub mod role_rules;
 pub mod roles;
 pub mod rules;
+pub mod sea_orm_active_enums;
+pub mod sessions;
 pub mod skus;
 pub mod user_levels;
 pub mod users;
@@ -1,13 +1,20 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+#![allow(unused_imports)]
+
 pub use super::admins::Entity as Admins;
+pub use super::audit_logs::Entity as AuditLogs;
 pub use super::coupons::Entity as Coupons;
 pub use super::goods::Entity as Goods;
 pub use super::image_classes::Entity as ImageClasses;
 pub use super::images::Entity as Images;
+pub use super::login_attempts::Entity as LoginAttempts;
 pub use super::notices::Entity as Notices;
+pub use super::password_reset_tokens::Entity as PasswordResetTokens;
+pub use super::role_rules::Entity as RoleRules;
 pub use super::roles::Entity as Roles;
 pub use super::rules::Entity as Rules;
+pub use super::sessions::Entity as Sessions;
 pub use super::skus::Entity as Skus;
 pub use super::user_levels::Entity as UserLevels;
 pub use super::users::Entity as Users;
@@ -1,23 +1,62 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use super::sea_orm_active_enums::Status;
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "roles")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
-    pub status: i32,
+    pub status: Status,
     pub desc: String,
-    pub rule_ids: Option<Vec<i32>>,
-    pub create_time: String,
-    pub update_time: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::admins::Entity")]
+    Admins,
+    #[sea_orm(has_many = "super::role_rules::Entity")]
+    RoleRules,
+}
+
+impl Related<super::admins::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Admins.def()
+    }
+}
+
+impl Related<super::role_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RoleRules.def()
+    }
+}
+
+impl Related<super::rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::role_rules::Relation::Rules.def()
+    }
 
-impl ActiveModelBehavior for ActiveModel {}
+    fn via() -> Option<RelationDef> {
+        Some(super::role_rules::Relation::Roles.def().rev())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
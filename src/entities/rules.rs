@@ -1,9 +1,13 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use super::sea_orm_active_enums::{RuleMenu, Status};
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "rules")]
 pub struct Model {
@@ -11,18 +15,46 @@ pub struct Model {
     pub id: i32,
     pub name: String,
     pub rule_id: i32,
-    pub status: i32,
-    pub create_time: String,
-    pub update_time: String,
+    pub status: Status,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
     pub front_path: String,
     pub condition: String,
-    pub menu: i32,
+    pub menu: RuleMenu,
     pub order: i32,
     pub icon: String,
     pub method: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::role_rules::Entity")]
+    RoleRules,
+}
+
+impl Related<super::role_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RoleRules.def()
+    }
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::roles::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::role_rules::Relation::Roles.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::role_rules::Relation::Rules.def().rev())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
@@ -0,0 +1,147 @@
+//! 整数列对应的枚举，数据库与 JSON 中均保存为整数
+
+use sea_orm::entity::prelude::*;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use utoipa::ToSchema;
+
+/// 启用状态：0 禁用；1 启用
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum Status {
+    Disabled = 0,
+    Enabled = 1,
+}
+
+/// 商品上架状态：0 未上架；1 已上架
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum GoodStatus {
+    Unlisted = 0,
+    Listed = 1,
+}
+
+/// 库存显示：0 隐藏；1 显示
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum StockDisplay {
+    Hidden = 0,
+    Shown = 1,
+}
+
+/// 商品审核状态：0 未审核；1 同意；2 拒绝
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum CheckStatus {
+    Pending = 0,
+    Approved = 1,
+    Rejected = 2,
+}
+
+/// 优惠券类型：0 满减；1 折扣
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum CouponType {
+    Reduction = 0,
+    Discount = 1,
+}
+
+/// 规格类型：0 文字；1 颜色；2 图片
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum SkuType {
+    Text = 0,
+    Color = 1,
+    Image = 2,
+}
+
+/// 规则类型：0 仅用于权限校验；1 同时作为菜单显示
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize_repr,
+    Deserialize_repr,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[repr(i32)]
+pub enum RuleMenu {
+    Permission = 0,
+    Menu = 1,
+}
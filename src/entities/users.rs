@@ -1,27 +1,56 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 use utoipa::ToSchema;
 
+use super::sea_orm_active_enums::Status;
+use crate::utils::time::set_timestamps;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, ToSchema, Serialize)]
 #[sea_orm(table_name = "users")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub username: String,
+    #[serde(skip_serializing)]
     pub password: String,
-    pub status: i32,
-    pub user_level_id: i32,
+    pub status: Status,
+    pub user_level_id: Option<i32>,
     pub nickname: Option<String>,
     pub phone: Option<String>,
     pub email: Option<String>,
     pub avatar: Option<String>,
-    pub create_time: String,
-    pub update_time: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user_levels::Entity",
+        from = "Column::UserLevelId",
+        to = "super::user_levels::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    UserLevels,
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::user_levels::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserLevels.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        set_timestamps(&mut self.create_time, Some(&mut self.update_time), insert);
+        Ok(self)
+    }
+}
@@ -0,0 +1,482 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::{
+    config::LoginGuardConfig,
+    entities::{
+        admins, rules,
+        sea_orm_active_enums::{RuleMenu, Status},
+        sessions,
+    },
+    handlers::rules::utils::{build_rule_tree, with_ancestors},
+    middlewares::permission::find_role_rules,
+    models::{
+        admins::{AdminInfo, AdminLoginReq, AdminLoginRes, AdminMenuRes},
+        password_resets::{PasswordResetConfirmReq, PasswordResetRequestReq},
+        sessions::RefreshTokenReq,
+    },
+    utils::{
+        error::ApiError,
+        login_guard::{check_locked, clear_failures, client_ip, record_failure},
+        mailer::Mailer,
+        password::{hash_password_async, verify_login_password_async},
+        password_reset::{consume_reset_token, spawn_reset_mail, RESET_THROTTLE_ADMIN},
+        response::{response_t, ResponseT},
+        session::{
+            create_session, revoke_session, revoke_subject_sessions, rotate_session, ClientInfo,
+            RotateResult, REVOKE_ACCOUNT_DISABLED, REVOKE_LOGOUT, REVOKE_PASSWORD_RESET,
+            SUBJECT_ADMIN,
+        },
+        token::{AdminClaims, AdminTokenKeys, CurrentAdmin},
+        validation::ValidatedJson,
+    },
+};
+
+/// 为会话签发访问令牌，并与刷新令牌一起返回
+fn issue_tokens(
+    keys: &AdminTokenKeys,
+    admin: &admins::Model,
+    session: &sessions::Model,
+    refresh_token: String,
+) -> Result<HttpResponse, ApiError> {
+    let claims = AdminClaims::new(admin, session.id, keys.0.expires_in);
+    let token = keys.0.encode(&claims).map_err(ApiError::internal)?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(AdminLoginRes {
+            token,
+            token_type: String::from("Bearer"),
+            expires_in: keys.0.expires_in,
+            refresh_token,
+            refresh_expires_in: keys.0.refresh_expires_in,
+        }),
+        None,
+    )))
+}
+
+/// 管理员登录
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `username` ：字符串，用户名（必填）
+/// - `password` ：字符串，密码（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，访问令牌与刷新令牌
+/// - 失败：状态码 401，用户名或密码错误
+/// - 失败：状态码 403，管理员已被禁用
+/// - 失败：状态码 429，失败次数过多，需按 `Retry-After` 等待后重试
+/// - 失败：状态码 500
+///
+/// # 示例
+///
+/// ```
+/// POST /api/admins/login
+/// Connect-Type: application/json
+///
+/// {
+///       "username": "admin",
+///       "password": "xxxxxxxxxx"
+/// }
+/// ```
+#[utoipa::path(
+    post,
+    path  = "/api/admins/login",
+    request_body = AdminLoginReq,
+    responses(
+        (status = 200, description = "登录成功", body = ResponseT<AdminLoginRes>),
+        (status = 401, description = "用户名或密码错误"),
+        (status = 403, description = "管理员已被禁用"),
+        (status = 429, description = "登录失败次数过多"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn login(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    keys: web::Data<AdminTokenKeys>,
+    guard_config: web::Data<LoginGuardConfig>,
+    login_data: web::Json<AdminLoginReq>,
+) -> Result<HttpResponse, ApiError> {
+    let ip = client_ip(&req);
+    if let Some(retry_after) =
+        check_locked(db.get_ref(), SUBJECT_ADMIN, &login_data.username, &ip).await?
+    {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+
+    let admin = admins::Entity::find()
+        .filter(admins::Column::Username.eq(login_data.username.clone()))
+        .one(db.get_ref())
+        .await?;
+    let verified = verify_login_password_async(
+        &login_data.password,
+        admin.as_ref().map(|admin| admin.password.as_str()),
+    )
+    .await?;
+    let Some(admin) = admin.filter(|_| verified) else {
+        record_failure(
+            db.get_ref(),
+            &guard_config,
+            SUBJECT_ADMIN,
+            &login_data.username,
+            &ip,
+        )
+        .await?;
+        return Err(ApiError::unauthorized("用户名或密码错误"));
+    };
+    clear_failures(db.get_ref(), SUBJECT_ADMIN, &admin.username).await?;
+    if admin.status == Status::Disabled {
+        return Err(ApiError::forbidden("管理员已被禁用"));
+    }
+
+    let (session, refresh_token) = create_session(
+        db.get_ref(),
+        SUBJECT_ADMIN,
+        admin.id,
+        None,
+        keys.0.refresh_expires_in,
+        &ClientInfo::from_request(&req),
+    )
+    .await?;
+    issue_tokens(&keys, &admin, &session, refresh_token)
+}
+
+/// 刷新管理员令牌
+///
+/// 每个刷新令牌只能使用一次，成功后返回新的访问令牌与刷新令牌。
+/// 已使用过的刷新令牌再次出现时，视为泄露并吊销同一登录产生的全部会话
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `refresh_token` ：字符串，刷新令牌（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，新的访问令牌与刷新令牌
+/// - 失败：状态码 401，刷新令牌无效、已过期或已被使用
+/// - 失败：状态码 403，管理员已被禁用
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/admins/refresh",
+    request_body = RefreshTokenReq,
+    responses(
+        (status = 200, description = "刷新成功", body = ResponseT<AdminLoginRes>),
+        (status = 401, description = "刷新令牌无效"),
+        (status = 403, description = "管理员已被禁用"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn refresh(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    keys: web::Data<AdminTokenKeys>,
+    refresh_data: web::Json<RefreshTokenReq>,
+) -> Result<HttpResponse, ApiError> {
+    let rotate_result = rotate_session(
+        db.get_ref(),
+        SUBJECT_ADMIN,
+        &refresh_data.refresh_token,
+        keys.0.refresh_expires_in,
+        &ClientInfo::from_request(&req),
+    )
+    .await?;
+    let (session, refresh_token) = match rotate_result {
+        RotateResult::Rotated(session, refresh_token) => (session, refresh_token),
+        RotateResult::Invalid => return Err(ApiError::unauthorized("刷新令牌无效或已过期")),
+        RotateResult::Reused => {
+            return Err(ApiError::unauthorized(
+                "刷新令牌已被使用，相关会话已全部吊销",
+            ))
+        }
+    };
+
+    let admin = admins::Entity::find_by_id(session.subject_id)
+        .one(db.get_ref())
+        .await?
+        .filter(|admin| admin.status == Status::Enabled);
+    let Some(admin) = admin else {
+        revoke_session(
+            db.get_ref(),
+            SUBJECT_ADMIN,
+            session.subject_id,
+            session.id,
+            REVOKE_ACCOUNT_DISABLED,
+        )
+        .await?;
+        return Err(ApiError::forbidden("管理员已被禁用"));
+    };
+    issue_tokens(&keys, &admin, &session, refresh_token)
+}
+
+/// 管理员退出登录
+///
+/// 吊销当前会话，其访问令牌与刷新令牌随即失效
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 401，令牌缺失、无效或已过期
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/admins/logout",
+    responses(
+        (status = 200, description = "退出成功", body = ResponseT<String>),
+        (status = 401, description = "未登录"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn logout(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
+) -> Result<HttpResponse, ApiError> {
+    revoke_session(
+        db.get_ref(),
+        SUBJECT_ADMIN,
+        current_admin.0.sub,
+        current_admin.0.sid,
+        REVOKE_LOGOUT,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 获取当前登录的管理员
+///
+/// # 请求头
+///
+/// - `Authorization` ：字符串，`Bearer <token>`（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，当前管理员（不含密码，附带所属角色）
+/// - 失败：状态码 401，令牌缺失、无效或已过期
+/// - 失败：状态码 404，管理员不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path  = "/api/admins/me",
+    responses(
+        (status = 200, description = "获取成功", body = ResponseT<AdminInfo>),
+        (status = 401, description = "未登录"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn get_current_admin(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
+) -> Result<HttpResponse, ApiError> {
+    let admin = admins::Entity::find_by_id(current_admin.0.sub)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("管理员不存在"))?;
+    let admin_info = AdminInfo::load(db.get_ref(), admin).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(admin_info), None)))
+}
+
+/// 获取当前管理员的菜单与权限
+///
+/// # 请求头
+///
+/// - `Authorization` ：字符串，`Bearer <token>`（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，包含以下字段：
+///   - `menus` ：角色可用的菜单树（`menu = 1` 且 `status = 1`），按 `order` 排序，超级管理员返回全部菜单。
+///     角色只拥有下级规则时，一并返回其上级菜单
+///   - `conditions` ：角色可用的全部权限别名，用于前端按钮级权限判断
+/// - 失败：状态码 401，令牌缺失、无效或已过期
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path  = "/api/admins/menus",
+    responses(
+        (status = 200, description = "获取成功", body = ResponseT<AdminMenuRes>),
+        (status = 401, description = "未登录"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn get_current_admin_menus(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
+) -> Result<HttpResponse, ApiError> {
+    let enabled_rules = rules::Entity::find().filter(rules::Column::Status.eq(Status::Enabled));
+    let rule_list = if current_admin.0.is_super == 1 {
+        enabled_rules.clone().all(db.get_ref()).await?
+    } else {
+        find_role_rules(db.get_ref(), current_admin.0.role_id).await?
+    };
+
+    let mut conditions: Vec<String> = rule_list
+        .iter()
+        .filter(|rule| !rule.condition.is_empty())
+        .map(|rule| rule.condition.clone())
+        .collect();
+    conditions.sort();
+    conditions.dedup();
+    // 角色只拥有下级规则时，补全其上级菜单，否则这些菜单无法挂到菜单树上
+    let mut rule_list = if current_admin.0.is_super == 1 {
+        rule_list
+    } else {
+        with_ancestors(rule_list, &enabled_rules.all(db.get_ref()).await?)
+    };
+    rule_list.sort_by_key(|rule| rule.order);
+    let menu_list = rule_list
+        .into_iter()
+        .filter(|rule| rule.menu == RuleMenu::Menu)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(AdminMenuRes {
+            menus: build_rule_tree(menu_list, 0),
+            conditions,
+        }),
+        None,
+    )))
+}
+
+/// 申请重置管理员密码
+///
+/// 管理员存在、已启用且设置了邮箱时，在后台生成一次性的重置令牌并发送到该邮箱。
+/// 为避免泄露账号是否存在，响应不等待发信，无论是否发送邮件、发送是否成功都返回成功。
+/// 同一用户名或 IP 频繁申请时，按登录限流的配置等待或锁定
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `username` ：字符串，用户名（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 429，申请过于频繁
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/admins/password_reset/request",
+    request_body = PasswordResetRequestReq,
+    responses(
+        (status = 200, description = "申请成功", body = ResponseT<String>),
+        (status = 429, description = "申请过于频繁"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn request_password_reset(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    mailer: web::Data<dyn Mailer>,
+    guard_config: web::Data<LoginGuardConfig>,
+    reset_data: web::Json<PasswordResetRequestReq>,
+) -> Result<HttpResponse, ApiError> {
+    // 无论账号是否存在都计入申请次数，限流结果同样不会泄露账号是否存在
+    let ip = client_ip(&req);
+    if let Some(retry_after) = check_locked(
+        db.get_ref(),
+        RESET_THROTTLE_ADMIN,
+        &reset_data.username,
+        &ip,
+    )
+    .await?
+    {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+    record_failure(
+        db.get_ref(),
+        &guard_config,
+        RESET_THROTTLE_ADMIN,
+        &reset_data.username,
+        &ip,
+    )
+    .await?;
+
+    let admin = admins::Entity::find()
+        .filter(admins::Column::Username.eq(reset_data.username.clone()))
+        .one(db.get_ref())
+        .await?
+        .filter(|admin| admin.status == Status::Enabled);
+    let recipient = admin.and_then(|admin| {
+        let email = admin.email.filter(|email| !email.is_empty())?;
+        Some((admin.id, email))
+    });
+
+    if let Some((admin_id, email)) = recipient {
+        spawn_reset_mail(db, mailer, SUBJECT_ADMIN, admin_id, email);
+    }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 使用重置令牌设置新的管理员密码
+///
+/// 成功后令牌作废，该管理员的全部会话被吊销，登录失败记录被清除
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `token` ：字符串，邮件中的重置令牌（必填）
+/// - `new_password` ：字符串，新密码（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，令牌无效、已过期或已使用
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/admins/password_reset/confirm",
+    request_body = PasswordResetConfirmReq,
+    responses(
+        (status = 200, description = "密码重置成功", body = ResponseT<String>),
+        (status = 400, description = "重置令牌无效"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn confirm_password_reset(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    reset_data: ValidatedJson<PasswordResetConfirmReq>,
+) -> Result<HttpResponse, ApiError> {
+    let invalid_token = || ApiError::validation("重置令牌无效或已过期");
+    let admin_id = consume_reset_token(db.get_ref(), SUBJECT_ADMIN, &reset_data.token)
+        .await?
+        .ok_or_else(invalid_token)?;
+    let admin = admins::Entity::find_by_id(admin_id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(invalid_token)?;
+    let password_hash = hash_password_async(&reset_data.new_password).await?;
+    let username = admin.username.clone();
+
+    let mut updated_admin: admins::ActiveModel = admin.into();
+    updated_admin.password = Set(password_hash);
+    updated_admin.update(db.get_ref()).await?;
+    revoke_subject_sessions(db.get_ref(), SUBJECT_ADMIN, admin_id, REVOKE_PASSWORD_RESET).await?;
+    clear_failures(db.get_ref(), SUBJECT_ADMIN, &username).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
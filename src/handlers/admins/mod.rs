@@ -1,13 +1,82 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
 use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
 
 use crate::{
-    entities::admins::{self, ActiveModel, Model},
-    models::admins::{CreateAdmin, UpdateAdminStatus},
-    utils::response::{response_t, ResponseT},
+    entities::{
+        admins::{self, ActiveModel},
+        roles,
+        sea_orm_active_enums::Status,
+    },
+    models::admins::{AdminInfo, CreateAdmin, UpdateAdminStatus},
+    utils::{
+        error::ApiError,
+        login_guard::clear_failures,
+        password::hash_password_async,
+        response::{response_t, ResponseT},
+        session::{revoke_subject_sessions, REVOKE_ACCOUNT_DISABLED, SUBJECT_ADMIN},
+        token::{AdminClaims, CurrentAdmin},
+        validation::{validate_reference, ValidatedJson},
+    },
 };
 
+pub mod auth;
+pub mod sessions;
+
+/// 查找要修改、删除或吊销会话的管理员，只有超级管理员可以操作超级管理员
+async fn find_managed_admin(
+    db: &sea_orm::DatabaseConnection,
+    current_admin: &AdminClaims,
+    id: i32,
+) -> Result<Option<admins::Model>, ApiError> {
+    let admin = admins::Entity::find_by_id(id).one(db).await?;
+    if admin
+        .as_ref()
+        .is_some_and(|admin| admin.is_super == 1 && current_admin.is_super != 1)
+    {
+        return Err(ApiError::forbidden("只有超级管理员可以操作超级管理员"));
+    }
+    Ok(admin)
+}
+
+/// 查看管理员资料
+///
+/// # 路径
+///
+/// - `id` ：整数，管理员ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，管理员及其所属角色，角色已被删除时 `role` 为空
+/// - 失败：状态码 404，管理员不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path  = "/api/admins/manager/{id}",
+    responses(
+        (status = 200, description = "管理员获取成功", body = ResponseT<AdminInfo>),
+        (status = 404, description = "管理员不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn get_admin_detail(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i16>,
+) -> Result<HttpResponse, ApiError> {
+    let (admin, role) = admins::Entity::find_by_id(*id)
+        .find_also_related(roles::Entity)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("管理员不存在"))?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(AdminInfo::with_role(admin, role)),
+        None,
+    )))
+}
+
 /// 创建新管理员
 ///
 /// # 请求体
@@ -18,11 +87,13 @@ use crate::{
 /// - `role_id` ：整数，角色表 ID（必填）
 /// - `status` ：整数，管理员状态（必填），0 禁用；1 可用
 /// - `avatar` ：字符串，头像
+/// - `email` ：字符串，邮箱，用于接收重置密码邮件
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的管理员
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，新创建的管理员（不含密码，附带所属角色）
+/// - 失败：状态码 400，请求参数不合法或角色不存在
+/// - 失败：状态码 409，用户名已存在
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -33,6 +104,7 @@ use crate::{
 ///
 /// {
 ///       "avatar": null,
+///       "email": "admin2@example.com",
 ///       "password": "xxxxxxxxxx",
 ///       "role_id": 38,
 ///       "status": 1,
@@ -44,46 +116,42 @@ use crate::{
     path  = "/api/admins/manager",
     request_body = CreateAdmin,
     responses(
-        (status = 200, description = "管理员创建成功", body = ResponseT<Model>),
+        (status = 200, description = "管理员创建成功", body = ResponseT<AdminInfo>),
+        (status = 400, description = "请求参数不合法或角色不存在"),
+        (status = 409, description = "用户名已存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "admins"
 )]
 pub async fn create_admin(
     db: web::Data<sea_orm::DatabaseConnection>,
-    admin_data: web::Json<CreateAdmin>,
-) -> impl Responder {
+    admin_data: ValidatedJson<CreateAdmin>,
+) -> Result<HttpResponse, ApiError> {
     let admin_result = admins::Entity::find()
         .filter(admins::Column::Username.eq(admin_data.username.clone()))
         .one(db.get_ref())
-        .await;
-    if let Ok(Some(_)) = admin_result {
-        return HttpResponse::Ok().json(response_t::<()>(
-            Some(201),
-            None,
-            Some(String::from("username已存在")),
-        ));
+        .await?;
+    if admin_result.is_some() {
+        return Err(ApiError::conflict("username已存在"));
     }
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    validate_reference::<roles::Entity>(db.get_ref(), admin_data.role_id, "role_id", "角色不存在")
+        .await?;
+    let password_hash = hash_password_async(&admin_data.password).await?;
 
     let new_admin = ActiveModel {
         username: Set(admin_data.username.clone()),
-        password: Set(admin_data.password.clone()),
-        role_id: Set(admin_data.role_id),
+        password: Set(password_hash),
+        role_id: Set(Some(admin_data.role_id)),
         status: Set(admin_data.status),
         avatar: Set(admin_data.avatar.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
+        email: Set(admin_data.email.clone()),
         is_super: Set(0),
         ..Default::default()
     };
-    let result = new_admin.insert(db.get_ref()).await;
+    let new_admin = new_admin.insert(db.get_ref()).await?;
+    let admin_info = AdminInfo::load(db.get_ref(), new_admin).await?;
 
-    match result {
-        Ok(new_admin) => HttpResponse::Ok().json(response_t(Some(200), Some(new_admin), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(admin_info), None)))
 }
 
 /// 修改管理员
@@ -100,11 +168,15 @@ pub async fn create_admin(
 /// - `role_id` ：整数，角色表 ID（必填）
 /// - `status` ：整数，管理员状态（必填），0 禁用；1 可用
 /// - `avatar` ：字符串，头像
+/// - `email` ：字符串，邮箱，用于接收重置密码邮件
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的管理员
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法或角色不存在
+/// - 失败：状态码 403，非超级管理员修改超级管理员
+/// - 失败：状态码 404，管理员不存在
+/// - 失败：状态码 409，用户名已存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -112,41 +184,53 @@ pub async fn create_admin(
     path  = "/api/admins/manager/{id}",
     request_body = CreateAdmin,
     responses(
-        (status = 200, description = "管理员更新成功", body = ResponseT<Model>),
+        (status = 200, description = "管理员更新成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法或角色不存在"),
+        (status = 403, description = "非超级管理员修改超级管理员"),
+        (status = 404, description = "管理员不存在"),
+        (status = 409, description = "用户名已存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "admins"
 )]
 pub async fn update_admin(
     db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
     id: web::Path<i16>,
-    admin_data: web::Json<CreateAdmin>,
-) -> impl Responder {
-    let admin_result = admins::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match admin_result {
-        Ok(Some(admin)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_admin: ActiveModel = admin.into();
-            updated_admin.username = Set(admin_data.username.clone());
-            updated_admin.role_id = Set(admin_data.role_id);
-            updated_admin.status = Set(admin_data.status);
-            updated_admin.avatar = Set(admin_data.avatar.clone());
-            updated_admin.update_time = Set(format_time.clone());
-            let result = updated_admin.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
+    admin_data: ValidatedJson<CreateAdmin>,
+) -> Result<HttpResponse, ApiError> {
+    let admin = find_managed_admin(db.get_ref(), &current_admin.0, i32::from(*id))
+        .await?
+        .ok_or_else(|| ApiError::not_found("管理员不存在"))?;
+    let duplicate = admins::Entity::find()
+        .filter(admins::Column::Username.eq(admin_data.username.clone()))
+        .filter(admins::Column::Id.ne(admin.id))
+        .one(db.get_ref())
+        .await?;
+    if duplicate.is_some() {
+        return Err(ApiError::conflict("username已存在"));
+    }
+    validate_reference::<roles::Entity>(db.get_ref(), admin_data.role_id, "role_id", "角色不存在")
+        .await?;
+
+    let mut updated_admin: ActiveModel = admin.into();
+    updated_admin.username = Set(admin_data.username.clone());
+    updated_admin.role_id = Set(Some(admin_data.role_id));
+    updated_admin.status = Set(admin_data.status);
+    updated_admin.avatar = Set(admin_data.avatar.clone());
+    updated_admin.email = Set(admin_data.email.clone());
+    let admin = updated_admin.update(db.get_ref()).await?;
+    if admin.status == Status::Disabled {
+        revoke_subject_sessions(
+            db.get_ref(),
+            SUBJECT_ADMIN,
+            admin.id,
+            REVOKE_ACCOUNT_DISABLED,
+        )
+        .await?;
     }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除管理员
@@ -158,26 +242,35 @@ pub async fn update_admin(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 403，非超级管理员删除超级管理员
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
     delete,
     path  = "/api/admins/manager/{id}",
     responses(
-        (status = 200, description = "管理员删除成功", body = ResponseT<Model>),
+        (status = 200, description = "管理员删除成功", body = ResponseT<String>),
+        (status = 403, description = "非超级管理员删除超级管理员"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "admins"
 )]
 pub async fn delete_admin(
     db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
     id: web::Path<i16>,
-) -> impl Responder {
-    let result = admins::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+) -> Result<HttpResponse, ApiError> {
+    find_managed_admin(db.get_ref(), &current_admin.0, i32::from(*id)).await?;
+    admins::Entity::delete_by_id(*id).exec(db.get_ref()).await?;
+    revoke_subject_sessions(
+        db.get_ref(),
+        SUBJECT_ADMIN,
+        i32::from(*id),
+        REVOKE_ACCOUNT_DISABLED,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改管理员状态
@@ -189,12 +282,14 @@ pub async fn delete_admin(
 /// # 请求体
 ///
 /// 需要一个 JSON 对象，包含以下字段：
-/// - `status` ：整数，管理员状态（必填），0 禁用；1 可用
+/// - `status` ：整数，管理员状态（必填），0 禁用；1 可用。禁用时会吊销该管理员的全部会话
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的管理员
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 403，非超级管理员修改超级管理员
+/// - 失败：状态码 404，管理员不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -203,35 +298,72 @@ pub async fn delete_admin(
     request_body = UpdateAdminStatus,
     responses(
         (status = 200, description = "管理员状态修改成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 403, description = "非超级管理员修改超级管理员"),
+        (status = 404, description = "管理员不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "admins"
 )]
 pub async fn update_admin_status(
     db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
     id: web::Path<i16>,
-    admin_data: web::Json<UpdateAdminStatus>,
-) -> impl Responder {
-    let admin_result = admins::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match admin_result {
-        Ok(Some(admin)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_admin: ActiveModel = admin.into();
-            updated_admin.status = Set(admin_data.status);
-            updated_admin.update_time = Set(format_time.clone());
-            let result = updated_admin.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
+    admin_data: ValidatedJson<UpdateAdminStatus>,
+) -> Result<HttpResponse, ApiError> {
+    let admin = find_managed_admin(db.get_ref(), &current_admin.0, i32::from(*id))
+        .await?
+        .ok_or_else(|| ApiError::not_found("管理员不存在"))?;
+
+    let mut updated_admin: ActiveModel = admin.into();
+    updated_admin.status = Set(admin_data.status);
+    let admin = updated_admin.update(db.get_ref()).await?;
+    if admin.status == Status::Disabled {
+        revoke_subject_sessions(
+            db.get_ref(),
+            SUBJECT_ADMIN,
+            admin.id,
+            REVOKE_ACCOUNT_DISABLED,
+        )
+        .await?;
     }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 解除管理员的登录锁定
+///
+/// 清除该管理员用户名的登录失败记录，按 IP 的限制不受影响
+///
+/// # 路径
+///
+/// - `id` ：整数，管理员ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 404，管理员不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    patch,
+    path  = "/api/admins/manager/{id}/unlock",
+    responses(
+        (status = 200, description = "管理员解锁成功", body = ResponseT<String>),
+        (status = 404, description = "管理员不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "admins"
+)]
+pub async fn unlock_admin(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i16>,
+) -> Result<HttpResponse, ApiError> {
+    let admin = admins::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("管理员不存在"))?;
+    clear_failures(db.get_ref(), SUBJECT_ADMIN, &admin.username).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
@@ -0,0 +1,138 @@
+use actix_web::{web, HttpResponse};
+
+use crate::{
+    models::sessions::SessionInfo,
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseT},
+        session::{
+            find_active_sessions, revoke_session, revoke_subject_sessions, REVOKE_ADMIN_REVOKED,
+            SUBJECT_ADMIN,
+        },
+        token::CurrentAdmin,
+    },
+};
+
+use super::find_managed_admin;
+
+/// 获取管理员的有效会话列表
+///
+/// # 路径
+///
+/// - `id` ：整数，管理员ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，未吊销且未过期的会话，最新的在前
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path  = "/api/admins/manager/{id}/sessions",
+    responses(
+        (status = 200, description = "获取成功", body = ResponseT<Vec<SessionInfo>>),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn get_admin_sessions(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let session_list = find_active_sessions(db.get_ref(), SUBJECT_ADMIN, *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(
+            session_list
+                .into_iter()
+                .map(SessionInfo::from)
+                .collect::<Vec<_>>(),
+        ),
+        None,
+    )))
+}
+
+/// 吊销管理员的某个会话
+///
+/// # 路径
+///
+/// - `id` ：整数，管理员ID（必填）
+/// - `session_id` ：整数，会话ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 403，非超级管理员吊销超级管理员的会话
+/// - 失败：状态码 404，会话不存在或已被吊销
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    delete,
+    path  = "/api/admins/manager/{id}/sessions/{session_id}",
+    responses(
+        (status = 200, description = "会话吊销成功", body = ResponseT<String>),
+        (status = 403, description = "非超级管理员吊销超级管理员的会话"),
+        (status = 404, description = "会话不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn revoke_admin_session(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (id, session_id) = path.into_inner();
+    find_managed_admin(db.get_ref(), &current_admin.0, id).await?;
+    let rows_affected = revoke_session(
+        db.get_ref(),
+        SUBJECT_ADMIN,
+        id,
+        session_id,
+        REVOKE_ADMIN_REVOKED,
+    )
+    .await?;
+    if rows_affected == 0 {
+        return Err(ApiError::not_found("会话不存在或已被吊销"));
+    }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 吊销管理员的全部会话
+///
+/// # 路径
+///
+/// - `id` ：整数，管理员ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，被吊销的会话数
+/// - 失败：状态码 403，非超级管理员吊销超级管理员的会话
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    delete,
+    path  = "/api/admins/manager/{id}/sessions",
+    responses(
+        (status = 200, description = "会话吊销成功", body = ResponseT<u64>),
+        (status = 403, description = "非超级管理员吊销超级管理员的会话"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "admins"
+)]
+pub async fn revoke_admin_sessions(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_admin: CurrentAdmin,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    find_managed_admin(db.get_ref(), &current_admin.0, *id).await?;
+    let rows_affected =
+        revoke_subject_sessions(db.get_ref(), SUBJECT_ADMIN, *id, REVOKE_ADMIN_REVOKED).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(rows_affected), None)))
+}
@@ -0,0 +1,83 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::{
+    entities::audit_logs::{self, Model},
+    models::{audit_logs::GetAuditLogListReq, Pagination},
+    services::pagination::{paginate, SortFields},
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::ValidatedQuery,
+    },
+};
+
+const SORT_FIELDS: SortFields<audit_logs::Column> = SortFields {
+    columns: &[("create_time", audit_logs::Column::CreateTime)],
+    default: "-id",
+};
+
+/// 获取操作日志列表
+///
+/// # 查询参数
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`create_time`，默认按 `-id`
+///
+/// - `admin_id` ：整数，操作的管理员 ID
+/// - `target` ：字符串，操作的数据表
+/// - `method` ：字符串，请求方法
+/// - `outcome` ：字符串，操作结果，success 成功；failure 失败
+/// - `start_time` ：字符串，起始时间，RFC 3339 格式
+/// - `end_time` ：字符串，结束时间，RFC 3339 格式
+///
+/// # 响应
+///
+/// - 成功：状态码 200，操作日志
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/audit_logs",
+    params(
+        Pagination,
+        GetAuditLogListReq
+    ),
+    responses(
+        (status = 200, description = "操作日志列表获取成功", body = ResponseT<ResponseListT<Model>>),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "audit_logs"
+)]
+pub async fn get_audit_log_list(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    pagination: ValidatedQuery<Pagination>,
+    query: web::Query<GetAuditLogListReq>,
+) -> Result<HttpResponse, ApiError> {
+    let mut select = audit_logs::Entity::find();
+    if let Some(admin_id) = query.admin_id {
+        select = select.filter(audit_logs::Column::AdminId.eq(admin_id));
+    }
+    if let Some(target) = query.target.clone() {
+        select = select.filter(audit_logs::Column::Target.eq(target));
+    }
+    if let Some(method) = query.method.clone() {
+        select = select.filter(audit_logs::Column::Method.eq(method.to_uppercase()));
+    }
+    if let Some(outcome) = query.outcome.clone() {
+        select = select.filter(audit_logs::Column::Outcome.eq(outcome));
+    }
+    if let Some(start_time) = query.start_time {
+        select = select.filter(audit_logs::Column::CreateTime.gte(start_time));
+    }
+    if let Some(end_time) = query.end_time {
+        select = select.filter(audit_logs::Column::CreateTime.lte(end_time));
+    }
+    let audit_log_list = paginate(db.get_ref(), select, &pagination, &SORT_FIELDS).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(audit_log_list)),
+        None,
+    )))
+}
@@ -1,20 +1,99 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
+use async_trait::async_trait;
 use sea_orm::{
-    ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    EntityTrait, PaginatorTrait,
+    DatabaseConnection,
 };
 
 use crate::{
     entities::coupons::{self, ActiveModel, Model},
     models::{
-        coupons::{CreateCouponReq, UpdateCouponReq, UpdateCouponStatusReq},
+        coupons::{validate_coupon_usage, CreateCouponReq, UpdateCouponReq, UpdateCouponStatusReq},
         Pagination,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource, StatusResource},
+        pagination::SortFields,
+    },
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::{validate_time_range, ValidatedJson, ValidatedQuery},
+    },
 };
 
+#[async_trait]
+impl CrudResource for coupons::Entity {
+    type CreateReq = CreateCouponReq;
+    type UpdateReq = UpdateCouponReq;
+
+    const NOT_FOUND: &'static str = "优惠券不存在";
+
+    const SORT_FIELDS: SortFields<coupons::Column> = SortFields {
+        columns: &[
+            ("order", coupons::Column::Order),
+            ("value", coupons::Column::Value),
+            ("start_time", coupons::Column::StartTime),
+            ("end_time", coupons::Column::EndTime),
+            ("create_time", coupons::Column::CreateTime),
+        ],
+        default: "id",
+    };
+
+    fn new_model(data: &CreateCouponReq) -> ActiveModel {
+        ActiveModel {
+            name: Set(data.name.clone()),
+            r#type: Set(data.r#type),
+            value: Set(data.value),
+            total: Set(data.total),
+            used: Set(data.used),
+            min_price: Set(data.min_price),
+            start_time: Set(data.start_time),
+            end_time: Set(data.end_time),
+            order: Set(data.order),
+            status: Set(data.status),
+            desc: Set(data.desc.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(coupon: &mut ActiveModel, data: &UpdateCouponReq) {
+        coupon.name = data.name.clone().map(Set).unwrap_or(NotSet);
+        coupon.r#type = data.r#type.map(Set).unwrap_or(NotSet);
+        coupon.status = data.status.map(Set).unwrap_or(NotSet);
+        coupon.value = data.value.map(Set).unwrap_or(NotSet);
+        coupon.total = data.total.map(Set).unwrap_or(NotSet);
+        coupon.used = data.used.map(Set).unwrap_or(NotSet);
+        coupon.min_price = data.min_price.map(Set).unwrap_or(NotSet);
+        coupon.start_time = data.start_time.map(Set).unwrap_or(NotSet);
+        coupon.end_time = data.end_time.map(Set).unwrap_or(NotSet);
+        coupon.order = data.order.map(Set).unwrap_or(NotSet);
+        coupon.desc = data.desc.clone().map(Set).unwrap_or(NotSet);
+    }
+
+    /// 只修改其中一个字段时，与原数据组合后再校验
+    async fn before_update(
+        _db: &DatabaseConnection,
+        coupon: &Model,
+        data: &UpdateCouponReq,
+    ) -> Result<(), ApiError> {
+        let start_time = data.start_time.unwrap_or(coupon.start_time);
+        let end_time = data.end_time.unwrap_or(coupon.end_time);
+        validate_time_range(&start_time, &end_time)
+            .and_then(|_| {
+                validate_coupon_usage(
+                    data.used.unwrap_or(coupon.used),
+                    data.total.unwrap_or(coupon.total),
+                )
+            })
+            .map_err(|err| ApiError::validation(err.to_string()))
+    }
+}
+
+impl StatusResource for coupons::Entity {
+    const STATUS_COLUMN: coupons::Column = coupons::Column::Status;
+}
+
 /// 创建优惠券
 ///
 /// # 请求体
@@ -22,12 +101,12 @@ use crate::{
 /// 需要一个 JSON 对象，包含以下字段：
 /// - `name` ：字符串，优惠券名（必填）
 /// - `type` ：整数，类型：0 满减；1 折扣（必填）
-/// - `value` ：浮点数，面值（必填）
+/// - `value` ：字符串，面值，最多两位小数，小于 99999999.99（必填）
 /// - `total`：整数，发行量（必填）
 /// - `used`：整数，使用量（必填）
-/// - `min_price`：浮点数，最低使用价格（必填）
-/// - `start_time` ：字符串，开始时间（必填）
-/// - `end_time` ：字符串，结束时间（必填）
+/// - `min_price`：字符串，最低使用价格，最多两位小数，小于 99999999.99（必填）
+/// - `start_time` ：字符串，开始时间，RFC 3339 格式，如 `2022-06-11T20:14:35Z`（必填）
+/// - `end_time` ：字符串，结束时间，RFC 3339 格式（必填）
 /// - `order` ：整数，权重（必填）
 /// - `status` ：整数，用户状态（必填），0 禁用；1 可用
 /// - `desc` ：字符串，描述（必填）
@@ -35,7 +114,7 @@ use crate::{
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的优惠券
-/// - 失败：状态码 201，优惠券已存在
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -47,12 +126,12 @@ use crate::{
 /// {
 ///       "name": "优惠券名称",
 ///       "type": 0,
-///       "value": 20.00,
+///       "value": "20.00",
 ///       "total": 100,
 ///       "used": 50,
-///       "min_price": 50.00,
-///       "start_time": "2022-06-11 20:14:35",
-///       "end_time": "2022-10-05 14:01:15",
+///       "min_price": "50.00",
+///       "start_time": "2022-06-11T20:14:35Z",
+///       "end_time": "2022-10-05T14:01:15Z",
 ///       "order": 50,
 ///       "status": 0,
 ///       "desc": "描述",
@@ -64,39 +143,49 @@ use crate::{
     request_body = CreateCouponReq,
     responses(
         (status = 200, description = "优惠券创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "coupons"
 )]
 pub async fn create_coupon(
     db: web::Data<sea_orm::DatabaseConnection>,
-    coupon_data: web::Json<CreateCouponReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    coupon_data: ValidatedJson<CreateCouponReq>,
+) -> Result<HttpResponse, ApiError> {
+    let new_coupon = crud::create::<coupons::Entity>(db.get_ref(), &coupon_data).await?;
 
-    let new_coupon = ActiveModel {
-        name: Set(coupon_data.name.clone()),
-        r#type: Set(coupon_data.r#type),
-        value: Set(coupon_data.value),
-        total: Set(coupon_data.total),
-        used: Set(coupon_data.used),
-        min_price: Set(coupon_data.min_price),
-        start_time: Set(coupon_data.start_time.clone()),
-        end_time: Set(coupon_data.end_time.clone()),
-        order: Set(coupon_data.order),
-        status: Set(coupon_data.status),
-        desc: Set(coupon_data.desc.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_coupon.insert(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_coupon), None)))
+}
 
-    match result {
-        Ok(new_coupon) => HttpResponse::Ok().json(response_t(Some(200), Some(new_coupon), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+/// 获取优惠券详情
+///
+/// # 路径
+///
+/// - `id` ：整数，优惠券 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，优惠券
+/// - 失败：状态码 404，优惠券不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/coupons/{id}",
+    responses(
+        (status = 200, description = "优惠券获取成功", body = ResponseT<Model>),
+        (status = 404, description = "优惠券不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "coupons"
+)]
+pub async fn get_coupon(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let coupon = crud::find::<coupons::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(coupon), None)))
 }
 
 /// 修改优惠券
@@ -110,12 +199,12 @@ pub async fn create_coupon(
 /// 需要一个 JSON 对象，包含以下字段：
 /// - `name` ：字符串，优惠券名
 /// - `type` ：整数，类型，0 满减；1 折扣
-/// - `value` ：浮点数，面值
+/// - `value` ：字符串，面值，最多两位小数，小于 99999999.99
 /// - `total`：整数，发行量
 /// - `used`：整数，使用量
-/// - `min_price`：浮点数，最低使用价格
-/// - `start_time` ：字符串，开始时间
-/// - `end_time` ：字符串，结束时间
+/// - `min_price`：字符串，最低使用价格，最多两位小数，小于 99999999.99
+/// - `start_time` ：字符串，开始时间，RFC 3339 格式，如 `2022-06-11T20:14:35Z`
+/// - `end_time` ：字符串，结束时间，RFC 3339 格式
 /// - `order` ：整数，权重
 /// - `status` ：整数，用户状态，0 禁用；1 可用
 /// - `desc` ：字符串，描述
@@ -123,7 +212,8 @@ pub async fn create_coupon(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的优惠券
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，优惠券不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -132,47 +222,20 @@ pub async fn create_coupon(
     request_body = UpdateCouponReq,
     responses(
         (status = 200, description = "优惠券更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "优惠券不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "coupons"
 )]
 pub async fn update_coupon(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    coupon_data: web::Json<UpdateCouponReq>,
-) -> impl Responder {
-    let coupon_result = coupons::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    coupon_data: ValidatedJson<UpdateCouponReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<coupons::Entity>(db.get_ref(), *id, &coupon_data).await?;
 
-    match coupon_result {
-        Ok(Some(coupon)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_coupon: ActiveModel = coupon.into();
-            updated_coupon.name = coupon_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_coupon.r#type = coupon_data.r#type.clone().map(Set).unwrap_or(NotSet);
-            updated_coupon.status = coupon_data.status.map(Set).unwrap_or(NotSet);
-            updated_coupon.value = coupon_data.value.map(Set).unwrap_or(NotSet);
-            updated_coupon.total = coupon_data.total.map(Set).unwrap_or(NotSet);
-            updated_coupon.used = coupon_data.used.map(Set).unwrap_or(NotSet);
-            updated_coupon.min_price = coupon_data.min_price.map(Set).unwrap_or(NotSet);
-            updated_coupon.start_time = coupon_data.start_time.clone().map(Set).unwrap_or(NotSet);
-            updated_coupon.end_time = coupon_data.end_time.clone().map(Set).unwrap_or(NotSet);
-            updated_coupon.order = coupon_data.order.map(Set).unwrap_or(NotSet);
-            updated_coupon.desc = coupon_data.desc.clone().map(Set).unwrap_or(NotSet);
-            updated_coupon.update_time = Set(format_time.clone());
-            let result = updated_coupon.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改优惠券状态
@@ -189,7 +252,8 @@ pub async fn update_coupon(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，优惠券不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -198,37 +262,20 @@ pub async fn update_coupon(
     request_body = UpdateCouponStatusReq,
     responses(
         (status = 200, description = "优惠券更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "优惠券不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "coupons"
 )]
 pub async fn update_coupon_status(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    coupon_data: web::Json<UpdateCouponStatusReq>,
-) -> impl Responder {
-    let coupon_result = coupons::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    coupon_data: ValidatedJson<UpdateCouponStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update_status::<coupons::Entity>(db.get_ref(), *id, coupon_data.status).await?;
 
-    match coupon_result {
-        Ok(Some(coupon)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_coupon: ActiveModel = coupon.into();
-            updated_coupon.status = Set(coupon_data.status.clone());
-            updated_coupon.update_time = Set(format_time.clone());
-            let result = updated_coupon.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除优惠券
@@ -253,17 +300,19 @@ pub async fn update_coupon_status(
 )]
 pub async fn delete_coupon(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-) -> impl Responder {
-    let result = coupons::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete::<coupons::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取优惠券列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`order`、`value`、`start_time`、`end_time`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -283,26 +332,13 @@ pub async fn delete_coupon(
 )]
 pub async fn get_coupon_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let select = coupons::Entity::find();
-    let paginator = select.paginate(db.get_ref(), size);
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let coupon_list = crud::list::<coupons::Entity>(db.get_ref(), &query).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(role_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(role_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(coupon_list)),
+        None,
+    )))
 }
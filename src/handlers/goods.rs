@@ -1,19 +1,39 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
 use migration::Expr;
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    ColumnTrait, EntityTrait, QueryFilter,
 };
 
 use crate::{
-    entities::goods::{self, ActiveModel, Model},
-    models::goods::{
-        BatchDeleteGoodReq, BatchRestoreGoodReq, BatchSoftDeleteGoodReq, BatchUpdateStatusReq,
-        CreateGoodReq, CreateGoodRes, GetGoodListReq, UpdateGoodIsCheckReq, UpdateGoodReq,
+    entities::{
+        goods::{self, ActiveModel, Model},
+        sea_orm_active_enums::CheckStatus,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    models::{
+        goods::{
+            BatchDeleteGoodReq, BatchRestoreGoodReq, BatchSoftDeleteGoodReq, BatchUpdateStatusReq,
+            CreateGoodReq, CreateGoodRes, GetGoodListReq, UpdateGoodIsCheckReq, UpdateGoodReq,
+        },
+        Pagination,
+    },
+    services::pagination::{paginate, SortFields},
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        time::now,
+        validation::{ValidatedJson, ValidatedQuery},
+    },
+};
+
+const SORT_FIELDS: SortFields<goods::Column> = SortFields {
+    columns: &[
+        ("stock", goods::Column::Stock),
+        ("min_price", goods::Column::MinPrice),
+        ("create_time", goods::Column::CreateTime),
+    ],
+    default: "id",
 };
 
 /// 创建新商品
@@ -29,13 +49,14 @@ use crate::{
 /// - `stock` ：整数，总库存（必填）
 /// - `min_stock` ：整数，库存预警（必填）
 /// - `status` ：整数，上架（必填），0 禁用；1 可用
-/// - `stock_display` ：整数，库存显示（必填），0 隐藏；1 禁用
-/// - `min_price` ：整数，最低售价（必填）
-/// - `min_ori_price` ：整数，最低原价（必填）
+/// - `stock_display` ：整数，库存显示（必填），0 隐藏；1 显示
+/// - `min_price` ：字符串，最低售价，最多两位小数，小于 99999999.99（必填）
+/// - `min_ori_price` ：字符串，最低原价，最多两位小数，小于 99999999.99（必填）
 ///
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的商品
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -64,17 +85,15 @@ use crate::{
     request_body = CreateGoodReq,
     responses(
         (status = 200, description = "商品创建成功", body = ResponseT<CreateGoodRes>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
 )]
 pub async fn create_good(
     db: web::Data<sea_orm::DatabaseConnection>,
-    good_data: web::Json<CreateGoodReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-
+    good_data: ValidatedJson<CreateGoodReq>,
+) -> Result<HttpResponse, ApiError> {
     let new_good = ActiveModel {
         title: Set(good_data.title.clone()),
         category_id: Set(good_data.category_id),
@@ -85,19 +104,14 @@ pub async fn create_good(
         min_stock: Set(good_data.min_stock),
         status: Set(good_data.status),
         stock_display: Set(good_data.stock_display),
-        min_price: Set(good_data.min_price.clone()),
-        min_ori_price: Set(good_data.min_ori_price.clone()),
-        is_check: Set(0),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
+        min_price: Set(good_data.min_price),
+        min_ori_price: Set(good_data.min_ori_price),
+        is_check: Set(CheckStatus::Pending),
         ..Default::default()
     };
-    let result = new_good.insert(db.get_ref()).await;
+    let new_good = new_good.insert(db.get_ref()).await?;
 
-    match result {
-        Ok(new_good) => HttpResponse::Ok().json(response_t(Some(200), Some(new_good), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_good), None)))
 }
 
 /// 修改商品
@@ -117,13 +131,15 @@ pub async fn create_good(
 /// - `stock` ：整数，总库存
 /// - `min_stock` ：整数，库存预警
 /// - `status` ：整数，上架，0 禁用；1 可用
-/// - `stock_display` ：整数，库存显示，0 隐藏；1 禁用
-/// - `min_price` ：整数，最低售价
-/// - `min_ori_price` ：整数，最低原价
+/// - `stock_display` ：整数，库存显示，0 隐藏；1 显示
+/// - `min_price` ：字符串，最低售价，最多两位小数，小于 99999999.99
+/// - `min_ori_price` ：字符串，最低原价，最多两位小数，小于 99999999.99
 ///
 /// # 响应
 ///
 /// - 成功：状态码 200，修改之后的商品
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，商品不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -132,6 +148,8 @@ pub async fn create_good(
     request_body = UpdateGoodReq,
     responses(
         (status = 200, description = "商品更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "商品不存在"),
         (status = 500, description = "内部服务器错误"),
     ),
     tag = "goods"
@@ -139,39 +157,28 @@ pub async fn create_good(
 pub async fn update_good(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    good_data: web::Json<UpdateGoodReq>,
-) -> impl Responder {
-    let good_result = goods::Entity::find_by_id(*id).one(db.get_ref()).await;
-    match good_result {
-        Ok(Some(good)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut update_good: ActiveModel = good.into();
-            update_good.title = good_data.title.clone().map(Set).unwrap_or(NotSet);
-            update_good.category_id = good_data.category_id.map(Set).unwrap_or(NotSet);
-            update_good.cover = good_data.cover.clone().map(Set).unwrap_or(NotSet);
-            update_good.desc = good_data.desc.clone().map(Set).unwrap_or(NotSet);
-            update_good.unit = good_data.unit.clone().map(Set).unwrap_or(NotSet);
-            update_good.stock = good_data.stock.map(Set).unwrap_or(NotSet);
-            update_good.min_stock = good_data.min_stock.map(Set).unwrap_or(NotSet);
-            update_good.status = good_data.status.map(Set).unwrap_or(NotSet);
-            update_good.stock_display = good_data.stock_display.map(Set).unwrap_or(NotSet);
-            update_good.min_price = good_data.min_price.clone().map(Set).unwrap_or(NotSet);
-            update_good.min_ori_price = good_data.min_ori_price.clone().map(Set).unwrap_or(NotSet);
-            update_good.update_time = Set(format_time.clone());
-            let result = update_good.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    good_data: ValidatedJson<UpdateGoodReq>,
+) -> Result<HttpResponse, ApiError> {
+    let good = goods::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("商品不存在"))?;
+
+    let mut update_good: ActiveModel = good.into();
+    update_good.title = good_data.title.clone().map(Set).unwrap_or(NotSet);
+    update_good.category_id = good_data.category_id.map(Set).unwrap_or(NotSet);
+    update_good.cover = good_data.cover.clone().map(Set).unwrap_or(NotSet);
+    update_good.desc = good_data.desc.clone().map(Set).unwrap_or(NotSet);
+    update_good.unit = good_data.unit.clone().map(Set).unwrap_or(NotSet);
+    update_good.stock = good_data.stock.map(Set).unwrap_or(NotSet);
+    update_good.min_stock = good_data.min_stock.map(Set).unwrap_or(NotSet);
+    update_good.status = good_data.status.map(Set).unwrap_or(NotSet);
+    update_good.stock_display = good_data.stock_display.map(Set).unwrap_or(NotSet);
+    update_good.min_price = good_data.min_price.map(Set).unwrap_or(NotSet);
+    update_good.min_ori_price = good_data.min_ori_price.map(Set).unwrap_or(NotSet);
+    update_good.update(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 批量修改商品状态
@@ -185,6 +192,7 @@ pub async fn update_good(
 /// # 响应
 ///
 /// - 成功：状态码 200，修改之后的商品
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -193,34 +201,32 @@ pub async fn update_good(
     request_body = BatchUpdateStatusReq,
     responses(
         (status = 200, description = "商品更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误"),
     ),
     tag = "goods"
 )]
 pub async fn batch_update_good_status(
     db: web::Data<sea_orm::DatabaseConnection>,
-    good_data: web::Json<BatchUpdateStatusReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-    let good_result = goods::Entity::update_many()
+    good_data: ValidatedJson<BatchUpdateStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    let now = now();
+    goods::Entity::update_many()
         .col_expr(goods::Column::Status, Expr::value(good_data.status))
-        .col_expr(goods::Column::UpdateTime, Expr::value(format_time))
+        .col_expr(goods::Column::UpdateTime, Expr::value(now))
         .filter(goods::Column::Id.is_in(good_data.ids.clone()))
         .exec(db.get_ref())
-        .await;
-    if let Ok(_) = good_result {
-        return HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None));
-    }
-    return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取商品列表
 ///
 /// # 查询
 ///
-/// - `page` ：整数，页码（必填）
-/// - `size` ：整数，单页数量（必填）
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`stock`、`min_price`、`create_time`
+///
 /// - `tab` ：字符串，类型，all 全部；checking 审核中；selling 出售中；off 已下架；min_stock 库存预警；delete 回收站
 /// - `title` ：字符串，名称
 /// - `category_id` ：整数，商品分类 ID
@@ -234,6 +240,7 @@ pub async fn batch_update_good_status(
     get,
     path = "/api/goods",
     params(
+        Pagination,
         GetGoodListReq
     ),
     responses(
@@ -244,8 +251,9 @@ pub async fn batch_update_good_status(
 )]
 pub async fn get_good_list(
     db: web::Data<sea_orm::DatabaseConnection>,
+    pagination: ValidatedQuery<Pagination>,
     query: web::Query<GetGoodListReq>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let mut select = goods::Entity::find();
     if let Some(category_id) = query.category_id {
         select = select.filter(goods::Column::CategoryId.eq(category_id));
@@ -254,22 +262,13 @@ pub async fn get_good_list(
         let title_pattern = format!("%{}%", title);
         select = select.filter(goods::Column::Title.like(title_pattern));
     }
-    let paginator = select.paginate(db.get_ref(), query.size);
-
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(query.page - 1).await;
+    let good_list = paginate(db.get_ref(), select, &pagination, &SORT_FIELDS).await?;
 
-    match result {
-        Ok(role_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(role_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(good_list)),
+        None,
+    )))
 }
 
 /// 批量软删除商品
@@ -282,6 +281,7 @@ pub async fn get_good_list(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -290,26 +290,24 @@ pub async fn get_good_list(
     request_body = BatchSoftDeleteGoodReq,
     responses(
         (status = 200, description = "商品批量删除成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
 )]
 pub async fn batch_soft_delete_good(
     db: web::Data<sea_orm::DatabaseConnection>,
-    good_data: web::Json<BatchSoftDeleteGoodReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-    let good_result = goods::Entity::update_many()
-        .col_expr(goods::Column::UpdateTime, Expr::value(&format_time))
-        .col_expr(goods::Column::DeleteTime, Expr::value(&format_time))
+    good_data: ValidatedJson<BatchSoftDeleteGoodReq>,
+) -> Result<HttpResponse, ApiError> {
+    let now = now();
+    goods::Entity::update_many()
+        .col_expr(goods::Column::UpdateTime, Expr::value(now))
+        .col_expr(goods::Column::DeleteTime, Expr::value(now))
         .filter(goods::Column::Id.is_in(good_data.ids.clone()))
         .exec(db.get_ref())
-        .await;
-    if let Ok(_) = good_result {
-        return HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None));
-    }
-    return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 批量恢复商品
@@ -322,6 +320,7 @@ pub async fn batch_soft_delete_good(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -330,29 +329,27 @@ pub async fn batch_soft_delete_good(
     request_body = BatchRestoreGoodReq,
     responses(
         (status = 200, description = "商品批量恢复成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
 )]
 pub async fn batch_restore_good(
     db: web::Data<sea_orm::DatabaseConnection>,
-    good_data: web::Json<BatchRestoreGoodReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-    let good_result = goods::Entity::update_many()
-        .col_expr(goods::Column::UpdateTime, Expr::value(&format_time))
+    good_data: ValidatedJson<BatchRestoreGoodReq>,
+) -> Result<HttpResponse, ApiError> {
+    let now = now();
+    goods::Entity::update_many()
+        .col_expr(goods::Column::UpdateTime, Expr::value(now))
         .col_expr(
             goods::Column::DeleteTime,
             Expr::value(Option::<String>::None),
         )
         .filter(goods::Column::Id.is_in(good_data.ids.clone()))
         .exec(db.get_ref())
-        .await;
-    if let Ok(_) = good_result {
-        return HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None));
-    }
-    return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 批量删除商品
@@ -365,6 +362,7 @@ pub async fn batch_restore_good(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -373,22 +371,21 @@ pub async fn batch_restore_good(
     request_body = BatchDeleteGoodReq,
     responses(
         (status = 200, description = "商品删除成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
 )]
 pub async fn batch_delete_good(
     db: web::Data<sea_orm::DatabaseConnection>,
-    good_data: web::Json<BatchDeleteGoodReq>,
-) -> impl Responder {
-    let result = goods::Entity::delete_many()
+    good_data: ValidatedJson<BatchDeleteGoodReq>,
+) -> Result<HttpResponse, ApiError> {
+    goods::Entity::delete_many()
         .filter(goods::Column::Id.is_in(good_data.ids.clone()))
         .exec(db.get_ref())
-        .await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 审核商品
@@ -405,6 +402,8 @@ pub async fn batch_delete_good(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，商品不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -413,6 +412,8 @@ pub async fn batch_delete_good(
     request_body = UpdateGoodIsCheckReq,
     responses(
         (status = 200, description = "商品审核成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "商品不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
@@ -420,29 +421,18 @@ pub async fn batch_delete_good(
 pub async fn update_good_is_check(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    good_data: web::Json<UpdateGoodIsCheckReq>,
-) -> impl Responder {
-    let good_result = goods::Entity::find_by_id(*id).one(db.get_ref()).await;
-    match good_result {
-        Ok(Some(good)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut update_good: ActiveModel = good.into();
-            update_good.is_check = Set(good_data.is_check);
-            update_good.update_time = Set(format_time.clone());
-            let result = update_good.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    good_data: ValidatedJson<UpdateGoodIsCheckReq>,
+) -> Result<HttpResponse, ApiError> {
+    let good = goods::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("商品不存在"))?;
+
+    let mut update_good: ActiveModel = good.into();
+    update_good.is_check = Set(good_data.is_check);
+    update_good.update(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 查看商品资料
@@ -454,6 +444,7 @@ pub async fn update_good_is_check(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 404，商品不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -461,6 +452,7 @@ pub async fn update_good_is_check(
     path  = "/api/goods/{id}",
     responses(
         (status = 200, description = "商品审核成功", body = ResponseT<Model>),
+        (status = 404, description = "商品不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "goods"
@@ -468,13 +460,11 @@ pub async fn update_good_is_check(
 pub async fn get_good_detail(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-) -> impl Responder {
-    let good_result = goods::Entity::find_by_id(*id).one(db.get_ref()).await;
-    match good_result {
-        Ok(Some(good_result)) => {
-            return HttpResponse::Ok().json(response_t(Some(200), Some(good_result), None));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+) -> Result<HttpResponse, ApiError> {
+    let good = goods::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("商品不存在"))?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(good), None)))
 }
@@ -0,0 +1,161 @@
+use std::{future::Future, time::Instant};
+
+use actix_web::{web, HttpResponse};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::{
+    config::UploadConfig,
+    models::health::{
+        CheckResult, LivenessRes, ReadinessChecks, ReadinessRes, STATUS_ERROR, STATUS_OK,
+    },
+    utils::response::{response_t, ResponseT},
+};
+
+/// 执行一项检查并记录耗时
+async fn timed_check<F>(check: F) -> CheckResult
+where
+    F: Future<Output = Result<(), String>>,
+{
+    let started_at = Instant::now();
+    let result = check.await;
+    let latency_ms = started_at.elapsed().as_secs_f64() * 1000.0;
+    CheckResult {
+        status: String::from(if result.is_ok() {
+            STATUS_OK
+        } else {
+            STATUS_ERROR
+        }),
+        latency_ms: (latency_ms * 1000.0).round() / 1000.0,
+        error: result.err(),
+    }
+}
+
+async fn check_database(db: &DatabaseConnection) -> Result<(), String> {
+    db.ping().await.map_err(|err| err.to_string())
+}
+
+async fn check_migrations(db: &DatabaseConnection) -> Result<(), String> {
+    let pending = Migrator::get_pending_migrations(db)
+        .await
+        .map_err(|err| err.to_string())?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = pending.iter().map(|migration| migration.name()).collect();
+    Err(format!(
+        "有 {} 个迁移未执行：{}",
+        pending.len(),
+        names.join(", ")
+    ))
+}
+
+/// 在上传目录中写入并删除一个临时文件
+async fn check_upload_dir(upload_config: &UploadConfig) -> Result<(), String> {
+    let path = upload_config
+        .dir
+        .join(format!(".readyz-{}", Uuid::new_v4()));
+    tokio::fs::write(&path, b"ok")
+        .await
+        .map_err(|err| format!("{} 不可写：{err}", upload_config.dir.display()))?;
+    tokio::fs::remove_file(&path)
+        .await
+        .map_err(|err| format!("无法删除 {}：{err}", path.display()))
+}
+
+/// 存活检查
+///
+/// 进程能够处理请求即返回成功，不检查任何依赖
+///
+/// # 响应
+///
+/// - 成功：状态码 200
+///
+/// # 示例
+///
+/// ```
+/// GET /healthz
+/// ```
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    responses(
+        (status = 200, description = "服务存活", body = ResponseT<LivenessRes>),
+    ),
+    tag = "health"
+)]
+pub async fn healthz() -> HttpResponse {
+    HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(LivenessRes {
+            status: String::from(STATUS_OK),
+        }),
+        None,
+    ))
+}
+
+/// 就绪检查
+///
+/// 同时执行以下检查，并返回每项检查的结果与耗时：
+/// - `database`：数据库能否连通
+/// - `migrations`：是否所有迁移都已执行
+/// - `upload_dir`：上传图片的目录是否可写
+///
+/// # 响应
+///
+/// - 成功：状态码 200，所有检查均通过
+/// - 失败：状态码 503，至少一项检查未通过，`data` 中同样包含每项检查的结果
+///
+/// # 示例
+///
+/// ```
+/// GET /readyz
+/// ```
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    responses(
+        (status = 200, description = "服务就绪", body = ResponseT<ReadinessRes>),
+        (status = 503, description = "服务未就绪", body = ResponseT<ReadinessRes>),
+    ),
+    tag = "health"
+)]
+pub async fn readyz(
+    db: web::Data<DatabaseConnection>,
+    upload_config: web::Data<UploadConfig>,
+) -> HttpResponse {
+    let (database, migrations, upload_dir) = tokio::join!(
+        timed_check(check_database(db.get_ref())),
+        timed_check(check_migrations(db.get_ref())),
+        timed_check(check_upload_dir(upload_config.get_ref())),
+    );
+    let checks = ReadinessChecks {
+        database,
+        migrations,
+        upload_dir,
+    };
+    let ready = [&checks.database, &checks.migrations, &checks.upload_dir]
+        .iter()
+        .all(|check| check.error.is_none());
+
+    if ready {
+        HttpResponse::Ok().json(response_t(
+            Some(200),
+            Some(ReadinessRes {
+                status: String::from(STATUS_OK),
+                checks,
+            }),
+            None,
+        ))
+    } else {
+        HttpResponse::ServiceUnavailable().json(response_t(
+            Some(503),
+            Some(ReadinessRes {
+                status: String::from(STATUS_ERROR),
+                checks,
+            }),
+            Some(String::from("服务未就绪")),
+        ))
+    }
+}
@@ -1,9 +1,8 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
+use async_trait::async_trait;
 use sea_orm::{
-    ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
 };
 
 use crate::{
@@ -15,9 +14,66 @@ use crate::{
         image_classes::{CreateImageClassReq, UpdateImageClassReq},
         Pagination,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource},
+        pagination::{paginate, SortFields},
+    },
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::{ValidatedJson, ValidatedQuery},
+    },
+};
+
+const IMAGE_SORT_FIELDS: SortFields<images::Column> = SortFields {
+    columns: &[
+        ("name", images::Column::Name),
+        ("create_time", images::Column::CreateTime),
+    ],
+    default: "id",
 };
 
+#[async_trait]
+impl CrudResource for image_classes::Entity {
+    type CreateReq = CreateImageClassReq;
+    type UpdateReq = UpdateImageClassReq;
+
+    const NOT_FOUND: &'static str = "图库不存在";
+
+    const SORT_FIELDS: SortFields<image_classes::Column> = SortFields {
+        columns: &[
+            ("order", image_classes::Column::Order),
+            ("create_time", image_classes::Column::CreateTime),
+        ],
+        default: "id",
+    };
+
+    fn new_model(data: &CreateImageClassReq) -> ActiveModel {
+        ActiveModel {
+            name: Set(data.name.clone()),
+            order: Set(data.order),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(image_class: &mut ActiveModel, data: &UpdateImageClassReq) {
+        image_class.name = data.name.clone().map(Set).unwrap_or(NotSet);
+        image_class.order = data.order.map(Set).unwrap_or(NotSet);
+    }
+
+    /// 外键同样会拒绝删除，提前检查以返回明确的提示
+    async fn before_delete(db: &DatabaseConnection, id: i32) -> Result<(), ApiError> {
+        let image_count = images::Entity::find()
+            .filter(images::Column::ImageClassId.eq(id))
+            .count(db)
+            .await?;
+        if image_count > 0 {
+            return Err(ApiError::conflict("图库中仍有图片，请先删除图片"));
+        }
+        Ok(())
+    }
+}
+
 /// 创建新图库
 ///
 /// # 请求体
@@ -29,7 +85,7 @@ use crate::{
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的图库
-/// - 失败：状态码 201，图库已存在
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -50,32 +106,50 @@ use crate::{
     request_body = CreateImageClassReq,
     responses(
         (status = 200, description = "图库创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "image_classes"
 )]
 pub async fn create_image_class(
     db: web::Data<sea_orm::DatabaseConnection>,
-    image_class_data: web::Json<CreateImageClassReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    image_class_data: ValidatedJson<CreateImageClassReq>,
+) -> Result<HttpResponse, ApiError> {
+    let new_image_class =
+        crud::create::<image_classes::Entity>(db.get_ref(), &image_class_data).await?;
 
-    let new_image_class = ActiveModel {
-        name: Set(image_class_data.name.clone()),
-        order: Set(image_class_data.order),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_image_class.insert(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_image_class), None)))
+}
 
-    match result {
-        Ok(new_image_class) => {
-            HttpResponse::Ok().json(response_t(Some(200), Some(new_image_class), None))
-        }
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+/// 获取图库详情
+///
+/// # 路径
+///
+/// - `id` ：整数，图库 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，图库
+/// - 失败：状态码 404，图库不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/image_classes/{id}",
+    responses(
+        (status = 200, description = "图库获取成功", body = ResponseT<Model>),
+        (status = 404, description = "图库不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "image_classes"
+)]
+pub async fn get_image_class(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let image_class = crud::find::<image_classes::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(image_class), None)))
 }
 
 /// 修改图库
@@ -93,6 +167,8 @@ pub async fn create_image_class(
 /// # 响应
 ///
 /// - 成功：状态码 200，修改之后的图库
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，图库不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -101,40 +177,20 @@ pub async fn create_image_class(
     request_body = UpdateImageClassReq,
     responses(
         (status = 200, description = "图库更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "图库不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "image_classes"
 )]
 pub async fn update_image_class(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    image_class_data: web::Json<UpdateImageClassReq>,
-) -> impl Responder {
-    let image_class_result = image_classes::Entity::find_by_id(*id)
-        .one(db.get_ref())
-        .await;
+    id: web::Path<i32>,
+    image_class_data: ValidatedJson<UpdateImageClassReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<image_classes::Entity>(db.get_ref(), *id, &image_class_data).await?;
 
-    match image_class_result {
-        Ok(Some(image_class)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_image_class: ActiveModel = image_class.into();
-            updated_image_class.name = image_class_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_image_class.order = image_class_data.order.map(Set).unwrap_or(NotSet);
-            updated_image_class.update_time = Set(format_time.clone());
-            let result = updated_image_class.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除图库
@@ -146,6 +202,7 @@ pub async fn update_image_class(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 409，图库中仍有图片
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -153,25 +210,26 @@ pub async fn update_image_class(
     path  = "/api/image_classes/{id}",
     responses(
         (status = 200, description = "图库删除成功", body = ResponseT<Model>),
+        (status = 409, description = "图库中仍有图片"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "image_classes"
 )]
 pub async fn delete_image_class(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-) -> impl Responder {
-    let result = image_classes::Entity::delete_by_id(*id)
-        .exec(db.get_ref())
-        .await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete::<image_classes::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取图库列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`order`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -191,31 +249,23 @@ pub async fn delete_image_class(
 )]
 pub async fn get_image_class_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let paginator = image_classes::Entity::find().paginate(db.get_ref(), size);
-
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let image_class_list = crud::list::<image_classes::Entity>(db.get_ref(), &query).await?;
 
-    match result {
-        Ok(image_class_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(image_class_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(image_class_list)),
+        None,
+    )))
 }
 
 /// 获取图库下的图片列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`name`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -235,28 +285,15 @@ pub async fn get_image_class_list(
 )]
 pub async fn get_image_list_by_image_class_id(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let paginator = images::Entity::find()
-        .filter(images::Column::ImageClassId.eq(*id))
-        .paginate(db.get_ref(), size);
+    id: web::Path<i32>,
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let select = images::Entity::find().filter(images::Column::ImageClassId.eq(*id));
+    let image_list = paginate(db.get_ref(), select, &query, &IMAGE_SORT_FIELDS).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(image_class_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(image_class_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(image_list)),
+        None,
+    )))
 }
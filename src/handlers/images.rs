@@ -1,29 +1,58 @@
-use std::fs::File;
-use std::io::Write;
+use std::io::ErrorKind;
+use std::path::Path;
 
 use actix_multipart::Multipart;
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
 use futures_util::{StreamExt, TryStreamExt};
 use sea_orm::ActiveValue::{NotSet, Set};
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use tokio::{fs, io::AsyncWriteExt};
+use uuid::Uuid;
 
+use crate::config::UploadConfig;
+use crate::entities::image_classes;
 use crate::entities::images::{self, ActiveModel, Model};
 use crate::models::images::{DeleteImageAllReq, UpdateImageReq, UploadImageReq};
+use crate::utils::error::ApiError;
+use crate::utils::metrics::Metrics;
 use crate::utils::response::{response_t, ResponseT};
+use crate::utils::validation::{validate_reference, ValidatedJson};
+
+/// 由客户端提供的文件名得到图片名称与保存的文件名
+///
+/// 图片名称只取文件名的最后一段，不含目录；保存时使用随机文件名，只保留原扩展名，
+/// 避免文件名中的路径写到上传目录之外或覆盖已有文件
+fn upload_filenames(filename: &str) -> Result<(String, String), ApiError> {
+    let name = Path::new(filename)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| ApiError::validation("文件名不合法"))?;
+    let stored_name = match Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| ext.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        Some(ext) => format!("{}.{}", Uuid::new_v4(), ext.to_ascii_lowercase()),
+        None => Uuid::new_v4().to_string(),
+    };
+    Ok((name.to_string(), stored_name))
+}
 
 /// 上传图片
 ///
+/// 文件以随机文件名保存在上传目录中，原文件名作为图片名称；请求被拒绝时不会留下文件
+///
 /// # 请求体
 ///
 /// 需要一个 FormData 对象，包含以下字段：
 /// - `imageClassId` ：整数，图库 ID（必填）
-/// - `img` ：二进制，图片（必填）
+/// - `img` ：二进制，图片（必填，只能有一个）
 ///
 /// # 响应
 ///
 /// - 成功：状态码 200，新上传的图片
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法、缺少图片、图片多于一张或图库不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -31,75 +60,128 @@ use crate::utils::response::{response_t, ResponseT};
     path = "/api/images/upload",
     request_body(content_type = "multipart/form-data", content = UploadImageReq, description = "File to upload"),
     responses(
-        (status = 200, description = "OK")
+        (status = 200, description = "OK"),
+        (status = 400, description = "请求参数不合法或图库不存在")
     ),
     tag = "images"
 )]
 pub async fn upload_file(
     db: web::Data<sea_orm::DatabaseConnection>,
-    mut multipart: Multipart,
-) -> impl Responder {
-    let mut form_data: Option<UploadImageReq> = None;
+    upload_config: web::Data<UploadConfig>,
+    metrics: web::Data<Metrics>,
+    multipart: Multipart,
+) -> Result<HttpResponse, ApiError> {
+    let mut stored: Option<StoredImage> = None;
+    let result = save_image(&db, &upload_config, &metrics, multipart, &mut stored).await;
+    // 请求被拒绝或写库失败时，删除已写入的文件
+    if let (Err(_), Some(stored)) = (&result, stored) {
+        remove_image_files(vec![stored.path]).await;
+    }
+    result?;
 
-    while let Some(mut field) = multipart.try_next().await.unwrap() {
-        let name = field.name().unwrap();
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
 
-        if form_data.is_none() {
-            form_data = Some(UploadImageReq {
-                image_class_id: -1,
-                img: String::new(),
-            });
-        }
+/// 已写入上传目录的图片文件
+struct StoredImage {
+    name: String,
+    path: String,
+}
+
+/// 读取表单、保存文件并写入图片记录
+///
+/// 表单必须包含 `imageClassId` 与且仅与一个 `img`；文件一经创建即记录到 `stored`，
+/// 以便出错时由调用方删除
+async fn save_image(
+    db: &sea_orm::DatabaseConnection,
+    upload_config: &UploadConfig,
+    metrics: &Metrics,
+    mut multipart: Multipart,
+    stored: &mut Option<StoredImage>,
+) -> Result<(), ApiError> {
+    let mut image_class_id = None;
+
+    while let Some(mut field) = multipart
+        .try_next()
+        .await
+        .map_err(|err| ApiError::validation(err.to_string()))?
+    {
+        let name = field.name().unwrap_or_default();
 
         if name == "imageClassId" {
             let mut value = String::new();
             while let Some(chunk) = field.next().await {
-                value.push_str(&String::from_utf8_lossy(&chunk.unwrap()));
+                let chunk = chunk.map_err(|err| ApiError::validation(err.to_string()))?;
+                value.push_str(&String::from_utf8_lossy(&chunk));
             }
-            let parsed_id = value.trim().parse::<i32>().unwrap();
-            form_data.as_mut().unwrap().image_class_id = parsed_id;
+            let parsed_id = value
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| ApiError::validation("imageClassId 必须为整数"))?;
+            validate_reference::<image_classes::Entity>(
+                db,
+                parsed_id,
+                "imageClassId",
+                "图库不存在",
+            )
+            .await?;
+            image_class_id = Some(parsed_id);
         } else if name == "img" {
-            let content = field.content_disposition().unwrap();
-
-            let filename = content.get_filename().unwrap();
+            if stored.is_some() {
+                return Err(ApiError::validation("只能上传一张图片"));
+            }
+            let filename = field
+                .content_disposition()
+                .and_then(|content| content.get_filename())
+                .ok_or_else(|| ApiError::validation("缺少文件名"))?;
+            let (name, stored_name) = upload_filenames(filename)?;
 
-            let data_url = format!("upload/{}", filename);
-            let mut file = File::create(&data_url).unwrap();
-            let mut stream = field.into_stream();
-            while let Some(chunk) = stream.next().await {
-                let data = chunk.unwrap();
-                file.write_all(&data).unwrap();
+            let path = upload_config
+                .dir
+                .join(stored_name)
+                .to_string_lossy()
+                .into_owned();
+            let mut file = fs::File::create(&path).await.map_err(ApiError::internal)?;
+            *stored = Some(StoredImage {
+                name,
+                path: path.clone(),
+            });
+            let mut size = 0;
+            while let Some(chunk) = field.next().await {
+                let data = chunk.map_err(|err| ApiError::validation(err.to_string()))?;
+                file.write_all(&data).await.map_err(ApiError::internal)?;
+                size += data.len() as u64;
             }
-            form_data.as_mut().unwrap().img = data_url;
+            file.flush().await.map_err(ApiError::internal)?;
+            metrics.observe_upload(size);
         }
     }
 
-    if form_data.is_none() {
-        return HttpResponse::Ok().body("File uploaded successfully!");
-    }
-
-    let image_data = match form_data {
-        Some(data) => data,
-        None => return HttpResponse::BadRequest().body("Missing form data!"),
-    };
-
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    let image_class_id =
+        image_class_id.ok_or_else(|| ApiError::invalid_field("imageClassId", "缺少图库 ID"))?;
+    let image = stored
+        .as_ref()
+        .ok_or_else(|| ApiError::invalid_field("img", "缺少图片"))?;
 
     let new_image = ActiveModel {
-        name: Set(image_data.img.clone()),
-        url: Set(image_data.img.clone()),
-        path: Set(image_data.img.clone()),
-        image_class_id: Set(image_data.image_class_id),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
+        name: Set(image.name.clone()),
+        url: Set(image.path.clone()),
+        path: Set(image.path.clone()),
+        image_class_id: Set(Some(image_class_id)),
         ..Default::default()
     };
-    let result = new_image.insert(db.get_ref()).await;
+    new_image.insert(db).await?;
+    Ok(())
+}
 
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
+/// 删除图片文件，文件已不存在时忽略，其他错误只记录日志
+async fn remove_image_files(paths: Vec<String>) {
+    for path in paths {
+        if let Err(err) = fs::remove_file(&path).await {
+            if err.kind() != ErrorKind::NotFound {
+                tracing::error!(error = %err, path, "failed to remove image file");
+            }
+        }
     }
 }
 
@@ -110,9 +192,12 @@ pub async fn upload_file(
 /// 需要一个 JSON 对象，包含以下字段：
 /// - `ids` ：整数向量，图片 ID 数组（必填）
 ///
+/// 图片文件随记录一起删除
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -121,25 +206,29 @@ pub async fn upload_file(
     request_body = DeleteImageAllReq,
     responses(
         (status = 200, description = "图片删除成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "images"
 )]
 pub async fn delete_all_image(
     db: web::Data<sea_orm::DatabaseConnection>,
-    image_data: web::Json<DeleteImageAllReq>,
-) -> impl Responder {
-    let result = images::Entity::delete_many()
+    image_data: ValidatedJson<DeleteImageAllReq>,
+) -> Result<HttpResponse, ApiError> {
+    let paths = images::Entity::find()
+        .filter(images::Column::Id.is_in(image_data.ids.clone()))
+        .all(db.get_ref())
+        .await?
+        .into_iter()
+        .map(|image| image.path)
+        .collect();
+    images::Entity::delete_many()
         .filter(images::Column::Id.is_in(image_data.ids.clone()))
         .exec(db.get_ref())
-        .await;
-    match result {
-        Ok(demo) => {
-            println!("{:?}", demo);
-            return HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None));
-        }
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+        .await?;
+    remove_image_files(paths).await;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改图片名称
@@ -156,7 +245,8 @@ pub async fn delete_all_image(
 /// # 响应
 ///
 /// - 成功：状态码 200，修改之后的图片
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，图片不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -165,6 +255,8 @@ pub async fn delete_all_image(
     request_body = UpdateImageReq,
     responses(
         (status = 200, description = "图片更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "图片不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "images"
@@ -172,28 +264,16 @@ pub async fn delete_all_image(
 pub async fn update_image(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    image_data: web::Json<UpdateImageReq>,
-) -> impl Responder {
-    let image_result = images::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match image_result {
-        Ok(Some(image)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_image: ActiveModel = image.into();
-            updated_image.name = image_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_image.update_time = Set(format_time.clone());
-            let result = updated_image.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    image_data: ValidatedJson<UpdateImageReq>,
+) -> Result<HttpResponse, ApiError> {
+    let image = images::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("图片不存在"))?;
+
+    let mut updated_image: ActiveModel = image.into();
+    updated_image.name = image_data.name.clone().map(Set).unwrap_or(NotSet);
+    updated_image.update(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
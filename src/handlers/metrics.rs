@@ -0,0 +1,42 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::DatabaseConnection;
+
+use crate::utils::metrics::Metrics;
+
+/// Prometheus 指标
+///
+/// 以 Prometheus 文本格式输出以下指标：
+/// - `http_requests_total`、`http_request_duration_seconds`：按请求方式、路由模板（如 `/api/goods/{id}`）统计的请求数与耗时
+/// - `db_query_duration_seconds`：按语句类型统计的 SQL 执行耗时
+/// - `db_pool_connections`、`db_pool_max_connections`：连接池的空闲、使用中与最大连接数
+/// - `upload_files_total`、`upload_bytes_total`：上传的图片数与字节数
+/// - `goods_below_min_stock`：库存低于预警库存的商品数
+/// - `coupons_active`：已启用且处于有效期内的优惠券数
+///
+/// 该接口不需要登录，部署时应只允许监控系统访问
+///
+/// # 响应
+///
+/// - 成功：状态码 200
+///
+/// # 示例
+///
+/// ```
+/// GET /metrics
+/// ```
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Prometheus 文本格式的指标", content_type = "text/plain; version=0.0.4", body = String),
+    ),
+    tag = "metrics"
+)]
+pub async fn get_metrics(
+    db: web::Data<DatabaseConnection>,
+    metrics: web::Data<Metrics>,
+) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(metrics.render(db.get_ref()).await)
+}
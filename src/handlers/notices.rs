@@ -1,13 +1,49 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, PaginatorTrait};
+use actix_web::{web, HttpResponse};
+use sea_orm::ActiveValue::Set;
 
 use crate::{
     entities::notices::{self, ActiveModel, Model},
     models::{notices::CreateNotice, Pagination},
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource},
+        pagination::SortFields,
+    },
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::{ValidatedJson, ValidatedQuery},
+    },
 };
 
+/// 修改公告时同样需要提供全部字段
+impl CrudResource for notices::Entity {
+    type CreateReq = CreateNotice;
+    type UpdateReq = CreateNotice;
+
+    const NOT_FOUND: &'static str = "公告不存在";
+
+    const SORT_FIELDS: SortFields<notices::Column> = SortFields {
+        columns: &[
+            ("create_time", notices::Column::CreateTime),
+            ("update_time", notices::Column::UpdateTime),
+        ],
+        default: "id",
+    };
+
+    fn new_model(data: &CreateNotice) -> ActiveModel {
+        ActiveModel {
+            title: Set(data.title.clone()),
+            content: Set(data.content.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(notice: &mut ActiveModel, data: &CreateNotice) {
+        notice.title = Set(data.title.clone());
+        notice.content = Set(data.content.clone());
+    }
+}
+
 /// 创建新公告
 ///
 /// # 请求体
@@ -18,8 +54,8 @@ use crate::{
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的管理员
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，新创建的公告
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -42,30 +78,49 @@ use crate::{
     request_body = CreateNotice,
     responses(
         (status = 200, description = "公告创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "notices"
 )]
 pub async fn create_notice(
     db: web::Data<sea_orm::DatabaseConnection>,
-    notice_data: web::Json<CreateNotice>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-
-    let new_notice = ActiveModel {
-        title: Set(notice_data.title.clone()),
-        content: Set(notice_data.content.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_notice.insert(db.get_ref()).await;
+    notice_data: ValidatedJson<CreateNotice>,
+) -> Result<HttpResponse, ApiError> {
+    let new_notice = crud::create::<notices::Entity>(db.get_ref(), &notice_data).await?;
 
-    match result {
-        Ok(new_notice) => HttpResponse::Ok().json(response_t(Some(200), Some(new_notice), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_notice), None)))
+}
+
+/// 获取公告详情
+///
+/// # 路径
+///
+/// - `id` ：整数，公告 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，公告
+/// - 失败：状态码 404，公告不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/notices/{id}",
+    responses(
+        (status = 200, description = "公告获取成功", body = ResponseT<Model>),
+        (status = 404, description = "公告不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "notices"
+)]
+pub async fn get_notice(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let notice = crud::find::<notices::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(notice), None)))
 }
 
 /// 修改公告
@@ -82,8 +137,9 @@ pub async fn create_notice(
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的管理员
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，公告不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -91,39 +147,21 @@ pub async fn create_notice(
     path = "/api/notices/{id}",
     request_body = CreateNotice,
     responses(
-        (status = 200, description = "公告更新成功", body = ResponseT<Model>),
+        (status = 200, description = "公告更新成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "公告不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "notices"
 )]
 pub async fn update_notice(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    notice_data: web::Json<CreateNotice>,
-) -> impl Responder {
-    let notice_result = notices::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match notice_result {
-        Ok(Some(notice)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_notice: ActiveModel = notice.into();
-            updated_notice.title = Set(notice_data.title.clone());
-            updated_notice.content = Set(notice_data.content.clone());
-            updated_notice.update_time = Set(format_time.clone());
-            let result = updated_notice.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+    notice_data: ValidatedJson<CreateNotice>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<notices::Entity>(db.get_ref(), *id, &notice_data).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除公告
@@ -148,17 +186,19 @@ pub async fn update_notice(
 )]
 pub async fn delete_notice(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-) -> impl Responder {
-    let result = notices::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete::<notices::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取公告列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`create_time`、`update_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -168,8 +208,7 @@ pub async fn delete_notice(
     get,
     path = "/api/notices",
     params(
-        ("page", Query, description = "页码，默认值为 1"),
-        ("size", Query, description = "每页条目数，默认值为 10")
+        Pagination
     ),
     responses(
         (status = 200, description = "公告获取成功", body = ResponseT<Model>),
@@ -179,25 +218,13 @@ pub async fn delete_notice(
 )]
 pub async fn get_notice_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let paginator = notices::Entity::find().paginate(db.get_ref(), size);
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let notice_list = crud::list::<notices::Entity>(db.get_ref(), &query).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(role_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(role_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(notice_list)),
+        None,
+    )))
 }
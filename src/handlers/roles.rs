@@ -1,23 +1,70 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use std::collections::BTreeSet;
+
+use actix_web::{web, HttpResponse};
 use sea_orm::{
-    ActiveModelTrait,
+    sea_query::OnConflict,
     ActiveValue::{NotSet, Set},
-    EntityTrait, PaginatorTrait,
+    ColumnTrait, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
 };
 
 use crate::{
-    entities::roles::{self, ActiveModel, Model},
+    entities::{
+        role_rules,
+        roles::{self, ActiveModel, Model},
+        rules,
+    },
+    handlers::rules::utils::build_partial_rule_tree,
     models::{
         roles::{
-            CreateRoleReq, UpdateRoleReq, UpdateRoleRuleIdsReq,
+            CreateRoleReq, RoleInfo, RoleRulesRes, UpdateRoleReq, UpdateRoleRuleIdsReq,
             UpdateRoleStatusReq,
         },
         Pagination,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource, StatusResource},
+        pagination::SortFields,
+    },
+    utils::{
+        error::ApiError,
+        response::{response_page_t, response_t, ResponseListT, ResponseT},
+        validation::{ValidatedJson, ValidatedQuery},
+    },
 };
 
+/// 删除角色时，角色与规则的关联由外键一并删除，相应管理员的角色置为空
+impl CrudResource for roles::Entity {
+    type CreateReq = CreateRoleReq;
+    type UpdateReq = UpdateRoleReq;
+
+    const NOT_FOUND: &'static str = "角色不存在";
+
+    const SORT_FIELDS: SortFields<roles::Column> = SortFields {
+        columns: &[("create_time", roles::Column::CreateTime)],
+        default: "id",
+    };
+
+    fn new_model(data: &CreateRoleReq) -> ActiveModel {
+        ActiveModel {
+            name: Set(data.name.clone()),
+            desc: Set(data.desc.clone()),
+            status: Set(data.status),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(role: &mut ActiveModel, data: &UpdateRoleReq) {
+        role.name = data.name.clone().map(Set).unwrap_or(NotSet);
+        role.desc = data.desc.clone().map(Set).unwrap_or(NotSet);
+        role.status = data.status.map(Set).unwrap_or(NotSet);
+    }
+}
+
+impl StatusResource for roles::Entity {
+    const STATUS_COLUMN: roles::Column = roles::Column::Status;
+}
+
 /// 创建新角色
 ///
 /// # 请求体
@@ -30,7 +77,7 @@ use crate::{
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -51,31 +98,50 @@ use crate::{
     request_body = CreateRoleReq,
     responses(
         (status = 200, description = "角色创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "roles"
 )]
 pub async fn create_role(
     db: web::Data<sea_orm::DatabaseConnection>,
-    role_data: web::Json<CreateRoleReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-
-    let new_role = ActiveModel {
-        name: Set(role_data.name.clone()),
-        desc: Set(role_data.desc.clone()),
-        status: Set(role_data.status),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_role.insert(db.get_ref()).await;
+    role_data: ValidatedJson<CreateRoleReq>,
+) -> Result<HttpResponse, ApiError> {
+    let new_role = crud::create::<roles::Entity>(db.get_ref(), &role_data).await?;
 
-    match result {
-        Ok(new_role) => HttpResponse::Ok().json(response_t(Some(200), Some(new_role), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_role), None)))
+}
+
+/// 获取角色详情
+///
+/// # 路径
+///
+/// - `id` ：整数，角色 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，角色及其拥有的规则 ID
+/// - 失败：状态码 404，角色不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/roles/{id}",
+    responses(
+        (status = 200, description = "角色获取成功", body = ResponseT<RoleInfo>),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "roles"
+)]
+pub async fn get_role(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let role = crud::find::<roles::Entity>(db.get_ref(), *id).await?;
+    let role = RoleInfo::load_many(db.get_ref(), vec![role]).await?.pop();
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), role, None)))
 }
 
 /// 修改角色
@@ -94,7 +160,8 @@ pub async fn create_role(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，角色不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -103,39 +170,20 @@ pub async fn create_role(
     request_body = UpdateRoleReq,
     responses(
         (status = 200, description = "角色更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "角色不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "roles"
 )]
 pub async fn update_role(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    role_data: web::Json<UpdateRoleReq>,
-) -> impl Responder {
-    let role_result = roles::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match role_result {
-        Ok(Some(role)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_role: ActiveModel = role.into();
-            updated_role.name = role_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_role.desc = role_data.desc.clone().map(Set).unwrap_or(NotSet);
-            updated_role.status = role_data.status.map(Set).unwrap_or(NotSet);
-            updated_role.update_time = Set(format_time.clone());
-            let result = updated_role.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+    role_data: ValidatedJson<UpdateRoleReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<roles::Entity>(db.get_ref(), *id, &role_data).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除角色
@@ -160,17 +208,19 @@ pub async fn update_role(
 )]
 pub async fn delete_role(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-) -> impl Responder {
-    let result = roles::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete::<roles::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取角色列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -180,38 +230,26 @@ pub async fn delete_role(
     get,
     path = "/api/roles",
     params(
-        ("page", Query, description = "页码，默认值为 1"),
-        ("size", Query, description = "每页条目数，默认值为 10")
+        Pagination
     ),
     responses(
-        (status = 200, description = "角色列表获取成功", body = ResponseT<Model>),
+        (status = 200, description = "角色列表获取成功", body = ResponseT<ResponseListT<RoleInfo>>),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "roles"
 )]
 pub async fn get_role_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let page = crud::list::<roles::Entity>(db.get_ref(), &query).await?;
+    let role_list = RoleInfo::load_many(db.get_ref(), page.list).await?;
 
-    let paginator = roles::Entity::find().paginate(db.get_ref(), size);
-
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(role_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(role_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(response_page_t(role_list, page.total, page.next_cursor)),
+        None,
+    )))
 }
 
 /// 修改角色状态
@@ -228,7 +266,8 @@ pub async fn get_role_list(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，角色不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -237,41 +276,26 @@ pub async fn get_role_list(
     request_body = UpdateRoleStatusReq,
     responses(
         (status = 200, description = "角色更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "角色不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "roles"
 )]
 pub async fn update_role_status(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    role_data: web::Json<UpdateRoleStatusReq>,
-) -> impl Responder {
-    let role_result = roles::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match role_result {
-        Ok(Some(role)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_role: ActiveModel = role.into();
-            updated_role.status = Set(role_data.status.clone());
-            updated_role.update_time = Set(format_time.clone());
-            let result = updated_role.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+    role_data: ValidatedJson<UpdateRoleStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update_status::<roles::Entity>(db.get_ref(), *id, role_data.status).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 配置角色权限
 ///
+/// 以请求中的规则替换角色原有的全部规则
+///
 /// # 路径
 ///
 /// - `id` ：整数，角色 ID（必填）
@@ -279,12 +303,13 @@ pub async fn update_role_status(
 /// # 请求体
 ///
 /// 需要一个 JSON 对象，包含以下字段：
-/// - `rule_ids` ：数组<整数>，角色权限（必填）
+/// - `rule_ids` ：数组<整数>，角色权限（必填），规则必须存在
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法或规则不存在
+/// - 失败：状态码 404，角色不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -292,36 +317,181 @@ pub async fn update_role_status(
     path = "/api/roles/{id}/set_rules",
     request_body = UpdateRoleRuleIdsReq,
     responses(
-        (status = 200, description = "角色更新成功", body = ResponseT<Model>),
+        (status = 200, description = "角色更新成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法或规则不存在"),
+        (status = 404, description = "角色不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "roles"
 )]
 pub async fn update_role_rule_ids(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    role_data: web::Json<UpdateRoleRuleIdsReq>,
-) -> impl Responder {
-    let role_result = roles::Entity::find_by_id(*id).one(db.get_ref()).await;
-
-    match role_result {
-        Ok(Some(role)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_role: ActiveModel = role.into();
-            updated_role.rule_ids = Set(Some(role_data.rule_ids.clone()));
-            updated_role.update_time = Set(format_time.clone());
-            let result = updated_role.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
+    id: web::Path<i32>,
+    role_data: ValidatedJson<UpdateRoleRuleIdsReq>,
+) -> Result<HttpResponse, ApiError> {
+    let role = crud::find::<roles::Entity>(db.get_ref(), *id).await?;
+
+    let rule_ids: BTreeSet<i32> = role_data.rule_ids.iter().copied().collect();
+    let found = rules::Entity::find()
+        .filter(rules::Column::Id.is_in(rule_ids.clone()))
+        .count(db.get_ref())
+        .await?;
+    if found != rule_ids.len() as u64 {
+        return Err(ApiError::invalid_field("rule_ids", "规则不存在"));
+    }
+
+    let txn = db.begin().await?;
+    role_rules::Entity::delete_many()
+        .filter(role_rules::Column::RoleId.eq(role.id))
+        .exec(&txn)
+        .await?;
+    if !rule_ids.is_empty() {
+        role_rules::Entity::insert_many(rule_ids.into_iter().map(|rule_id| {
+            role_rules::ActiveModel {
+                role_id: Set(role.id),
+                rule_id: Set(rule_id),
             }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
+        }))
+        .exec_without_returning(&txn)
+        .await?;
     }
+    txn.commit().await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 获取角色的权限
+///
+/// # 路径
+///
+/// - `id` ：整数，角色 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，角色拥有的规则 ID 与规则树
+/// - 失败：状态码 404，角色不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/roles/{id}/rules",
+    responses(
+        (status = 200, description = "角色权限获取成功", body = ResponseT<RoleRulesRes>),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "roles"
+)]
+pub async fn get_role_rules(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let role = crud::find::<roles::Entity>(db.get_ref(), *id).await?;
+    let rule_list = role
+        .find_related(rules::Entity)
+        .order_by_asc(rules::Column::Order)
+        .order_by_asc(rules::Column::Id)
+        .all(db.get_ref())
+        .await?;
+    let mut rule_ids: Vec<i32> = rule_list.iter().map(|rule| rule.id).collect();
+    rule_ids.sort_unstable();
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(RoleRulesRes {
+            rule_ids,
+            rules: build_partial_rule_tree(rule_list),
+        }),
+        None,
+    )))
+}
+
+/// 授予角色单条权限
+///
+/// 角色已拥有该规则时不做修改
+///
+/// # 路径
+///
+/// - `id` ：整数，角色 ID（必填）
+/// - `rule_id` ：整数，规则 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 404，角色或规则不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path = "/api/roles/{id}/rules/{rule_id}",
+    responses(
+        (status = 200, description = "权限授予成功", body = ResponseT<String>),
+        (status = 404, description = "角色或规则不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "roles"
+)]
+pub async fn grant_role_rule(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (id, rule_id) = path.into_inner();
+    let role = crud::find::<roles::Entity>(db.get_ref(), id).await?;
+    let rule = rules::Entity::find_by_id(rule_id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("规则不存在"))?;
+
+    role_rules::Entity::insert(role_rules::ActiveModel {
+        role_id: Set(role.id),
+        rule_id: Set(rule.id),
+    })
+    .on_conflict(
+        OnConflict::columns([role_rules::Column::RoleId, role_rules::Column::RuleId])
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 撤销角色单条权限
+///
+/// 角色未拥有该规则时不做修改
+///
+/// # 路径
+///
+/// - `id` ：整数，角色 ID（必填）
+/// - `rule_id` ：整数，规则 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 404，角色不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    delete,
+    path = "/api/roles/{id}/rules/{rule_id}",
+    responses(
+        (status = 200, description = "权限撤销成功", body = ResponseT<String>),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "roles"
+)]
+pub async fn revoke_role_rule(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (id, rule_id) = path.into_inner();
+    let role = crud::find::<roles::Entity>(db.get_ref(), id).await?;
+
+    role_rules::Entity::delete_by_id((role.id, rule_id))
+        .exec(db.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
@@ -1,24 +1,24 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{NotSet, Set},
     EntityTrait,
 };
-use utils::{build_rule_tree, insert_rule_with_child};
+use utils::{build_rule_tree, seed_rules};
 
 use crate::{
     entities::rules::{self, ActiveModel, Model},
     models::rules::{
-        CreateRuleReq, Rule, UpdateRuleReq, UpdateRuleStatusReq,
+        CreateRuleReq, UpdateRuleReq, UpdateRuleStatusReq,
     },
     utils::{
-        json::read_json_from_file,
+        error::ApiError,
         response::{response_list_t, response_t, ResponseT},
+        validation::ValidatedJson,
     },
 };
 
-mod utils;
+pub mod utils;
 
 /// 初始化规则
 ///
@@ -28,9 +28,12 @@ mod utils;
 ///
 /// # 响应
 ///
-/// - 成功：返回状态码 200 和新创建的规则对象
+/// - 成功：状态码 200，`msg` 为“规则已初始化”
+/// - 失败：状态码 409，规则已初始化
 /// - 失败：返回状态码 500
 ///
+/// 也可以使用 `shop_demo_be seed rules` 命令初始化
+///
 /// # 示例
 ///
 /// ```
@@ -45,23 +48,24 @@ mod utils;
     post,
     path  = "/api/rules/init",
     responses(
-        (status = 200, description = "Rule created successfully", body = Model),
+        (status = 200, description = "规则初始化成功", body = ResponseT<String>),
+        (status = 409, description = "规则已初始化"),
         (status = 500, description = "Internal server error")
     ),
     tag = "rules"
 )]
-pub async fn init_rule(db: web::Data<sea_orm::DatabaseConnection>) -> impl Responder {
-    let result = read_json_from_file::<Vec<Rule>>("./src/assets/rule.json");
-    match result {
-        Ok(rules) => {
-            insert_rule_with_child(&db, rules).await;
-            HttpResponse::Ok().json("规则已初始化")
-        }
-        Err(err) => {
-            println!("发生错误: {}", err);
-            HttpResponse::InternalServerError().json("初始化规则失败")
-        }
-    }
+pub async fn init_rule(
+    db: web::Data<sea_orm::DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    seed_rules(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::conflict("规则已初始化"))?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        None,
+        None::<()>,
+        Some(String::from("规则已初始化")),
+    )))
 }
 
 /// 创建新规则
@@ -82,6 +86,7 @@ pub async fn init_rule(db: web::Data<sea_orm::DatabaseConnection>) -> impl Respo
 /// # 响应
 ///
 /// - 成功：返回状态码 200 和新创建的规则对象
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：返回状态码 500
 ///
 /// # 示例
@@ -100,17 +105,15 @@ pub async fn init_rule(db: web::Data<sea_orm::DatabaseConnection>) -> impl Respo
     request_body = CreateRuleReq,
     responses(
         (status = 200, description = "Rule created successfully", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "Internal server error")
     ),
     tag = "rules"
 )]
 pub async fn create_rule(
     db: web::Data<sea_orm::DatabaseConnection>,
-    rule_data: web::Json<CreateRuleReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-
+    rule_data: ValidatedJson<CreateRuleReq>,
+) -> Result<HttpResponse, ApiError> {
     let new_rule = ActiveModel {
         name: Set(rule_data.name.clone()),
         status: Set(rule_data.status),
@@ -121,16 +124,11 @@ pub async fn create_rule(
         order: Set(rule_data.order),
         icon: Set(rule_data.icon.clone()),
         method: Set(rule_data.method.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
         ..Default::default()
     };
-    let result = new_rule.insert(db.get_ref()).await;
+    let new_rule = new_rule.insert(db.get_ref()).await?;
 
-    match result {
-        Ok(new_rule) => HttpResponse::Ok().json(response_t(Some(200), Some(new_rule), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_rule), None)))
 }
 
 /// 修改规则
@@ -155,7 +153,8 @@ pub async fn create_rule(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的规则
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，规则不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -164,6 +163,8 @@ pub async fn create_rule(
     request_body = UpdateRuleReq,
     responses(
         (status = 200, description = "公告更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "规则不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "rules"
@@ -171,38 +172,26 @@ pub async fn create_rule(
 pub async fn update_rule(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    rule_data: web::Json<UpdateRuleReq>,
-) -> impl Responder {
-    let rule_result = rules::Entity::find_by_id(*id).one(db.get_ref()).await;
+    rule_data: ValidatedJson<UpdateRuleReq>,
+) -> Result<HttpResponse, ApiError> {
+    let rule = rules::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("规则不存在"))?;
 
-    match rule_result {
-        Ok(Some(rule)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_rule: ActiveModel = rule.into();
-            updated_rule.name = rule_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_rule.rule_id = rule_data.rule_id.map(Set).unwrap_or(NotSet);
-            updated_rule.status = rule_data.status.map(Set).unwrap_or(NotSet);
-            updated_rule.front_path = rule_data.front_path.clone().map(Set).unwrap_or(NotSet);
-            updated_rule.condition = rule_data.condition.clone().map(Set).unwrap_or(NotSet);
-            updated_rule.menu = rule_data.menu.map(Set).unwrap_or(NotSet);
-            updated_rule.order = rule_data.order.map(Set).unwrap_or(NotSet);
-            updated_rule.icon = rule_data.icon.clone().map(Set).unwrap_or(NotSet);
-            updated_rule.method = rule_data.method.clone().map(Set).unwrap_or(NotSet);
-            updated_rule.update_time = Set(format_time.clone());
-            let result = updated_rule.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    let mut updated_rule: ActiveModel = rule.into();
+    updated_rule.name = rule_data.name.clone().map(Set).unwrap_or(NotSet);
+    updated_rule.rule_id = rule_data.rule_id.map(Set).unwrap_or(NotSet);
+    updated_rule.status = rule_data.status.map(Set).unwrap_or(NotSet);
+    updated_rule.front_path = rule_data.front_path.clone().map(Set).unwrap_or(NotSet);
+    updated_rule.condition = rule_data.condition.clone().map(Set).unwrap_or(NotSet);
+    updated_rule.menu = rule_data.menu.map(Set).unwrap_or(NotSet);
+    updated_rule.order = rule_data.order.map(Set).unwrap_or(NotSet);
+    updated_rule.icon = rule_data.icon.clone().map(Set).unwrap_or(NotSet);
+    updated_rule.method = rule_data.method.clone().map(Set).unwrap_or(NotSet);
+    updated_rule.update(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改规则状态
@@ -219,7 +208,8 @@ pub async fn update_rule(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的会员等级
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，规则不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -228,6 +218,8 @@ pub async fn update_rule(
     request_body = UpdateRuleStatusReq,
     responses(
         (status = 200, description = "规则状态更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "规则不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "rules"
@@ -235,34 +227,24 @@ pub async fn update_rule(
 pub async fn update_rule_status(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    rule_data: web::Json<UpdateRuleStatusReq>,
-) -> impl Responder {
-    let rule_result = rules::Entity::find_by_id(*id).one(db.get_ref()).await;
+    rule_data: ValidatedJson<UpdateRuleStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    let rule = rules::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("规则不存在"))?;
+
+    let mut updated_rule: ActiveModel = rule.into();
+    updated_rule.status = Set(rule_data.status);
+    updated_rule.update(db.get_ref()).await?;
 
-    match rule_result {
-        Ok(Some(rule)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_rule: ActiveModel = rule.into();
-            updated_rule.status = Set(rule_data.status.clone());
-            updated_rule.update_time = Set(format_time.clone());
-            let result = updated_rule.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除规则
 ///
+/// 规则同时从拥有它的角色中移除
+///
 /// # 路径
 ///
 /// - `id` ：整数，规则 ID（必填）
@@ -284,12 +266,10 @@ pub async fn update_rule_status(
 pub async fn delete_rule(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-) -> impl Responder {
-    let result = rules::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+) -> Result<HttpResponse, ApiError> {
+    rules::Entity::delete_by_id(*id).exec(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取公告列表
@@ -308,21 +288,18 @@ pub async fn delete_rule(
     ),
     tag = "rules"
 )]
-pub async fn get_rule_list(db: web::Data<sea_orm::DatabaseConnection>) -> impl Responder {
-    let rule_result = rules::Entity::find().all(db.get_ref()).await;
+pub async fn get_rule_list(
+    db: web::Data<sea_orm::DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let rule_list = rules::Entity::find().all(db.get_ref()).await?;
+    let rule_list_len = rule_list.len() as u64;
 
-    match rule_result {
-        Ok(rule_list) => {
-            let rule_list_len = rule_list.len() as u64;
-            HttpResponse::Ok().json(response_t(
-                Some(200),
-                Some(response_list_t(
-                    build_rule_tree(rule_list, 0),
-                    rule_list_len,
-                )),
-                None,
-            ))
-        }
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(response_list_t(
+            build_rule_tree(rule_list, 0),
+            rule_list_len,
+        )),
+        None,
+    )))
 }
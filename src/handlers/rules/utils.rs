@@ -1,15 +1,26 @@
-use actix_web::web;
-use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DatabaseConnection, DbBackend, DbErr,
+    EntityTrait, PaginatorTrait, TransactionTrait,
+};
 
 use crate::{
-    entities::rules::{ActiveModel, Model},
+    entities::rules::{self, ActiveModel, Model},
     models::rules::Rule,
 };
 
-pub async fn insert_rule_with_child(
-    db: &web::Data<sea_orm::DatabaseConnection>,
+/// 规则初始数据，`seed rules` 命令与初始化规则接口共用
+///
+/// 编译时嵌入程序，安装后的程序无需在源码目录中运行
+pub const RULES_JSON: &str = include_str!("../../assets/rule.json");
+
+/// 递归插入规则及其子规则，返回插入的条数
+pub async fn insert_rule_with_child<C: ConnectionTrait>(
+    db: &C,
     rule_list: Vec<Rule>,
-) {
+) -> Result<u64, DbErr> {
+    let mut inserted = 0;
     for rule in rule_list {
         let active_model = ActiveModel {
             id: Set(rule.id),
@@ -25,15 +36,53 @@ pub async fn insert_rule_with_child(
             icon: Set(rule.icon),
             method: Set(rule.method),
         };
-        let insert_result = active_model.insert(db.get_ref()).await;
-        match insert_result {
-            Ok(_) => println!("成功插入规则"),
-            Err(err) => println!("插入失败: {:?}", err),
-        }
-        if rule.child.len() != 0 {
-            let _ = Box::pin(insert_rule_with_child(db, rule.child)).await;
+        active_model.insert(db).await?;
+        inserted += 1;
+        if !rule.child.is_empty() {
+            inserted += Box::pin(insert_rule_with_child(db, rule.child)).await?;
         }
     }
+    Ok(inserted)
+}
+
+/// 从 [`RULES_JSON`] 导入规则，已有规则时不做任何修改并返回 `None`
+///
+/// 规则使用文件中的 ID 插入，插入后同步 Postgres 的自增序列，避免之后新建规则时主键冲突
+pub async fn seed_rules(db: &DatabaseConnection) -> Result<Option<u64>, DbErr> {
+    if rules::Entity::find().count(db).await? > 0 {
+        return Ok(None);
+    }
+    let rule_list = serde_json::from_str::<Vec<Rule>>(RULES_JSON)
+        .map_err(|err| DbErr::Custom(format!("规则初始数据格式错误：{err}")))?;
+
+    let txn = db.begin().await?;
+    let inserted = insert_rule_with_child(&txn, rule_list).await?;
+    if txn.get_database_backend() == DbBackend::Postgres {
+        txn.execute_unprepared(
+            "SELECT setval(pg_get_serial_sequence('rules', 'id'), COALESCE(MAX(id), 1)) FROM rules",
+        )
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(Some(inserted))
+}
+
+fn rule_node(node: &Model) -> Rule {
+    Rule {
+        id: node.id,
+        name: node.name.clone(),
+        rule_id: node.rule_id,
+        status: node.status,
+        create_time: node.create_time,
+        update_time: node.update_time,
+        front_path: node.front_path.clone(),
+        condition: node.condition.clone(),
+        menu: node.menu,
+        order: node.order,
+        icon: node.icon.clone(),
+        method: node.method.clone(),
+        child: vec![],
+    }
 }
 
 pub fn build_rule_tree(nodes: Vec<Model>, rule_id: i32) -> Vec<Rule> {
@@ -41,21 +90,7 @@ pub fn build_rule_tree(nodes: Vec<Model>, rule_id: i32) -> Vec<Rule> {
 
     // 找到所有 parent_rule_id 为当前父节点的节点
     for node in nodes.iter().filter(|&node| node.rule_id == rule_id) {
-        let mut node_with_children = Rule {
-            id: node.id,
-            name: node.name.clone(),
-            rule_id: node.rule_id,
-            status: node.status,
-            create_time: node.create_time.clone(),
-            update_time: node.update_time.clone(),
-            front_path: node.front_path.clone(),
-            condition: node.condition.clone(),
-            menu: node.menu,
-            order: node.order,
-            icon: node.icon.clone(),
-            method: node.method.clone(),
-            child: vec![],
-        };
+        let mut node_with_children = rule_node(node);
         // 递归构建子树
         node_with_children.child = build_rule_tree(nodes.clone(), node.id);
         tree.push(node_with_children);
@@ -63,3 +98,36 @@ pub fn build_rule_tree(nodes: Vec<Model>, rule_id: i32) -> Vec<Rule> {
 
     tree
 }
+
+/// 由部分规则构建规则树，上级规则不在 `nodes` 中的规则作为根节点
+pub fn build_partial_rule_tree(nodes: Vec<Model>) -> Vec<Rule> {
+    let ids: HashSet<i32> = nodes.iter().map(|node| node.id).collect();
+    nodes
+        .iter()
+        .filter(|node| !ids.contains(&node.rule_id))
+        .map(|node| Rule {
+            child: build_rule_tree(nodes.clone(), node.id),
+            ..rule_node(node)
+        })
+        .collect()
+}
+
+/// 补全规则的上级规则，使只拥有下级规则时仍能从根节点构建出完整的树
+///
+/// 上级规则从 `all_rules` 中查找，找不到（如已被禁用）时该分支不再补全
+pub fn with_ancestors(rule_list: Vec<Model>, all_rules: &[Model]) -> Vec<Model> {
+    let all_rules: HashMap<i32, &Model> = all_rules.iter().map(|rule| (rule.id, rule)).collect();
+    let mut ids: HashSet<i32> = rule_list.iter().map(|rule| rule.id).collect();
+    let mut rule_list = rule_list;
+    let mut index = 0;
+    while index < rule_list.len() {
+        let parent_id = rule_list[index].rule_id;
+        if parent_id != 0 && ids.insert(parent_id) {
+            if let Some(&parent) = all_rules.get(&parent_id) {
+                rule_list.push(parent.clone());
+            }
+        }
+        index += 1;
+    }
+    rule_list
+}
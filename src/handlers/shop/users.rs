@@ -0,0 +1,497 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+};
+
+use crate::{
+    config::LoginGuardConfig,
+    entities::{sea_orm_active_enums::Status, sessions, user_levels, users},
+    models::{
+        password_resets::{PasswordResetConfirmReq, PasswordResetRequestReq},
+        sessions::RefreshTokenReq,
+        users::{RegisterUserReq, UpdateUserPasswordReq, UserInfo, UserLoginReq, UserLoginRes},
+    },
+    utils::{
+        error::ApiError,
+        login_guard::{check_locked, clear_failures, client_ip, record_failure},
+        mailer::Mailer,
+        password::{hash_password_async, verify_login_password_async, verify_password_async},
+        password_reset::{consume_reset_token, spawn_reset_mail, RESET_THROTTLE_USER},
+        response::{response_t, ResponseT},
+        session::{
+            create_session, revoke_other_sessions, revoke_session, revoke_subject_sessions,
+            rotate_session, ClientInfo, RotateResult, REVOKE_ACCOUNT_DISABLED, REVOKE_LOGOUT,
+            REVOKE_PASSWORD_CHANGED, REVOKE_PASSWORD_RESET, SUBJECT_USER,
+        },
+        token::{CurrentUser, UserClaims, UserTokenKeys},
+        validation::ValidatedJson,
+    },
+};
+
+/// 为会话签发访问令牌，并与刷新令牌一起返回
+fn issue_tokens(
+    keys: &UserTokenKeys,
+    user: &users::Model,
+    session: &sessions::Model,
+    refresh_token: String,
+) -> Result<HttpResponse, ApiError> {
+    let claims = UserClaims::new(user, session.id, keys.0.expires_in);
+    let token = keys.0.encode(&claims).map_err(ApiError::internal)?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(UserLoginRes {
+            token,
+            token_type: String::from("Bearer"),
+            expires_in: keys.0.expires_in,
+            refresh_token,
+            refresh_expires_in: keys.0.refresh_expires_in,
+        }),
+        None,
+    )))
+}
+
+/// 用户注册
+///
+/// 新用户默认启用，并归入 `level` 最低的可用会员等级（无可用等级时为 0）
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `username` ：字符串，用户名（必填）
+/// - `password` ：字符串，密码（必填）
+/// - `nickname`：字符串，昵称
+/// - `phone`：字符串，手机号
+/// - `email`：字符串，邮箱
+///
+/// # 响应
+///
+/// - 成功：状态码 200，新注册的用户（不含密码，附带会员等级）
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 409，用户名已存在
+/// - 失败：状态码 500
+///
+/// # 示例
+///
+/// ```
+/// POST /api/shop/users/register
+/// Connect-Type: application/json
+///
+/// {
+///       "username": "测试",
+///       "password": "test_test_test",
+///       "nickname": null,
+///       "phone": null,
+///       "email": null
+/// }
+/// ```
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/register",
+    request_body = RegisterUserReq,
+    responses(
+        (status = 200, description = "注册成功", body = ResponseT<UserInfo>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 409, description = "用户名已存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "shop"
+)]
+pub async fn register(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    user_data: ValidatedJson<RegisterUserReq>,
+) -> Result<HttpResponse, ApiError> {
+    let user_result = users::Entity::find()
+        .filter(users::Column::Username.eq(user_data.username.clone()))
+        .one(db.get_ref())
+        .await?;
+    if user_result.is_some() {
+        return Err(ApiError::conflict("username已存在"));
+    }
+
+    let user_level_id = user_levels::Entity::find()
+        .filter(user_levels::Column::Status.eq(Status::Enabled))
+        .order_by_asc(user_levels::Column::Level)
+        .one(db.get_ref())
+        .await?
+        .map(|user_level| user_level.id);
+    let password_hash = hash_password_async(&user_data.password).await?;
+
+    let new_user = users::ActiveModel {
+        username: Set(user_data.username.clone()),
+        password: Set(password_hash),
+        status: Set(Status::Enabled),
+        user_level_id: Set(user_level_id),
+        nickname: Set(user_data.nickname.clone()),
+        phone: Set(user_data.phone.clone()),
+        email: Set(user_data.email.clone()),
+        avatar: Set(None),
+        ..Default::default()
+    };
+    let new_user = new_user.insert(db.get_ref()).await?;
+    let user_info = UserInfo::load(db.get_ref(), new_user).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(user_info), None)))
+}
+
+/// 用户登录
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `username` ：字符串，用户名（必填）
+/// - `password` ：字符串，密码（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，访问令牌与刷新令牌
+/// - 失败：状态码 401，用户名或密码错误
+/// - 失败：状态码 403，用户已被禁用
+/// - 失败：状态码 429，失败次数过多，需按 `Retry-After` 等待后重试
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/login",
+    request_body = UserLoginReq,
+    responses(
+        (status = 200, description = "登录成功", body = ResponseT<UserLoginRes>),
+        (status = 401, description = "用户名或密码错误"),
+        (status = 403, description = "用户已被禁用"),
+        (status = 429, description = "登录失败次数过多"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "shop"
+)]
+pub async fn login(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    keys: web::Data<UserTokenKeys>,
+    guard_config: web::Data<LoginGuardConfig>,
+    login_data: web::Json<UserLoginReq>,
+) -> Result<HttpResponse, ApiError> {
+    let ip = client_ip(&req);
+    if let Some(retry_after) =
+        check_locked(db.get_ref(), SUBJECT_USER, &login_data.username, &ip).await?
+    {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+
+    let user = users::Entity::find()
+        .filter(users::Column::Username.eq(login_data.username.clone()))
+        .one(db.get_ref())
+        .await?;
+    let verified = verify_login_password_async(
+        &login_data.password,
+        user.as_ref().map(|user| user.password.as_str()),
+    )
+    .await?;
+    let Some(user) = user.filter(|_| verified) else {
+        record_failure(
+            db.get_ref(),
+            &guard_config,
+            SUBJECT_USER,
+            &login_data.username,
+            &ip,
+        )
+        .await?;
+        return Err(ApiError::unauthorized("用户名或密码错误"));
+    };
+    clear_failures(db.get_ref(), SUBJECT_USER, &user.username).await?;
+    if user.status == Status::Disabled {
+        return Err(ApiError::forbidden("用户已被禁用"));
+    }
+
+    let (session, refresh_token) = create_session(
+        db.get_ref(),
+        SUBJECT_USER,
+        user.id,
+        None,
+        keys.0.refresh_expires_in,
+        &ClientInfo::from_request(&req),
+    )
+    .await?;
+    issue_tokens(&keys, &user, &session, refresh_token)
+}
+
+/// 刷新用户令牌
+///
+/// 每个刷新令牌只能使用一次，成功后返回新的访问令牌与刷新令牌。
+/// 已使用过的刷新令牌再次出现时，视为泄露并吊销同一登录产生的全部会话
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `refresh_token` ：字符串，刷新令牌（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，新的访问令牌与刷新令牌
+/// - 失败：状态码 401，刷新令牌无效、已过期或已被使用
+/// - 失败：状态码 403，用户已被禁用
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/refresh",
+    request_body = RefreshTokenReq,
+    responses(
+        (status = 200, description = "刷新成功", body = ResponseT<UserLoginRes>),
+        (status = 401, description = "刷新令牌无效"),
+        (status = 403, description = "用户已被禁用"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "shop"
+)]
+pub async fn refresh(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    keys: web::Data<UserTokenKeys>,
+    refresh_data: web::Json<RefreshTokenReq>,
+) -> Result<HttpResponse, ApiError> {
+    let rotate_result = rotate_session(
+        db.get_ref(),
+        SUBJECT_USER,
+        &refresh_data.refresh_token,
+        keys.0.refresh_expires_in,
+        &ClientInfo::from_request(&req),
+    )
+    .await?;
+    let (session, refresh_token) = match rotate_result {
+        RotateResult::Rotated(session, refresh_token) => (session, refresh_token),
+        RotateResult::Invalid => return Err(ApiError::unauthorized("刷新令牌无效或已过期")),
+        RotateResult::Reused => {
+            return Err(ApiError::unauthorized(
+                "刷新令牌已被使用，相关会话已全部吊销",
+            ))
+        }
+    };
+
+    let user = users::Entity::find_by_id(session.subject_id)
+        .one(db.get_ref())
+        .await?
+        .filter(|user| user.status == Status::Enabled);
+    let Some(user) = user else {
+        revoke_session(
+            db.get_ref(),
+            SUBJECT_USER,
+            session.subject_id,
+            session.id,
+            REVOKE_ACCOUNT_DISABLED,
+        )
+        .await?;
+        return Err(ApiError::forbidden("用户已被禁用"));
+    };
+    issue_tokens(&keys, &user, &session, refresh_token)
+}
+
+/// 用户退出登录
+///
+/// 吊销当前会话，其访问令牌与刷新令牌随即失效，之后再使用将返回 401
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 401，令牌缺失、无效或已过期
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/logout",
+    responses(
+        (status = 200, description = "退出成功", body = ResponseT<String>),
+        (status = 401, description = "未登录"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "shop"
+)]
+pub async fn logout(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, ApiError> {
+    revoke_session(
+        db.get_ref(),
+        SUBJECT_USER,
+        current_user.claims.sub,
+        current_user.claims.sid,
+        REVOKE_LOGOUT,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 修改用户密码
+///
+/// 修改成功后吊销该用户的其他会话，只保留当前会话
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `old_password` ：字符串，原密码（必填）
+/// - `new_password` ：字符串，新密码（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 401，令牌无效或原密码错误
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    patch,
+    path  = "/api/shop/users/password",
+    request_body = UpdateUserPasswordReq,
+    responses(
+        (status = 200, description = "密码修改成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 401, description = "未登录或原密码错误"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("api_key2" = [])),
+    tag = "shop"
+)]
+pub async fn update_password(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    current_user: CurrentUser,
+    password_data: ValidatedJson<UpdateUserPasswordReq>,
+) -> Result<HttpResponse, ApiError> {
+    if !verify_password_async(&password_data.old_password, &current_user.user.password).await? {
+        return Err(ApiError::unauthorized("原密码错误"));
+    }
+    let password_hash = hash_password_async(&password_data.new_password).await?;
+
+    let mut updated_user: users::ActiveModel = current_user.user.into();
+    updated_user.password = Set(password_hash);
+    updated_user.update(db.get_ref()).await?;
+    revoke_other_sessions(
+        db.get_ref(),
+        SUBJECT_USER,
+        current_user.claims.sub,
+        current_user.claims.sid,
+        REVOKE_PASSWORD_CHANGED,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 申请重置用户密码
+///
+/// 用户存在、已启用且设置了邮箱时，在后台生成一次性的重置令牌并发送到该邮箱。
+/// 为避免泄露账号是否存在，响应不等待发信，无论是否发送邮件、发送是否成功都返回成功。
+/// 同一用户名或 IP 频繁申请时，按登录限流的配置等待或锁定
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `username` ：字符串，用户名（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 429，申请过于频繁
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/password_reset/request",
+    request_body = PasswordResetRequestReq,
+    responses(
+        (status = 200, description = "申请成功", body = ResponseT<String>),
+        (status = 429, description = "申请过于频繁"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "shop"
+)]
+pub async fn request_password_reset(
+    req: HttpRequest,
+    db: web::Data<sea_orm::DatabaseConnection>,
+    mailer: web::Data<dyn Mailer>,
+    guard_config: web::Data<LoginGuardConfig>,
+    reset_data: web::Json<PasswordResetRequestReq>,
+) -> Result<HttpResponse, ApiError> {
+    // 无论账号是否存在都计入申请次数，限流结果同样不会泄露账号是否存在
+    let ip = client_ip(&req);
+    if let Some(retry_after) =
+        check_locked(db.get_ref(), RESET_THROTTLE_USER, &reset_data.username, &ip).await?
+    {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+    record_failure(
+        db.get_ref(),
+        &guard_config,
+        RESET_THROTTLE_USER,
+        &reset_data.username,
+        &ip,
+    )
+    .await?;
+
+    let user = users::Entity::find()
+        .filter(users::Column::Username.eq(reset_data.username.clone()))
+        .one(db.get_ref())
+        .await?
+        .filter(|user| user.status == Status::Enabled);
+    let recipient = user.and_then(|user| {
+        let email = user.email.filter(|email| !email.is_empty())?;
+        Some((user.id, email))
+    });
+
+    if let Some((user_id, email)) = recipient {
+        spawn_reset_mail(db, mailer, SUBJECT_USER, user_id, email);
+    }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
+
+/// 使用重置令牌设置新的用户密码
+///
+/// 成功后令牌作废，该用户的全部会话被吊销，登录失败记录被清除
+///
+/// # 请求体
+///
+/// 需要一个 JSON 对象，包含以下字段：
+/// - `token` ：字符串，邮件中的重置令牌（必填）
+/// - `new_password` ：字符串，新密码（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，令牌无效、已过期或已使用
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    post,
+    path  = "/api/shop/users/password_reset/confirm",
+    request_body = PasswordResetConfirmReq,
+    responses(
+        (status = 200, description = "密码重置成功", body = ResponseT<String>),
+        (status = 400, description = "重置令牌无效"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "shop"
+)]
+pub async fn confirm_password_reset(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    reset_data: ValidatedJson<PasswordResetConfirmReq>,
+) -> Result<HttpResponse, ApiError> {
+    let invalid_token = || ApiError::validation("重置令牌无效或已过期");
+    let user_id = consume_reset_token(db.get_ref(), SUBJECT_USER, &reset_data.token)
+        .await?
+        .ok_or_else(invalid_token)?;
+    let user = users::Entity::find_by_id(user_id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(invalid_token)?;
+    let password_hash = hash_password_async(&reset_data.new_password).await?;
+    let username = user.username.clone();
+
+    let mut updated_user: users::ActiveModel = user.into();
+    updated_user.password = Set(password_hash);
+    updated_user.update(db.get_ref()).await?;
+    revoke_subject_sessions(db.get_ref(), SUBJECT_USER, user_id, REVOKE_PASSWORD_RESET).await?;
+    clear_failures(db.get_ref(), SUBJECT_USER, &username).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
+}
@@ -1,20 +1,64 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
-use sea_orm::{
-    ActiveModelTrait,
-    ActiveValue::{NotSet, Set},
-    ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
-};
+use actix_web::{web, HttpResponse};
+use sea_orm::ActiveValue::{NotSet, Set};
 
 use crate::{
-    entities::skus::{self, ActiveModel, Model},
+    entities::{
+        sea_orm_active_enums::SkuType,
+        skus::{self, ActiveModel, Model},
+    },
     models::{
         skus::{CreateSkuReq, DeleteSkuAllReq, UpdateSkuReq, UpdateSkuStatusReq},
         Pagination,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource, StatusResource},
+        pagination::SortFields,
+    },
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::{ValidatedJson, ValidatedQuery},
+    },
 };
 
+impl CrudResource for skus::Entity {
+    type CreateReq = CreateSkuReq;
+    type UpdateReq = UpdateSkuReq;
+
+    const NOT_FOUND: &'static str = "规格不存在";
+
+    const SORT_FIELDS: SortFields<skus::Column> = SortFields {
+        columns: &[
+            ("order", skus::Column::Order),
+            ("create_time", skus::Column::CreateTime),
+        ],
+        default: "id",
+    };
+
+    /// 规格类型暂不支持设置，均为文字
+    fn new_model(data: &CreateSkuReq) -> ActiveModel {
+        ActiveModel {
+            name: Set(data.name.clone()),
+            status: Set(data.status),
+            order: Set(data.order),
+            r#type: Set(SkuType::Text),
+            default: Set(data.default.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(sku: &mut ActiveModel, data: &UpdateSkuReq) {
+        sku.name = data.name.clone().map(Set).unwrap_or(NotSet);
+        sku.status = data.status.map(Set).unwrap_or(NotSet);
+        sku.order = data.order.map(Set).unwrap_or(NotSet);
+        sku.default = data.default.clone().map(Set).unwrap_or(NotSet);
+    }
+}
+
+impl StatusResource for skus::Entity {
+    const STATUS_COLUMN: skus::Column = skus::Column::Status;
+}
+
 /// 创建新规格
 ///
 /// # 请求体
@@ -28,7 +72,7 @@ use crate::{
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的规格
-/// - 失败：状态码 201，规格已存在
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -50,33 +94,49 @@ use crate::{
     request_body = CreateSkuReq,
     responses(
         (status = 200, description = "规格创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "skus"
 )]
 pub async fn create_sku(
     db: web::Data<sea_orm::DatabaseConnection>,
-    sku_data: web::Json<CreateSkuReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    sku_data: ValidatedJson<CreateSkuReq>,
+) -> Result<HttpResponse, ApiError> {
+    let new_sku = crud::create::<skus::Entity>(db.get_ref(), &sku_data).await?;
 
-    let new_sku = ActiveModel {
-        name: Set(sku_data.name.clone()),
-        status: Set(sku_data.status),
-        order: Set(sku_data.order),
-        r#type: Set(0),
-        default: Set(sku_data.default.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_sku.insert(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_sku), None)))
+}
 
-    match result {
-        Ok(new_sku) => HttpResponse::Ok().json(response_t(Some(200), Some(new_sku), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+/// 获取规格详情
+///
+/// # 路径
+///
+/// - `id` ：整数，规格 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，规格
+/// - 失败：状态码 404，规格不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/skus/{id}",
+    responses(
+        (status = 200, description = "规格获取成功", body = ResponseT<Model>),
+        (status = 404, description = "规格不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "skus"
+)]
+pub async fn get_sku(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let sku = crud::find::<skus::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(sku), None)))
 }
 
 /// 修改规格
@@ -96,7 +156,8 @@ pub async fn create_sku(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的规格
-/// - 失败：状态码 201，规格已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，规格不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -105,40 +166,20 @@ pub async fn create_sku(
     request_body = UpdateSkuReq,
     responses(
         (status = 200, description = "规格更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "规格不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "skus"
 )]
 pub async fn update_sku(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    sku_data: web::Json<UpdateSkuReq>,
-) -> impl Responder {
-    let sku_result = skus::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    sku_data: ValidatedJson<UpdateSkuReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<skus::Entity>(db.get_ref(), *id, &sku_data).await?;
 
-    match sku_result {
-        Ok(Some(sku)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_sku: ActiveModel = sku.into();
-            updated_sku.name = sku_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_sku.status = sku_data.status.map(Set).unwrap_or(NotSet);
-            updated_sku.order = sku_data.order.map(Set).unwrap_or(NotSet);
-            updated_sku.default = sku_data.default.clone().map(Set).unwrap_or(NotSet);
-            updated_sku.update_time = Set(format_time.clone());
-            let result = updated_sku.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改规格状态
@@ -155,7 +196,8 @@ pub async fn update_sku(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的规格
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，规格不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -164,37 +206,20 @@ pub async fn update_sku(
     request_body = UpdateSkuStatusReq,
     responses(
         (status = 200, description = "规格更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "规格不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "skus"
 )]
 pub async fn update_sku_status(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    sku_data: web::Json<UpdateSkuStatusReq>,
-) -> impl Responder {
-    let sku_result = skus::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    sku_data: ValidatedJson<UpdateSkuStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update_status::<skus::Entity>(db.get_ref(), *id, sku_data.status).await?;
 
-    match sku_result {
-        Ok(Some(sku)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_sku: ActiveModel = sku.into();
-            updated_sku.status = Set(sku_data.status.clone());
-            updated_sku.update_time = Set(format_time.clone());
-            let result = updated_sku.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 批量删除规格
@@ -207,6 +232,7 @@ pub async fn update_sku_status(
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -214,26 +240,26 @@ pub async fn update_sku_status(
     path  = "/api/skus/delete_all",
     responses(
         (status = 200, description = "规格删除成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "skus"
 )]
 pub async fn delete_all_sku(
     db: web::Data<sea_orm::DatabaseConnection>,
-    sku_data: web::Json<DeleteSkuAllReq>,
-) -> impl Responder {
-    let result = skus::Entity::delete_many()
-        .filter(skus::Column::Id.is_in(sku_data.ids.clone()))
-        .exec(db.get_ref())
-        .await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    sku_data: ValidatedJson<DeleteSkuAllReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete_many::<skus::Entity>(db.get_ref(), &sku_data.ids).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取规格列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`order`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -253,27 +279,13 @@ pub async fn delete_all_sku(
 )]
 pub async fn get_sku_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let select = skus::Entity::find();
-
-    let paginator = select.paginate(db.get_ref(), size);
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let sku_list = crud::list::<skus::Entity>(db.get_ref(), &query).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(sku_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(sku_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(sku_list)),
+        None,
+    )))
 }
@@ -1,10 +1,5 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
-use sea_orm::{
-    ActiveModelTrait,
-    ActiveValue::{NotSet, Set},
-    EntityTrait, PaginatorTrait,
-};
+use actix_web::{web, HttpResponse};
+use sea_orm::ActiveValue::{NotSet, Set};
 
 use crate::{
     entities::user_levels::{self, ActiveModel, Model},
@@ -14,9 +9,59 @@ use crate::{
         },
         Pagination,
     },
-    utils::response::{response_list_t, response_t, ResponseT},
+    services::{
+        crud::{self, CrudResource, StatusResource},
+        pagination::SortFields,
+    },
+    utils::{
+        error::ApiError,
+        response::{response_t, ResponseListT, ResponseT},
+        validation::{ValidatedJson, ValidatedQuery},
+    },
 };
 
+/// 删除会员等级时，相应用户的会员等级由外键置为空
+impl CrudResource for user_levels::Entity {
+    type CreateReq = CreateUserLevelReq;
+    type UpdateReq = UpdateUserLevelReq;
+
+    const NOT_FOUND: &'static str = "会员等级不存在";
+
+    const SORT_FIELDS: SortFields<user_levels::Column> = SortFields {
+        columns: &[
+            ("level", user_levels::Column::Level),
+            ("discount", user_levels::Column::Discount),
+            ("create_time", user_levels::Column::CreateTime),
+        ],
+        default: "id",
+    };
+
+    fn new_model(data: &CreateUserLevelReq) -> ActiveModel {
+        ActiveModel {
+            name: Set(data.name.clone()),
+            level: Set(data.level),
+            status: Set(data.status),
+            discount: Set(data.discount),
+            max_price: Set(data.max_price),
+            max_time: Set(data.max_time),
+            ..Default::default()
+        }
+    }
+
+    fn apply_update(user_level: &mut ActiveModel, data: &UpdateUserLevelReq) {
+        user_level.name = data.name.clone().map(Set).unwrap_or(NotSet);
+        user_level.level = data.level.map(Set).unwrap_or(NotSet);
+        user_level.status = data.status.map(Set).unwrap_or(NotSet);
+        user_level.discount = data.discount.map(Set).unwrap_or(NotSet);
+        user_level.max_price = data.max_price.map(Set).unwrap_or(NotSet);
+        user_level.max_time = data.max_time.map(Set).unwrap_or(NotSet);
+    }
+}
+
+impl StatusResource for user_levels::Entity {
+    const STATUS_COLUMN: user_levels::Column = user_levels::Column::Status;
+}
+
 /// 创建会员等级
 ///
 /// # 请求体
@@ -26,13 +71,13 @@ use crate::{
 /// - `level` ：整数，等级（必填）
 /// - `status` ：整数，用户状态（必填），0 禁用；1 可用
 /// - `discount` ：整数，折扣率 %（必填）
-/// - `max_price`：整数，累计消费金额（必填）
+/// - `max_price`：字符串，累计消费金额，最多两位小数，小于 99999999.99（必填）
 /// - `max_time`：整数，累计消费次数（必填）
 ///
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的会员等级
-/// - 失败：状态码 201，会员等级已存在
+/// - 失败：状态码 400，请求参数不合法
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -46,7 +91,7 @@ use crate::{
 ///       "status": 0,
 ///       "level": 100,
 ///       "discount": 10,
-///       "max_price": 1000,
+///       "max_price": "1000.00",
 ///       "max_time": 500,
 /// }
 /// ```
@@ -56,36 +101,50 @@ use crate::{
     request_body = CreateUserLevelReq,
     responses(
         (status = 200, description = "会员等级创建成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "user_levels"
 )]
 pub async fn create_user_level(
     db: web::Data<sea_orm::DatabaseConnection>,
-    user_level_data: web::Json<CreateUserLevelReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    user_level_data: ValidatedJson<CreateUserLevelReq>,
+) -> Result<HttpResponse, ApiError> {
+    let new_user_level =
+        crud::create::<user_levels::Entity>(db.get_ref(), &user_level_data).await?;
 
-    let new_user_level = ActiveModel {
-        name: Set(user_level_data.name.clone()),
-        level: Set(user_level_data.level),
-        status: Set(user_level_data.status),
-        discount: Set(user_level_data.discount),
-        max_price: Set(user_level_data.max_price),
-        max_time: Set(user_level_data.max_time),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
-        ..Default::default()
-    };
-    let result = new_user_level.insert(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(new_user_level), None)))
+}
 
-    match result {
-        Ok(new_user_level) => {
-            HttpResponse::Ok().json(response_t(Some(200), Some(new_user_level), None))
-        }
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+/// 获取会员等级详情
+///
+/// # 路径
+///
+/// - `id` ：整数，会员等级 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，会员等级
+/// - 失败：状态码 404，会员等级不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/user_levels/{id}",
+    responses(
+        (status = 200, description = "会员等级获取成功", body = ResponseT<Model>),
+        (status = 404, description = "会员等级不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "user_levels"
+)]
+pub async fn get_user_level(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    let user_level = crud::find::<user_levels::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(user_level), None)))
 }
 
 /// 修改会员等级
@@ -101,13 +160,14 @@ pub async fn create_user_level(
 /// - `level` ：整数，等级
 /// - `status` ：整数，用户状态，0 禁用；1 可用
 /// - `discount` ：整数，折扣率 %
-/// - `max_price`：整数，累计消费金额
+/// - `max_price`：字符串，累计消费金额，最多两位小数，小于 99999999.99
 /// - `max_time`：整数，累计消费次数
 ///
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的会员等级
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，会员等级不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -116,42 +176,20 @@ pub async fn create_user_level(
     request_body = UpdateUserLevelReq,
     responses(
         (status = 200, description = "会员等级更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "会员等级不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "user_levels"
 )]
 pub async fn update_user_level(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    user_level_data: web::Json<UpdateUserLevelReq>,
-) -> impl Responder {
-    let user_level_result = user_levels::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    user_level_data: ValidatedJson<UpdateUserLevelReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update::<user_levels::Entity>(db.get_ref(), *id, &user_level_data).await?;
 
-    match user_level_result {
-        Ok(Some(user_level)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_user_level: ActiveModel = user_level.into();
-            updated_user_level.name = user_level_data.name.clone().map(Set).unwrap_or(NotSet);
-            updated_user_level.level = user_level_data.level.map(Set).unwrap_or(NotSet);
-            updated_user_level.status = user_level_data.status.map(Set).unwrap_or(NotSet);
-            updated_user_level.discount = user_level_data.discount.map(Set).unwrap_or(NotSet);
-            updated_user_level.max_price = user_level_data.max_price.map(Set).unwrap_or(NotSet);
-            updated_user_level.max_time = user_level_data.max_time.map(Set).unwrap_or(NotSet);
-            updated_user_level.update_time = Set(format_time.clone());
-            let result = updated_user_level.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改会员等级状态
@@ -168,7 +206,8 @@ pub async fn update_user_level(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的会员等级
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法
+/// - 失败：状态码 404，会员等级不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -177,37 +216,20 @@ pub async fn update_user_level(
     request_body = UpdateUserLevelStatusReq,
     responses(
         (status = 200, description = "优惠券更新成功", body = ResponseT<Model>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "会员等级不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "user_levels"
 )]
 pub async fn update_user_level_status(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-    user_level_data: web::Json<UpdateUserLevelStatusReq>,
-) -> impl Responder {
-    let user_level_result = user_levels::Entity::find_by_id(*id).one(db.get_ref()).await;
+    id: web::Path<i32>,
+    user_level_data: ValidatedJson<UpdateUserLevelStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    crud::update_status::<user_levels::Entity>(db.get_ref(), *id, user_level_data.status).await?;
 
-    match user_level_result {
-        Ok(Some(user_level)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_user_level: ActiveModel = user_level.into();
-            updated_user_level.status = Set(user_level_data.status.clone());
-            updated_user_level.update_time = Set(format_time.clone());
-            let result = updated_user_level.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除会员等级
@@ -232,19 +254,19 @@ pub async fn update_user_level_status(
 )]
 pub async fn delete_user_level(
     db: web::Data<sea_orm::DatabaseConnection>,
-    id: web::Path<i16>,
-) -> impl Responder {
-    let result = user_levels::Entity::delete_by_id(*id)
-        .exec(db.get_ref())
-        .await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    crud::delete::<user_levels::Entity>(db.get_ref(), *id).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取会员等级列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`level`、`discount`、`create_time`
+///
 /// # 响应
 ///
 /// - 成功：状态码 200，Ok
@@ -264,26 +286,13 @@ pub async fn delete_user_level(
 )]
 pub async fn get_user_level_list(
     db: web::Data<sea_orm::DatabaseConnection>,
-    query: web::Query<Pagination>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
-    let select = user_levels::Entity::find();
-    let paginator = select.paginate(db.get_ref(), size);
+    query: ValidatedQuery<Pagination>,
+) -> Result<HttpResponse, ApiError> {
+    let user_level_list = crud::list::<user_levels::Entity>(db.get_ref(), &query).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
-
-    match result {
-        Ok(user_level_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(user_level_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(ResponseListT::from(user_level_list)),
+        None,
+    )))
 }
@@ -1,19 +1,77 @@
-use actix_web::{web, HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{web, HttpResponse};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    ColumnTrait, EntityTrait, QueryFilter,
 };
 
 use crate::{
-    entities::users::{self, ActiveModel, Model},
-    models::users::{
-        CreateUserReq, GetUserListReq, UpdateUserReq, UpdateUserStatusReq,
+    entities::{
+        user_levels,
+        users::{self, ActiveModel},
+    },
+    models::{
+        users::{CreateUserReq, GetUserListReq, UpdateUserReq, UpdateUserStatusReq, UserInfo},
+        Pagination,
+    },
+    services::pagination::{paginate, SortFields},
+    utils::{
+        error::ApiError,
+        login_guard::clear_failures,
+        password::hash_password_async,
+        response::{response_page_t, response_t, ResponseListT, ResponseT},
+        session::{revoke_subject_sessions, REVOKE_PASSWORD_CHANGED, SUBJECT_USER},
+        validation::{validate_reference, ValidatedJson, ValidatedQuery},
     },
-    utils::response::{response_list_t, response_t, ResponseT},
 };
 
+const SORT_FIELDS: SortFields<users::Column> = SortFields {
+    columns: &[
+        ("username", users::Column::Username),
+        ("create_time", users::Column::CreateTime),
+    ],
+    default: "id",
+};
+
+/// 查看用户资料
+///
+/// # 路径
+///
+/// - `id` ：整数，用户 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，用户及其会员等级，会员等级已被删除时 `user_level` 为空
+/// - 失败：状态码 404，用户不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    responses(
+        (status = 200, description = "用户获取成功", body = ResponseT<UserInfo>),
+        (status = 404, description = "用户不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "users"
+)]
+pub async fn get_user_detail(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i16>,
+) -> Result<HttpResponse, ApiError> {
+    let (user, user_level) = users::Entity::find_by_id(*id)
+        .find_also_related(user_levels::Entity)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("用户不存在"))?;
+
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(UserInfo::with_user_level(user, user_level)),
+        None,
+    )))
+}
+
 /// 创建新用户
 ///
 /// # 请求体
@@ -30,8 +88,8 @@ use crate::{
 ///
 /// # 响应
 ///
-/// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 成功：状态码 200，新创建的用户（不含密码，附带会员等级）
+/// - 失败：状态码 400，请求参数不合法或会员等级不存在
 /// - 失败：状态码 500
 ///
 /// # 示例
@@ -56,37 +114,40 @@ use crate::{
     path  = "/api/users",
     request_body = CreateUserReq,
     responses(
-        (status = 200, description = "角色创建成功", body = ResponseT<Model>),
+        (status = 200, description = "用户创建成功", body = ResponseT<UserInfo>),
+        (status = 400, description = "请求参数不合法或会员等级不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "users"
 )]
 pub async fn create_user(
     db: web::Data<sea_orm::DatabaseConnection>,
-    user_data: web::Json<CreateUserReq>,
-) -> impl Responder {
-    let now = Utc::now();
-    let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    user_data: ValidatedJson<CreateUserReq>,
+) -> Result<HttpResponse, ApiError> {
+    validate_reference::<user_levels::Entity>(
+        db.get_ref(),
+        user_data.user_level_id,
+        "user_level_id",
+        "会员等级不存在",
+    )
+    .await?;
+    let password_hash = hash_password_async(&user_data.password).await?;
 
     let new_user = ActiveModel {
         username: Set(user_data.username.clone()),
-        password: Set(user_data.password.clone()),
+        password: Set(password_hash),
         status: Set(user_data.status),
-        user_level_id: Set(user_data.user_level_id),
+        user_level_id: Set(Some(user_data.user_level_id)),
         nickname: Set(user_data.nickname.clone()),
         phone: Set(user_data.phone.clone()),
         email: Set(user_data.email.clone()),
         avatar: Set(user_data.avatar.clone()),
-        create_time: Set(format_time.clone()),
-        update_time: Set(format_time.clone()),
         ..Default::default()
     };
-    let result = new_user.insert(db.get_ref()).await;
+    let new_user = new_user.insert(db.get_ref()).await?;
+    let user_info = UserInfo::load(db.get_ref(), new_user).await?;
 
-    match result {
-        Ok(new_user) => HttpResponse::Ok().json(response_t(Some(200), Some(new_user), None)),
-        Err(err) => HttpResponse::NotImplemented().json(format!("Error inserting rule: {:?}", err)),
-    }
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(user_info), None)))
 }
 
 /// 修改用户
@@ -99,7 +160,7 @@ pub async fn create_user(
 ///
 /// 需要一个 JSON 对象，包含以下字段：
 /// - `username` ：字符串，用户名
-/// - `password` ：字符串，密码
+/// - `password` ：字符串，密码，修改后该用户的全部会话都会被吊销
 /// - `status` ：整数，用户状态，0 禁用；1 可用
 /// - `user_level_id`：整数，会员等级 ID
 /// - `nickname`：字符串，昵称
@@ -110,7 +171,8 @@ pub async fn create_user(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法或会员等级不存在
+/// - 失败：状态码 404，用户不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -118,7 +180,9 @@ pub async fn create_user(
     path = "/api/users/{id}",
     request_body = UpdateUserReq,
     responses(
-        (status = 200, description = "用户更新成功", body = ResponseT<Model>),
+        (status = 200, description = "用户更新成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法或会员等级不存在"),
+        (status = 404, description = "用户不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "users"
@@ -126,53 +190,62 @@ pub async fn create_user(
 pub async fn update_user(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    user_data: web::Json<UpdateUserReq>,
-) -> impl Responder {
-    let user_result = users::Entity::find_by_id(*id).one(db.get_ref()).await;
+    user_data: ValidatedJson<UpdateUserReq>,
+) -> Result<HttpResponse, ApiError> {
+    let user = users::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("用户不存在"))?;
+    if let Some(user_level_id) = user_data.user_level_id {
+        validate_reference::<user_levels::Entity>(
+            db.get_ref(),
+            user_level_id,
+            "user_level_id",
+            "会员等级不存在",
+        )
+        .await?;
+    }
 
-    match user_result {
-        Ok(Some(user)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_user: ActiveModel = user.into();
-            updated_user.username = user_data.username.clone().map(Set).unwrap_or(NotSet);
-            updated_user.password = user_data.password.clone().map(Set).unwrap_or(NotSet);
-            updated_user.status = user_data.status.map(Set).unwrap_or(NotSet);
-            updated_user.user_level_id = user_data.user_level_id.map(Set).unwrap_or(NotSet);
-            updated_user.nickname = user_data
-                .nickname
-                .clone()
-                .map(|nickname| Set(Some(nickname)))
-                .unwrap_or(NotSet);
-            updated_user.phone = user_data
-                .phone
-                .clone()
-                .map(|phone| Set(Some(phone)))
-                .unwrap_or(NotSet);
-            updated_user.email = user_data
-                .email
-                .clone()
-                .map(|email| Set(Some(email)))
-                .unwrap_or(NotSet);
-            updated_user.avatar = user_data
-                .avatar
-                .clone()
-                .map(|avatar| Set(Some(avatar)))
-                .unwrap_or(NotSet);
-            updated_user.update_time = Set(format_time.clone());
-            let result = updated_user.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
+    let password_hash = match user_data.password.as_deref() {
+        Some(password) => Some(hash_password_async(password).await?),
+        None => None,
+    };
+    let password_changed = password_hash.is_some();
+    let mut updated_user: ActiveModel = user.into();
+    updated_user.username = user_data.username.clone().map(Set).unwrap_or(NotSet);
+    updated_user.password = password_hash.map(Set).unwrap_or(NotSet);
+    updated_user.status = user_data.status.map(Set).unwrap_or(NotSet);
+    updated_user.user_level_id = user_data
+        .user_level_id
+        .map(|user_level_id| Set(Some(user_level_id)))
+        .unwrap_or(NotSet);
+    updated_user.nickname = user_data
+        .nickname
+        .clone()
+        .map(|nickname| Set(Some(nickname)))
+        .unwrap_or(NotSet);
+    updated_user.phone = user_data
+        .phone
+        .clone()
+        .map(|phone| Set(Some(phone)))
+        .unwrap_or(NotSet);
+    updated_user.email = user_data
+        .email
+        .clone()
+        .map(|email| Set(Some(email)))
+        .unwrap_or(NotSet);
+    updated_user.avatar = user_data
+        .avatar
+        .clone()
+        .map(|avatar| Set(Some(avatar)))
+        .unwrap_or(NotSet);
+    let user = updated_user.update(db.get_ref()).await?;
+    if password_changed {
+        revoke_subject_sessions(db.get_ref(), SUBJECT_USER, user.id, REVOKE_PASSWORD_CHANGED)
+            .await?;
     }
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 修改用户状态
@@ -189,7 +262,8 @@ pub async fn update_user(
 /// # 响应
 ///
 /// - 成功：状态码 200，新创建的角色
-/// - 失败：状态码 201，用户名已存在
+/// - 失败：状态码 400，请求参数不合法或会员等级不存在
+/// - 失败：状态码 404，用户不存在
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
@@ -197,7 +271,9 @@ pub async fn update_user(
     path = "/api/users/{id}/update_status",
     request_body = UpdateUserStatusReq,
     responses(
-        (status = 200, description = "角色更新成功", body = ResponseT<Model>),
+        (status = 200, description = "用户状态更新成功", body = ResponseT<String>),
+        (status = 400, description = "请求参数不合法"),
+        (status = 404, description = "用户不存在"),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "users"
@@ -205,30 +281,18 @@ pub async fn update_user(
 pub async fn update_user_status(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-    user_data: web::Json<UpdateUserStatusReq>,
-) -> impl Responder {
-    let role_result = users::Entity::find_by_id(*id).one(db.get_ref()).await;
+    user_data: ValidatedJson<UpdateUserStatusReq>,
+) -> Result<HttpResponse, ApiError> {
+    let user = users::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("用户不存在"))?;
 
-    match role_result {
-        Ok(Some(user)) => {
-            let now = Utc::now();
-            let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            let mut updated_user: ActiveModel = user.into();
-            updated_user.status = Set(user_data.status.clone());
-            updated_user.update_time = Set(format_time.clone());
-            let result = updated_user.update(db.get_ref()).await;
-            if let Ok(_) = result {
-                return HttpResponse::Ok().json(response_t(
-                    Some(200),
-                    Some(String::from("OK")),
-                    None,
-                ));
-            }
-            return HttpResponse::NotImplemented().json(format!("Error inserting rule"));
-        }
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+    let mut updated_user: ActiveModel = user.into();
+    updated_user.status = Set(user_data.status);
+    updated_user.update(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 删除用户
@@ -246,7 +310,7 @@ pub async fn update_user_status(
     delete,
     path  = "/api/users/{id}",
     responses(
-        (status = 200, description = "角色删除成功", body = ResponseT<Model>),
+        (status = 200, description = "用户删除成功", body = ResponseT<String>),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "users"
@@ -254,40 +318,44 @@ pub async fn update_user_status(
 pub async fn delete_user(
     db: web::Data<sea_orm::DatabaseConnection>,
     id: web::Path<i16>,
-) -> impl Responder {
-    let result = users::Entity::delete_by_id(*id).exec(db.get_ref()).await;
-    match result {
-        Ok(_) => HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+) -> Result<HttpResponse, ApiError> {
+    users::Entity::delete_by_id(*id).exec(db.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
 
 /// 获取用户列表
 ///
+/// # 查询
+///
+/// 分页与排序参数见 [`Pagination`]，可排序字段：`id`、`username`、`create_time`
+///
+/// - `keyword` ：字符串，按用户名、手机号或邮箱模糊查询
+/// - `user_level_id` ：整数，会员等级 ID
+///
 /// # 响应
 ///
-/// - 成功：状态码 200，Ok
+/// - 成功：状态码 200，用户列表（不含密码，附带会员等级）
 /// - 失败：状态码 500
 ///
 #[utoipa::path(
     get,
     path = "/api/users",
     params(
+        Pagination,
         GetUserListReq
     ),
     responses(
-        (status = 200, description = "角色列表获取成功", body = ResponseT<Model>),
+        (status = 200, description = "用户列表获取成功", body = ResponseT<ResponseListT<UserInfo>>),
         (status = 500, description = "内部服务器错误")
     ),
     tag = "users"
 )]
 pub async fn get_user_list(
     db: web::Data<sea_orm::DatabaseConnection>,
+    pagination: ValidatedQuery<Pagination>,
     query: web::Query<GetUserListReq>,
-) -> impl Responder {
-    let page = query.page;
-    let size = query.size;
-
+) -> Result<HttpResponse, ApiError> {
     let mut select = users::Entity::find();
     if let Some(user_level_id) = query.user_level_id {
         select = select.filter(users::Column::UserLevelId.eq(user_level_id));
@@ -302,20 +370,49 @@ pub async fn get_user_list(
                     .or(users::Column::Email.like(&keyword_pattern))),
         )
     }
-    let paginator = select.paginate(db.get_ref(), size);
+    let page = paginate(db.get_ref(), select, &pagination, &SORT_FIELDS).await?;
+    let user_list = UserInfo::load_many(db.get_ref(), page.list).await?;
 
-    let total = match paginator.num_items().await {
-        Ok(total) => total,
-        Err(e) => return HttpResponse::InternalServerError().json(format!("Error: {}", e)),
-    };
-    let result = paginator.fetch_page(page - 1).await;
+    Ok(HttpResponse::Ok().json(response_t(
+        Some(200),
+        Some(response_page_t(user_list, page.total, page.next_cursor)),
+        None,
+    )))
+}
 
-    match result {
-        Ok(role_list) => HttpResponse::Ok().json(response_t(
-            Some(200),
-            Some(response_list_t(role_list, total)),
-            None,
-        )),
-        Err(_) => HttpResponse::InternalServerError().finish(),
-    }
+/// 解除用户的登录锁定
+///
+/// 清除该用户用户名的登录失败记录，按 IP 的限制不受影响
+///
+/// # 路径
+///
+/// - `id` ：整数，用户 ID（必填）
+///
+/// # 响应
+///
+/// - 成功：状态码 200，Ok
+/// - 失败：状态码 404，用户不存在
+/// - 失败：状态码 500
+///
+#[utoipa::path(
+    patch,
+    path  = "/api/users/{id}/unlock",
+    responses(
+        (status = 200, description = "用户解锁成功", body = ResponseT<String>),
+        (status = 404, description = "用户不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    tag = "users"
+)]
+pub async fn unlock_user(
+    db: web::Data<sea_orm::DatabaseConnection>,
+    id: web::Path<i16>,
+) -> Result<HttpResponse, ApiError> {
+    let user = users::Entity::find_by_id(*id)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found("用户不存在"))?;
+    clear_failures(db.get_ref(), SUBJECT_USER, &user.username).await?;
+
+    Ok(HttpResponse::Ok().json(response_t(Some(200), Some(String::from("OK")), None)))
 }
@@ -0,0 +1,12 @@
+pub mod api_docs;
+pub mod app;
+pub mod commands;
+pub mod config;
+pub mod entities;
+pub mod handlers;
+pub mod logging;
+pub mod middlewares;
+pub mod models;
+pub mod routers;
+pub mod services;
+pub mod utils;
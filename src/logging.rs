@@ -0,0 +1,22 @@
+use tracing_subscriber::{fmt, EnvFilter};
+
+use crate::config::{LogConfig, LogFormat};
+
+/// 初始化日志输出
+///
+/// 设置了 `RUST_LOG` 时按其过滤，否则使用配置中的级别。
+/// 依赖库通过 `log` 输出的日志同样会被收集
+pub fn init(config: &LogConfig) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(config.level_filter().to_string()));
+    let subscriber = fmt().with_env_filter(filter);
+    match config.format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init(),
+    }
+}
@@ -0,0 +1,250 @@
+use actix_web::{
+    body::{to_bytes, BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    error::{ErrorInternalServerError, ErrorPayloadTooLarge},
+    http::{header, Method},
+    middleware::Next,
+    web, Error, HttpMessage,
+};
+use futures_util::StreamExt;
+use sea_orm::{
+    sea_query::{Alias, Expr},
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    entities::{
+        admins, audit_logs, coupons, goods, image_classes, images, notices, role_rules, roles,
+        rules, skus, user_levels, users,
+    },
+    utils::token::AdminClaims,
+};
+
+/// 不记录的接口，会话变更已记录在 `sessions` 表中
+const SKIPPED_ROUTES: &[(Method, &str)] = &[(Method::POST, "/api/admins/logout")];
+
+/// 缓存的请求体上限，与 `web::Json` 的默认上限一致
+const BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+pub const OUTCOME_SUCCESS: &str = "success";
+pub const OUTCOME_FAILURE: &str = "failure";
+
+fn is_mutation(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// 从路径中解析操作的数据表与 ID，如 `/api/goods/12/check` 解析为 `("goods", [12])`
+fn parse_target(path: &str) -> (String, Vec<i32>) {
+    let mut segments = path
+        .trim_start_matches("/api/")
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let target = segments.next().unwrap_or_default();
+    let mut next = segments.next();
+    if target == "admins" && next == Some("manager") {
+        next = segments.next();
+    }
+    let ids = next
+        .and_then(|segment| segment.parse().ok())
+        .into_iter()
+        .collect();
+    (target.to_string(), ids)
+}
+
+/// 批量接口的 ID 位于请求体的 `ids` 字段中
+fn body_ids(body: &Value) -> Vec<i32> {
+    body.get("ids")
+        .and_then(Value::as_array)
+        .map(|ids| {
+            ids.iter()
+                .filter_map(Value::as_i64)
+                .filter_map(|id| i32::try_from(id).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn find_rows<E>(db: &DatabaseConnection, ids: &[i32]) -> Result<Value, DbErr>
+where
+    E: EntityTrait,
+    E::Model: Serialize,
+{
+    let rows = E::find()
+        .filter(Expr::col(Alias::new("id")).is_in(ids.iter().copied()))
+        .all(db)
+        .await?;
+    Ok(serde_json::to_value(rows).unwrap_or_default())
+}
+
+/// 角色的权限保存在 `role_rules` 表中，快照中以 `rule_ids` 字段附带角色拥有的规则
+async fn find_roles(db: &DatabaseConnection, ids: &[i32]) -> Result<Value, DbErr> {
+    let mut rows = find_rows::<roles::Entity>(db, ids).await?;
+    let role_rules = role_rules::Entity::find()
+        .filter(role_rules::Column::RoleId.is_in(ids.iter().copied()))
+        .order_by_asc(role_rules::Column::RuleId)
+        .all(db)
+        .await?;
+    if let Some(rows) = rows.as_array_mut() {
+        for row in rows.iter_mut().filter_map(Value::as_object_mut) {
+            let role_id = row.get("id").and_then(Value::as_i64);
+            let rule_ids: Vec<i32> = role_rules
+                .iter()
+                .filter(|role_rule| Some(i64::from(role_rule.role_id)) == role_id)
+                .map(|role_rule| role_rule.rule_id)
+                .collect();
+            row.insert(String::from("rule_ids"), Value::from(rule_ids));
+        }
+    }
+    Ok(rows)
+}
+
+/// 读取数据快照，数据不存在时为空，管理员与用户的密码字段不会写入日志
+async fn snapshot(db: &DatabaseConnection, target: &str, ids: &[i32]) -> Option<Value> {
+    if ids.is_empty() {
+        return None;
+    }
+    let result = match target {
+        "admins" => find_rows::<admins::Entity>(db, ids).await,
+        "coupons" => find_rows::<coupons::Entity>(db, ids).await,
+        "goods" => find_rows::<goods::Entity>(db, ids).await,
+        "image_classes" => find_rows::<image_classes::Entity>(db, ids).await,
+        "images" => find_rows::<images::Entity>(db, ids).await,
+        "notices" => find_rows::<notices::Entity>(db, ids).await,
+        "roles" => find_roles(db, ids).await,
+        "rules" => find_rows::<rules::Entity>(db, ids).await,
+        "skus" => find_rows::<skus::Entity>(db, ids).await,
+        "user_levels" => find_rows::<user_levels::Entity>(db, ids).await,
+        "users" => find_rows::<users::Entity>(db, ids).await,
+        _ => return None,
+    };
+    let Value::Array(mut rows) = result
+        .inspect_err(|err| tracing::error!(error = %err, target, "failed to read audit snapshot"))
+        .ok()?
+    else {
+        return None;
+    };
+    if rows.is_empty() {
+        return None;
+    }
+    for row in rows.iter_mut().filter_map(Value::as_object_mut) {
+        row.remove("password");
+    }
+    Some(Value::Array(rows))
+}
+
+/// 后台数据变更审计
+///
+/// 记录已登录管理员的每个 `POST`/`PUT`/`PATCH`/`DELETE` 请求：操作人、路由、
+/// 数据表与 ID、变更前后的数据、客户端 IP 以及操作结果。
+/// 需注册在权限中间件之内，以便读取 [`AdminClaims`]。写日志失败只记录错误，不影响请求本身
+pub async fn audit(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let method = req.method().clone();
+    let path = req.path().to_string();
+    let claims = req.extensions().get::<AdminClaims>().cloned();
+    let db = req.app_data::<web::Data<DatabaseConnection>>().cloned();
+    let skipped = SKIPPED_ROUTES
+        .iter()
+        .any(|(skipped_method, skipped_path)| *skipped_method == method && *skipped_path == path);
+    let (Some(claims), Some(db), true, false) = (claims, db, is_mutation(&method), skipped) else {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    };
+
+    let (target, mut target_ids) = parse_target(&path);
+    let is_json = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if is_json {
+        let mut payload = req.take_payload();
+        let mut body = web::BytesMut::new();
+        while let Some(chunk) = payload.next().await {
+            let chunk = chunk?;
+            if body.len() + chunk.len() > BODY_LIMIT {
+                return Err(ErrorPayloadTooLarge("请求体过大"));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        let body = body.freeze();
+        if target_ids.is_empty() {
+            if let Ok(value) = serde_json::from_slice::<Value>(&body) {
+                target_ids = body_ids(&value);
+            }
+        }
+        let (_, mut new_payload) = actix_http::h1::Payload::create(true);
+        new_payload.unread_data(body);
+        req.set_payload(new_payload.into());
+    }
+
+    // 新建数据时还没有 ID，变更前快照为空
+    let before = snapshot(db.get_ref(), &target, &target_ids).await;
+    let ip = req.peer_addr().map(|addr| addr.ip().to_string());
+
+    let res = next.call(req).await?;
+    let (http_req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let body = to_bytes(body)
+        .await
+        .map_err(|err| ErrorInternalServerError(err.into().to_string()))?;
+
+    let status = res.status();
+    let envelope = serde_json::from_slice::<Value>(&body).ok();
+    let code = envelope
+        .as_ref()
+        .and_then(|envelope| envelope.get("code"))
+        .and_then(Value::as_i64);
+    let outcome = if status.is_success() && code.is_none_or(|code| code == 200) {
+        OUTCOME_SUCCESS
+    } else {
+        OUTCOME_FAILURE
+    };
+    // 创建接口没有路径 ID，从响应的 `data.id` 中读取新数据的 ID
+    if target_ids.is_empty() && outcome == OUTCOME_SUCCESS {
+        if let Some(id) = envelope
+            .as_ref()
+            .and_then(|envelope| envelope.pointer("/data/id"))
+            .and_then(Value::as_i64)
+            .and_then(|id| i32::try_from(id).ok())
+        {
+            target_ids.push(id);
+        }
+    }
+    // 数据被删除后变更后快照为空；删除角色的单条权限等子资源时仍会记录角色的最新数据
+    let after = snapshot(db.get_ref(), &target, &target_ids).await;
+
+    let audit_log = audit_logs::ActiveModel {
+        admin_id: Set(claims.sub),
+        method: Set(method.to_string()),
+        route: Set(http_req.match_pattern().unwrap_or_else(|| path.clone())),
+        path: Set(path),
+        target: Set(target),
+        target_ids: Set(Value::from(target_ids)),
+        before: Set(before),
+        after: Set(after),
+        ip: Set(ip),
+        status_code: Set(i32::from(status.as_u16())),
+        outcome: Set(outcome.to_string()),
+        ..Default::default()
+    };
+    if let Err(err) = audit_log.insert(db.get_ref()).await {
+        tracing::error!(error = %err, "failed to write audit log");
+    }
+
+    Ok(ServiceResponse::new(
+        http_req,
+        res.set_body(BoxBody::new(body)),
+    ))
+}
@@ -0,0 +1,40 @@
+use std::time::Instant;
+
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    web, Error,
+};
+
+use crate::utils::metrics::{Metrics, UNMATCHED_ROUTE};
+
+/// 记录请求数与请求耗时
+///
+/// 按路由模板（如 `/api/goods/{id}`）而不是实际路径统计，未匹配到路由的请求统一记为 `<unmatched>`
+pub async fn metrics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let Some(metrics) = req.app_data::<web::Data<Metrics>>().cloned() else {
+        return next.call(req).await;
+    };
+    let method = req.method().to_string();
+    let route = req
+        .match_pattern()
+        .unwrap_or_else(|| String::from(UNMATCHED_ROUTE));
+    let started_at = Instant::now();
+
+    let result = next.call(req).await;
+    let status = match &result {
+        Ok(res) => res.status(),
+        Err(err) => err.as_response_error().status_code(),
+    };
+    metrics.observe_request(
+        &method,
+        &route,
+        status.as_u16(),
+        started_at.elapsed().as_secs_f64(),
+    );
+    result
+}
@@ -0,0 +1,4 @@
+pub mod audit;
+pub mod metrics;
+pub mod permission;
+pub mod request_id;
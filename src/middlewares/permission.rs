@@ -0,0 +1,301 @@
+use std::sync::LazyLock;
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{ResourceDef, ServiceRequest, ServiceResponse},
+    http::Method,
+    middleware::Next,
+    web, Error, HttpMessage,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter};
+
+use crate::{
+    entities::{admins, roles, rules, sea_orm_active_enums::Status},
+    utils::{
+        error::ApiError,
+        session::{find_active_session, SUBJECT_ADMIN},
+        token::{bearer_token, AdminClaims, AdminTokenKeys, ADMIN_AUDIENCE},
+    },
+};
+
+/// 无需登录即可访问的接口
+const PUBLIC_ROUTES: &[(Method, &str)] = &[
+    (Method::POST, "/api/admins/login"),
+    (Method::POST, "/api/admins/refresh"),
+    (Method::POST, "/api/admins/password_reset/request"),
+    (Method::POST, "/api/admins/password_reset/confirm"),
+];
+
+/// 登录即可访问、无需对应规则的接口
+const LOGIN_ROUTES: &[(Method, &str)] = &[
+    (Method::POST, "/api/admins/logout"),
+    (Method::GET, "/api/admins/me"),
+    (Method::GET, "/api/admins/menus"),
+];
+
+/// 接口与 `rules.condition` 的对应关系，按顺序匹配，静态路径需排在动态路径之前
+const ROUTE_CONDITIONS: &[(Method, &str, &str)] = &[
+    (Method::POST, "/api/rules/init", "createRule"),
+    (Method::GET, "/api/rules", "getRuleList"),
+    (Method::POST, "/api/rules", "createRule"),
+    (Method::PUT, "/api/rules/{id}", "updateRule"),
+    (Method::DELETE, "/api/rules/{id}", "deleteRule"),
+    (
+        Method::PATCH,
+        "/api/rules/{id}/update_status",
+        "updateRuleStatus",
+    ),
+    (Method::POST, "/api/admins/manager", "createManager"),
+    (Method::GET, "/api/admins/manager/{id}", "getManagerList"),
+    (Method::PUT, "/api/admins/manager/{id}", "updateManager"),
+    (Method::DELETE, "/api/admins/manager/{id}", "deleteManager"),
+    (
+        Method::PATCH,
+        "/api/admins/manager/{id}/update_status",
+        "updateManagerStatus",
+    ),
+    (
+        Method::PATCH,
+        "/api/admins/manager/{id}/unlock",
+        "updateManagerStatus",
+    ),
+    (
+        Method::GET,
+        "/api/admins/manager/{id}/sessions",
+        "getManagerList",
+    ),
+    (
+        Method::DELETE,
+        "/api/admins/manager/{id}/sessions",
+        "deleteManager",
+    ),
+    (
+        Method::DELETE,
+        "/api/admins/manager/{id}/sessions/{session_id}",
+        "deleteManager",
+    ),
+    (Method::GET, "/api/notices", "getNoticeList"),
+    (Method::POST, "/api/notices", "createNotice"),
+    (Method::GET, "/api/notices/{id}", "getNoticeList"),
+    (Method::PUT, "/api/notices/{id}", "updateNotice"),
+    (Method::DELETE, "/api/notices/{id}", "deleteNotice"),
+    (Method::GET, "/api/roles", "getRoleList"),
+    (Method::POST, "/api/roles", "createRole"),
+    (Method::GET, "/api/roles/{id}", "getRoleList"),
+    (Method::PUT, "/api/roles/{id}", "updateRole"),
+    (Method::DELETE, "/api/roles/{id}", "deleteRole"),
+    (
+        Method::PATCH,
+        "/api/roles/{id}/update_status",
+        "updateRoleStatus",
+    ),
+    (Method::PATCH, "/api/roles/{id}/set_rules", "setRoleRules"),
+    (Method::GET, "/api/roles/{id}/rules", "getRoleList"),
+    (
+        Method::POST,
+        "/api/roles/{id}/rules/{rule_id}",
+        "grantRoleRule",
+    ),
+    (
+        Method::DELETE,
+        "/api/roles/{id}/rules/{rule_id}",
+        "revokeRoleRule",
+    ),
+    (Method::GET, "/api/users", "getUserList"),
+    (Method::POST, "/api/users", "createUser"),
+    (Method::GET, "/api/users/{id}", "getUserList"),
+    (Method::PUT, "/api/users/{id}", "updateUser"),
+    (
+        Method::PATCH,
+        "/api/users/{id}/update_status",
+        "updateUserStatus",
+    ),
+    (Method::PATCH, "/api/users/{id}/unlock", "updateUserStatus"),
+    (Method::DELETE, "/api/users/{id}", "deleteUser"),
+    (Method::GET, "/api/skus", "getSkusList"),
+    (Method::POST, "/api/skus", "createSkus"),
+    (Method::DELETE, "/api/skus/delete_all", "deleteSkus"),
+    (Method::GET, "/api/skus/{id}", "getSkusList"),
+    (Method::PUT, "/api/skus/{id}", "updateSkus"),
+    (
+        Method::PATCH,
+        "/api/skus/{id}/update_status",
+        "updateSkusStatus",
+    ),
+    (Method::GET, "/api/coupons", "getCouponList"),
+    (Method::POST, "/api/coupons", "createCoupon"),
+    (Method::GET, "/api/coupons/{id}", "getCouponList"),
+    (Method::PUT, "/api/coupons/{id}", "updateCoupon"),
+    (
+        Method::PATCH,
+        "/api/coupons/{id}/update_status",
+        "updateCouponStatus",
+    ),
+    (Method::DELETE, "/api/coupons/{id}", "deleteCoupon"),
+    (Method::GET, "/api/user_levels", "getUserLevelList"),
+    (Method::POST, "/api/user_levels", "createUserLevel"),
+    (Method::GET, "/api/user_levels/{id}", "getUserLevelList"),
+    (Method::PUT, "/api/user_levels/{id}", "updateUserLevel"),
+    (
+        Method::PATCH,
+        "/api/user_levels/{id}/update_status",
+        "updateUserLevelStatus",
+    ),
+    (Method::DELETE, "/api/user_levels/{id}", "deleteUserLevel"),
+    (Method::POST, "/api/images/upload", "uploadImage"),
+    (Method::DELETE, "/api/images/delete_all", "deleteImage"),
+    (Method::PUT, "/api/images/{id}", "updateImage"),
+    (Method::GET, "/api/image_classes", "getImageClassList"),
+    (
+        Method::GET,
+        "/api/image_classes/{id}/image",
+        "getCurrentImageList",
+    ),
+    (Method::POST, "/api/image_classes", "createImageClass"),
+    (Method::GET, "/api/image_classes/{id}", "getImageClassList"),
+    (Method::PUT, "/api/image_classes/{id}", "updateImageClass"),
+    (
+        Method::DELETE,
+        "/api/image_classes/{id}",
+        "deleteImageClass",
+    ),
+    (Method::GET, "/api/goods", "getGoodsList"),
+    (Method::POST, "/api/goods", "createGoods"),
+    (Method::DELETE, "/api/goods", "destroyGoods"),
+    (
+        Method::PATCH,
+        "/api/goods/status/batch",
+        "updateGoodsStatus",
+    ),
+    (Method::PATCH, "/api/goods/delete/batch", "deleteGoods"),
+    (Method::PATCH, "/api/goods/restore/batch", "restoreGoods"),
+    (Method::GET, "/api/goods/{id}", "readGoods"),
+    (Method::PUT, "/api/goods/{id}", "updateGoods"),
+    (Method::PATCH, "/api/goods/{id}/check", "checkGoods"),
+    (Method::GET, "/api/audit_logs", "getAuditLogList"),
+];
+
+static ROUTE_CONDITION_DEFS: LazyLock<Vec<(Method, ResourceDef, &'static str)>> =
+    LazyLock::new(|| {
+        ROUTE_CONDITIONS
+            .iter()
+            .map(|(method, pattern, condition)| {
+                (method.clone(), ResourceDef::new(*pattern), *condition)
+            })
+            .collect()
+    });
+
+/// 查找请求对应的权限别名
+///
+/// `path` 需为路由匹配使用的已解码路径，否则经百分号编码的路径会绕过规则匹配
+fn route_condition(method: &Method, path: &str) -> Option<&'static str> {
+    ROUTE_CONDITION_DEFS
+        .iter()
+        .find(|(route_method, def, _)| route_method == method && def.is_match(path))
+        .map(|(_, _, condition)| *condition)
+}
+
+fn is_listed(routes: &[(Method, &str)], method: &Method, path: &str) -> bool {
+    routes
+        .iter()
+        .any(|(route_method, route_path)| route_method == method && *route_path == path)
+}
+
+/// 查找角色可用的全部规则：角色需处于启用状态，规则需处于启用状态；未分配角色时为空
+pub async fn find_role_rules(
+    db: &DatabaseConnection,
+    role_id: Option<i32>,
+) -> Result<Vec<rules::Model>, DbErr> {
+    let Some(role_id) = role_id else {
+        return Ok(vec![]);
+    };
+    let Some(role) = roles::Entity::find_by_id(role_id)
+        .filter(roles::Column::Status.eq(Status::Enabled))
+        .one(db)
+        .await?
+    else {
+        return Ok(vec![]);
+    };
+    role.find_related(rules::Entity)
+        .filter(rules::Column::Status.eq(Status::Enabled))
+        .all(db)
+        .await
+}
+
+/// 校验令牌、会话与角色规则，通过时返回令牌载荷，角色与超级管理员标记替换为数据库中的值
+async fn authorize(req: &ServiceRequest) -> Result<AdminClaims, ApiError> {
+    let keys = req
+        .app_data::<web::Data<AdminTokenKeys>>()
+        .ok_or_else(|| ApiError::internal("未配置管理员令牌密钥"))?;
+    let token =
+        bearer_token(req.request()).ok_or_else(|| ApiError::unauthorized("缺少访问令牌"))?;
+    let mut claims = keys
+        .0
+        .decode::<AdminClaims>(token, ADMIN_AUDIENCE)
+        .map_err(|_| ApiError::unauthorized("访问令牌无效或已过期"))?;
+
+    let db = req
+        .app_data::<web::Data<DatabaseConnection>>()
+        .ok_or_else(|| ApiError::internal("未配置数据库连接"))?;
+    let session = find_active_session(db.get_ref(), SUBJECT_ADMIN, claims.sid).await?;
+    if session.is_none_or(|session| session.subject_id != claims.sub) {
+        return Err(ApiError::unauthorized("会话已失效"));
+    }
+
+    // 角色与超级管理员标记以数据库为准，修改后无需等待令牌过期即可生效
+    let admin = admins::Entity::find_by_id(claims.sub)
+        .one(db.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::unauthorized("会话已失效"))?;
+    claims.role_id = admin.role_id;
+    claims.is_super = admin.is_super;
+
+    if claims.is_super != 1 {
+        let path = req.match_info().as_str();
+        let granted = match route_condition(req.method(), path) {
+            Some(condition) => find_role_rules(db.get_ref(), claims.role_id)
+                .await?
+                .iter()
+                .any(|rule| {
+                    rule.condition == condition
+                        && rule.method.eq_ignore_ascii_case(req.method().as_str())
+                }),
+            // 未登记的接口默认拒绝，新增接口时需在上方登记
+            None => is_listed(LOGIN_ROUTES, req.method(), path),
+        };
+        if !granted {
+            return Err(ApiError::forbidden("没有访问该接口的权限"));
+        }
+    }
+    Ok(claims)
+}
+
+/// 后台接口权限校验
+///
+/// - 公开接口直接放行
+/// - 校验 `Authorization: Bearer <token>` 及其所属会话，失败返回 401
+/// - 超级管理员（`admins.is_super = 1`）直接放行
+/// - 其余管理员需通过 `role_rules` 拥有与请求方法、接口别名相符的启用规则，否则返回 403
+/// - 既不在 [`LOGIN_ROUTES`] 中、也没有对应规则的接口，非超级管理员一律返回 403
+///
+/// 路径按路由匹配时使用的已解码路径比较，与实际命中的路由保持一致。
+///
+/// 角色与超级管理员标记取自 `admins` 表而非令牌，修改后立即生效。
+/// 校验通过后，令牌载荷会以 [`AdminClaims`] 的形式写入请求扩展，其中的角色同样以数据库为准。
+/// 校验失败时直接返回错误响应而不是 `Err`，以便外层中间件为其加上请求 ID 等响应头
+pub async fn permission(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if !is_listed(PUBLIC_ROUTES, req.method(), req.match_info().as_str()) {
+        match authorize(&req).await {
+            Ok(claims) => {
+                req.extensions_mut().insert(claims);
+            }
+            Err(err) => return Ok(req.error_response(err)),
+        }
+    }
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
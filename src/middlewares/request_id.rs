@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue, USER_AGENT},
+    middleware::Next,
+    Error,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 沿用客户端或网关传入的请求 ID，不合法时重新生成，避免日志被注入任意内容
+fn incoming_request_id(req: &ServiceRequest) -> Option<String> {
+    let value = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?;
+    let valid = !value.is_empty()
+        && value.len() <= 64
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+    valid.then(|| value.to_string())
+}
+
+/// 请求 ID 与访问日志
+///
+/// 为每个请求分配 ID，请求处理期间的日志都位于带有该 ID 的 `request` span 中，
+/// 响应通过 `X-Request-Id` 头返回该 ID。请求结束后输出一条访问日志，包含状态码与耗时。
+/// 需注册为最外层的中间件
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let request_id = incoming_request_id(&req).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.path(),
+    );
+    let ip = req.peer_addr().map(|addr| addr.ip().to_string());
+    let user_agent = req
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let started_at = Instant::now();
+
+    let result = next.call(req).instrument(span.clone()).await;
+    let latency_ms = (started_at.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0;
+    let _entered = span.enter();
+    // 内层中间件返回的 `Err` 由 actix 在此之后转换为响应，无法再添加响应头
+    let mut res = match result {
+        Ok(res) => res.map_into_boxed_body(),
+        Err(err) => {
+            let status = err.as_response_error().status_code().as_u16();
+            tracing::warn!(status, latency_ms, ip, user_agent, error = %err, "request failed");
+            return Err(err);
+        }
+    };
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    let status = res.status().as_u16();
+    if res.status().is_server_error() {
+        tracing::warn!(status, latency_ms, ip, user_agent, "request completed");
+    } else {
+        tracing::info!(status, latency_ms, ip, user_agent, "request completed");
+    }
+    Ok(res)
+}
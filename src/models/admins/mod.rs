@@ -1,28 +1,159 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr, LoaderTrait};
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::{admins::Model, roles, sea_orm_active_enums::Status},
+    models::{roles::RoleSummary, rules::Rule},
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "username": "admin2",
     "role_id": 38,
     "status": 1,
     "avatar": null,
+    "email": "admin2@example.com",
     "password": "xxxxxxxxxx",
 }))]
-
 pub struct CreateAdmin {
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
     pub username: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
     pub password: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub role_id: i32,
-    pub status: i32,
+    pub status: Status,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub avatar: Option<String>,
+    #[validate(email(message = "邮箱格式不正确"))]
+    #[schema(format = Email)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example  = json!({
+    "status": 1,
+}))]
+pub struct UpdateAdminStatus {
+    pub status: Status,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 #[schema(example  = json!({
+    "username": "admin",
+    "password": "xxxxxxxxxx",
+}))]
+pub struct AdminLoginReq {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "token": "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...",
+    "token_type": "Bearer",
+    "expires_in": 7200,
+    "refresh_token": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    "refresh_expires_in": 604800,
+}))]
+pub struct AdminLoginRes {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 1,
+    "username": "admin",
+    "role_id": 38,
     "status": 1,
+    "avatar": null,
+    "is_super": 1,
+    "email": "admin@example.com",
+    "create_time": "2022-06-17T19:57:32Z",
+    "update_time": "2022-06-17T19:57:32Z",
+    "role": {
+        "id": 38,
+        "name": "运营",
+        "status": 1,
+    },
 }))]
+pub struct AdminInfo {
+    pub id: i32,
+    pub username: String,
+    pub role_id: Option<i32>,
+    pub status: Status,
+    pub avatar: Option<String>,
+    pub is_super: i32,
+    pub email: Option<String>,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+    /// 管理员所属角色，角色不存在时为空
+    pub role: Option<RoleSummary>,
+}
 
-pub struct UpdateAdminStatus {
-    pub status: i32,
+impl From<Model> for AdminInfo {
+    fn from(admin: Model) -> Self {
+        AdminInfo {
+            id: admin.id,
+            username: admin.username,
+            role_id: admin.role_id,
+            status: admin.status,
+            avatar: admin.avatar,
+            is_super: admin.is_super,
+            email: admin.email,
+            create_time: admin.create_time,
+            update_time: admin.update_time,
+            role: None,
+        }
+    }
+}
+
+impl AdminInfo {
+    /// 转换管理员及其所属角色
+    pub fn with_role(admin: Model, role: Option<roles::Model>) -> Self {
+        AdminInfo {
+            role: role.map(RoleSummary::from),
+            ..AdminInfo::from(admin)
+        }
+    }
+
+    /// 转换管理员列表，并批量查询各自的角色
+    pub async fn load_many(
+        db: &DatabaseConnection,
+        admins: Vec<Model>,
+    ) -> Result<Vec<AdminInfo>, DbErr> {
+        let roles = admins.load_one(roles::Entity, db).await?;
+        Ok(admins
+            .into_iter()
+            .zip(roles)
+            .map(|(admin, role)| AdminInfo::with_role(admin, role))
+            .collect())
+    }
+
+    /// 转换单个管理员，并查询其角色
+    pub async fn load(db: &DatabaseConnection, admin: Model) -> Result<AdminInfo, DbErr> {
+        let mut admin_infos = AdminInfo::load_many(db, vec![admin]).await?;
+        Ok(admin_infos.remove(0))
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "menus": [],
+    "conditions": ["getRoleList", "createRole"],
+}))]
+pub struct AdminMenuRes {
+    pub menus: Vec<Rule>,
+    pub conditions: Vec<String>,
 }
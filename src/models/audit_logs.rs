@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct GetAuditLogListReq {
+    /// 操作的管理员 ID
+    pub admin_id: Option<i32>,
+    /// 操作的数据表，如 `goods`
+    pub target: Option<String>,
+    /// 请求方法，如 `DELETE`
+    pub method: Option<String>,
+    /// 操作结果：`success` 或 `failure`
+    pub outcome: Option<String>,
+    /// 起始时间，RFC 3339 格式，如 `2024-01-01T00:00:00Z`
+    pub start_time: Option<DateTime<Utc>>,
+    /// 结束时间，RFC 3339 格式
+    pub end_time: Option<DateTime<Utc>>,
+}
@@ -1,69 +1,122 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::sea_orm_active_enums::{CouponType, Status},
+    utils::{
+        money::Money,
+        validation::{validate_positive_price, validate_price, validate_time_range},
+    },
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "优惠券名称",
     "type": 0,
-    "value": 20.00,
+    "value": "20.00",
     "total": 100,
     "used": 50,
-    "min_price": 50.00,
-    "start_time": "2022-06-11 20:14:35",
-    "end_time": "2022-10-05 14:01:15",
+    "min_price": "50.00",
+    "start_time": "2022-06-11T20:14:35Z",
+    "end_time": "2022-10-05T14:01:15Z",
     "order": 50,
     "status": 0,
     "desc": "描述",
 }))]
-
+#[validate(schema(function = "validate_create_coupon"))]
 pub struct CreateCouponReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
-    pub r#type: i32,
-    pub value: f64,
+    pub r#type: CouponType,
+    #[validate(custom(function = "validate_positive_price"))]
+    pub value: Money,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub total: i32,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub used: i32,
-    pub min_price: f64,
-    pub start_time: String,
-    pub end_time: String,
-    pub status: i32,
+    #[validate(custom(function = "validate_price"))]
+    pub min_price: Money,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub status: Status,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: i32,
     pub desc: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "优惠券名称",
     "type": 0,
-    "value": 20.00,
+    "value": "20.00",
     "total": 100,
     "used": 50,
-    "min_price": 50.00,
-    "start_time": "2022-06-11 20:14:35",
-    "end_time": "2022-10-05 14:01:15",
+    "min_price": "50.00",
+    "start_time": "2022-06-11T20:14:35Z",
+    "end_time": "2022-10-05T14:01:15Z",
     "order": 50,
     "status": 0,
     "desc": "描述",
 }))]
-
+#[validate(schema(function = "validate_update_coupon"))]
 pub struct UpdateCouponReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
-    pub r#type: Option<i32>,
-    pub value: Option<f64>,
+    pub r#type: Option<CouponType>,
+    #[validate(custom(function = "validate_positive_price"))]
+    pub value: Option<Money>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub total: Option<i32>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub used: Option<i32>,
-    pub min_price: Option<f64>,
-    pub start_time: Option<String>,
-    pub end_time: Option<String>,
-    pub status: Option<i32>,
+    #[validate(custom(function = "validate_price"))]
+    pub min_price: Option<Money>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub status: Option<Status>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: Option<i32>,
     pub desc: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateCouponStatusReq {
-    pub status: i32,
+    pub status: Status,
+}
+
+/// 使用量不能超过发行量
+pub fn validate_coupon_usage(used: i32, total: i32) -> Result<(), ValidationError> {
+    if used > total {
+        return Err(ValidationError::new("usage").with_message("使用量不能超过发行量".into()));
+    }
+    Ok(())
+}
+
+fn validate_create_coupon(coupon: &CreateCouponReq) -> Result<(), ValidationError> {
+    validate_time_range(&coupon.start_time, &coupon.end_time)?;
+    validate_coupon_usage(coupon.used, coupon.total)
+}
+
+/// 只校验同时修改的字段，与原数据的组合在处理函数中校验
+fn validate_update_coupon(coupon: &UpdateCouponReq) -> Result<(), ValidationError> {
+    if let (Some(start_time), Some(end_time)) = (&coupon.start_time, &coupon.end_time) {
+        validate_time_range(start_time, end_time)?;
+    }
+    if let (Some(used), Some(total)) = (coupon.used, coupon.total) {
+        validate_coupon_usage(used, total)?;
+    }
+    Ok(())
 }
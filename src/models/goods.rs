@@ -1,7 +1,14 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::sea_orm_active_enums::{CheckStatus, GoodStatus, StockDisplay},
+    utils::{money::Money, validation::validate_price},
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "title": "商品名称",
     "category_id": 0,
@@ -16,22 +23,35 @@ use utoipa::{IntoParams, ToSchema};
     "min_ori_price": "100.00",
     "is_check": 0,
 }))]
-
 pub struct CreateGoodReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub title: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub category_id: i32,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub cover: String,
     pub desc: String,
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
     pub unit: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub stock: i32,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub min_stock: i32,
-    pub status: i32,
-    pub stock_display: i32,
-    pub min_price: String,
-    pub min_ori_price: String,
+    pub status: GoodStatus,
+    pub stock_display: StockDisplay,
+    #[validate(custom(function = "validate_price"))]
+    pub min_price: Money,
+    #[validate(custom(function = "validate_price"))]
+    pub min_ori_price: Money,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema)]
 #[schema(example  = json!({
     "id": 1,
     "title": "商品名称",
@@ -46,10 +66,9 @@ pub struct CreateGoodReq {
     "min_price": "1.00",
     "min_ori_price": "100.00",
     "is_check": 0,
-    "create_time": "2022-06-17 19:57:32",
-    "update_time": "2022-06-17 19:57:32",
+    "create_time": "2022-06-17T19:57:32Z",
+    "update_time": "2022-06-17T19:57:32Z",
 }))]
-
 pub struct CreateGoodRes {
     pub id: i32,
     pub title: String,
@@ -59,16 +78,16 @@ pub struct CreateGoodRes {
     pub unit: String,
     pub stock: i32,
     pub min_stock: i32,
-    pub status: i32,
-    pub stock_display: i32,
-    pub min_price: String,
-    pub min_ori_price: String,
-    pub is_check: i32,
-    pub create_time: String,
-    pub update_time: String,
+    pub status: GoodStatus,
+    pub stock_display: StockDisplay,
+    pub min_price: Money,
+    pub min_ori_price: Money,
+    pub is_check: CheckStatus,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "title": "商品名称",
     "category_id": 0,
@@ -82,76 +101,88 @@ pub struct CreateGoodRes {
     "min_price": "1.00",
     "min_ori_price": "100.00",
 }))]
-
 pub struct UpdateGoodReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub title: Option<String>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub category_id: Option<i32>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub cover: Option<String>,
     pub desc: Option<String>,
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
     pub unit: Option<String>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub stock: Option<i32>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub min_stock: Option<i32>,
-    pub status: Option<i32>,
-    pub stock_display: Option<i32>,
-    pub min_price: Option<String>,
-    pub min_ori_price: Option<String>,
+    pub status: Option<GoodStatus>,
+    pub stock_display: Option<StockDisplay>,
+    #[validate(custom(function = "validate_price"))]
+    pub min_price: Option<Money>,
+    #[validate(custom(function = "validate_price"))]
+    pub min_ori_price: Option<Money>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [0, 1, 2],
     "status": 0,
 }))]
-
 pub struct BatchUpdateStatusReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
-    pub status: i32,
+    pub status: GoodStatus,
 }
 
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
-
 pub struct GetGoodListReq {
-    #[param(style = Form, allow_reserved, example = 1)]
-    pub page: u64,
-    #[param(style = Form, allow_reserved, example = 10)]
-    pub size: u64,
+    #[allow(dead_code)]
     pub tab: Option<String>,
     pub title: Option<String>,
     pub category_id: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [0, 1, 2],
 }))]
-
 pub struct BatchSoftDeleteGoodReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [0, 1, 2],
 }))]
-
 pub struct BatchRestoreGoodReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [0, 1, 2],
 }))]
-
 pub struct BatchDeleteGoodReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "is_check": 1,
 }))]
-
 pub struct UpdateGoodIsCheckReq {
-    pub is_check: i32,
+    pub is_check: CheckStatus,
 }
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_ERROR: &str = "error";
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "status": "ok",
+}))]
+pub struct LivenessRes {
+    pub status: String,
+}
+
+/// 单项检查的结果
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "status": "ok",
+    "latency_ms": 1.25,
+    "error": null,
+}))]
+pub struct CheckResult {
+    /// `ok` 或 `error`
+    pub status: String,
+    /// 检查耗时（毫秒）
+    pub latency_ms: f64,
+    /// 检查失败的原因
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessChecks {
+    /// 数据库连接
+    pub database: CheckResult,
+    /// 是否还有未执行的迁移
+    pub migrations: CheckResult,
+    /// 上传目录是否可写
+    pub upload_dir: CheckResult,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "status": "error",
+    "checks": {
+        "database": { "status": "ok", "latency_ms": 0.82, "error": null },
+        "migrations": { "status": "error", "latency_ms": 2.41, "error": "有 1 个迁移未执行：admin_email" },
+        "upload_dir": { "status": "ok", "latency_ms": 0.17, "error": null },
+    },
+}))]
+pub struct ReadinessRes {
+    /// 所有检查均通过时为 `ok`，否则为 `error`
+    pub status: String,
+    pub checks: ReadinessChecks,
+}
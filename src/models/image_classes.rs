@@ -1,24 +1,31 @@
 use serde::Deserialize;
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "测试图库名称",
     "order": 50,
 }))]
-
 pub struct CreateImageClassReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: i32,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "测试图库名称",
     "order": 50,
 }))]
-
 pub struct UpdateImageClassReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: Option<i32>,
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct UploadImageReq {
@@ -9,19 +10,22 @@ pub struct UploadImageReq {
     pub image_class_id: i32,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [1, 2, 3],
 }))]
 pub struct DeleteImageAllReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "测试角色名称",
 }))]
-
 pub struct UpdateImageReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
 }
@@ -1,22 +1,61 @@
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
 pub mod admins;
+pub mod audit_logs;
 pub mod coupons;
 pub mod goods;
+pub mod health;
 pub mod image_classes;
 pub mod images;
 pub mod notices;
+pub mod password_resets;
 pub mod roles;
 pub mod rules;
+pub mod sessions;
 pub mod skus;
 pub mod user_levels;
 pub mod users;
 
-#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+/// 每页条目数的上限
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// 页码的上限，使偏移量不超出数据库整数的范围，更靠后的数据应改用游标查询
+pub const MAX_PAGE: u64 = 1_000_000;
+
+/// 列表接口共用的分页与排序参数
+///
+/// 按页码分页时，页码越大查询越慢。数据较多时可改用游标：按 `id` 排序且当前页已满时，
+/// 响应中会返回 `next_cursor`，下一次请求带上 `cursor` 即从该 ID 之后继续查询，此时忽略 `page`。
+/// 配合 `count=false` 可以同时省去计数查询
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
 pub struct Pagination {
-    #[param(style = Form, allow_reserved, example = 1)]
+    /// 页码，从 1 开始，默认为 1，最大为 1000000
+    #[serde(default = "default_page")]
+    #[validate(range(min = 1, max = MAX_PAGE, message = "必须在 1 到 1000000 之间"))]
+    #[param(style = Form, allow_reserved, example = 1, minimum = 1, maximum = 1000000)]
     pub page: u64,
-    #[param(style = Form, allow_reserved, example = 10)]
+    /// 每页条目数，最多 100
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "必须在 1 到 100 之间"))]
+    #[param(style = Form, allow_reserved, example = 10, minimum = 1, maximum = 100)]
     pub size: u64,
+    /// 排序字段，以逗号分隔，字段前加 `-` 表示倒序，如 `-order,id`。可用的字段见各接口
+    #[param(style = Form, allow_reserved, example = "-id")]
+    pub sort: Option<String>,
+    /// 游标，即上一页返回的 `next_cursor`，只能在按 `id` 排序时使用
+    #[param(style = Form, allow_reserved)]
+    pub cursor: Option<i32>,
+    /// 是否返回总条数，默认为 `true`
+    #[serde(default = "default_count")]
+    #[param(style = Form, allow_reserved, example = true)]
+    pub count: bool,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_count() -> bool {
+    true
 }
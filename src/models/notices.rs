@@ -1,13 +1,17 @@
 use serde::Deserialize;
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "title": "测试标题",
     "content": "测试内容",
 }))]
-
 pub struct CreateNotice {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub title: String,
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_length = 1)]
     pub content: String,
 }
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(example  = json!({
+    "username": "admin",
+}))]
+pub struct PasswordResetRequestReq {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example  = json!({
+    "token": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    "new_password": "xxxxxxxxxx",
+}))]
+pub struct PasswordResetConfirmReq {
+    pub token: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
+    pub new_password: String,
+}
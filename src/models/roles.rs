@@ -1,46 +1,145 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr, LoaderTrait};
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::{role_rules, roles::Model, sea_orm_active_enums::Status},
+    models::rules::Rule,
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "测试角色名称",
     "desc": "测试角色描述",
     "status": 1,
 }))]
-
 pub struct CreateRoleReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub desc: String,
-    pub status: i32,
+    pub status: Status,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "测试角色名称",
     "desc": "测试角色描述",
     "status": 1,
 }))]
-
 pub struct UpdateRoleReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub desc: Option<String>,
-    pub status: Option<i32>,
+    pub status: Option<Status>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateRoleStatusReq {
-    pub status: i32,
+    pub status: Status,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "rule_ids": [ 5, 10, 174, 175, 176 ],
 }))]
-
 pub struct UpdateRoleRuleIdsReq {
     pub rule_ids: Vec<i32>,
 }
+
+/// 角色及其拥有的规则 ID
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 38,
+    "name": "运营",
+    "status": 1,
+    "desc": "运营人员",
+    "create_time": "2022-06-17T19:57:32Z",
+    "update_time": "2022-06-17T19:57:32Z",
+    "rule_ids": [ 5, 10, 174, 175, 176 ],
+}))]
+pub struct RoleInfo {
+    pub id: i32,
+    pub name: String,
+    pub status: Status,
+    pub desc: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+    pub rule_ids: Vec<i32>,
+}
+
+impl RoleInfo {
+    /// 转换角色列表，并批量查询各自的规则 ID
+    pub async fn load_many(
+        db: &DatabaseConnection,
+        roles: Vec<Model>,
+    ) -> Result<Vec<RoleInfo>, DbErr> {
+        let role_rules = roles.load_many(role_rules::Entity, db).await?;
+        Ok(roles
+            .into_iter()
+            .zip(role_rules)
+            .map(|(role, role_rules)| {
+                let mut rule_ids: Vec<i32> = role_rules
+                    .into_iter()
+                    .map(|role_rule| role_rule.rule_id)
+                    .collect();
+                rule_ids.sort_unstable();
+                RoleInfo {
+                    id: role.id,
+                    name: role.name,
+                    status: role.status,
+                    desc: role.desc,
+                    create_time: role.create_time,
+                    update_time: role.update_time,
+                    rule_ids,
+                }
+            })
+            .collect())
+    }
+}
+
+/// 角色拥有的规则，`rules` 为按上下级组织的规则树
+///
+/// 上级规则未授予该角色时，下级规则直接作为根节点
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "rule_ids": [ 5, 10 ],
+    "rules": [],
+}))]
+pub struct RoleRulesRes {
+    pub rule_ids: Vec<i32>,
+    pub rules: Vec<Rule>,
+}
+
+/// 嵌套在管理员信息中的角色摘要
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 38,
+    "name": "运营",
+    "status": 1,
+}))]
+pub struct RoleSummary {
+    pub id: i32,
+    pub name: String,
+    pub status: Status,
+}
+
+impl From<Model> for RoleSummary {
+    fn from(role: Model) -> Self {
+        RoleSummary {
+            id: role.id,
+            name: role.name,
+            status: role.status,
+        }
+    }
+}
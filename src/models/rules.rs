@@ -1,25 +1,32 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema, Clone, Serialize)]
+use crate::{
+    entities::sea_orm_active_enums::{RuleMenu, Status},
+    utils::validation::validate_http_method,
+};
 
+#[derive(Debug, Deserialize, ToSchema, Clone, Serialize)]
 pub struct Rule {
     pub id: i32,
     pub name: String,
     pub rule_id: i32,
-    pub status: i32,
-    pub create_time: String,
-    pub update_time: String,
+    pub status: Status,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
     pub front_path: String,
     pub condition: String,
-    pub menu: i32,
+    pub menu: RuleMenu,
     pub order: i32,
     pub icon: String,
     pub method: String,
+    #[schema(no_recursion)]
     pub child: Vec<Rule>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "后台面板",
     "rule_id": 0,
@@ -31,20 +38,33 @@ pub struct Rule {
     "icon": "help",
     "method": "GET",
 }))]
-
 pub struct CreateRuleReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub rule_id: i32,
-    pub status: i32,
+    pub status: Status,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub front_path: String,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub condition: String,
-    pub menu: i32,
+    pub menu: RuleMenu,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: i32,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub icon: String,
+    #[validate(custom(function = "validate_http_method"))]
+    #[schema(pattern = "^(GET|POST|PUT|PATCH|DELETE)?$")]
     pub method: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "后台面板",
     "rule_id": 0,
@@ -56,24 +76,36 @@ pub struct CreateRuleReq {
     "icon": "help",
     "method": "GET",
 }))]
-
 pub struct UpdateRuleReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub rule_id: Option<i32>,
-    pub status: Option<i32>,
+    pub status: Option<Status>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub front_path: Option<String>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub condition: Option<String>,
-    pub menu: Option<i32>,
+    pub menu: Option<RuleMenu>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: Option<i32>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub icon: Option<String>,
+    #[validate(custom(function = "validate_http_method"))]
+    #[schema(pattern = "^(GET|POST|PUT|PATCH|DELETE)?$")]
     pub method: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateRuleStatusReq {
-    pub status: i32,
+    pub status: Status,
 }
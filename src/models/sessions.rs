@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entities::sessions::Model;
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(example  = json!({
+    "refresh_token": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+}))]
+pub struct RefreshTokenReq {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 1,
+    "family_id": "0b5c7a4e-6f0e-4d5b-9a55-2f1f3c8d9e21",
+    "expires_at": 1718625452,
+    "user_agent": "Mozilla/5.0",
+    "ip": "127.0.0.1",
+    "create_time": "2022-06-17T19:57:32Z",
+    "update_time": "2022-06-17T19:57:32Z",
+}))]
+pub struct SessionInfo {
+    pub id: i32,
+    pub family_id: String,
+    pub expires_at: i64,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+}
+
+impl From<Model> for SessionInfo {
+    fn from(session: Model) -> Self {
+        SessionInfo {
+            id: session.id,
+            family_id: session.family_id,
+            expires_at: session.expires_at,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            create_time: session.create_time,
+            update_time: session.update_time,
+        }
+    }
+}
@@ -1,50 +1,63 @@
 use serde::Deserialize;
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::entities::sea_orm_active_enums::Status;
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "规格名称",
     "status": 0,
     "order": 50,
     "default": "规格1,规格2",
 }))]
-
 pub struct CreateSkuReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
-    pub status: i32,
+    pub status: Status,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: i32,
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_length = 1)]
     pub default: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "规格名称",
     "status": 0,
     "order": 50,
     "default": "规格1,规格2",
 }))]
-
 pub struct UpdateSkuReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
-    pub status: Option<i32>,
+    pub status: Option<Status>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub order: Option<i32>,
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_length = 1)]
     pub default: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateSkuStatusReq {
-    pub status: i32,
+    pub status: Status,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "ids": [1, 2],
 }))]
-
 pub struct DeleteSkuAllReq {
+    #[validate(length(min = 1, message = "不能为空"))]
+    #[schema(min_items = 1)]
     pub ids: Vec<i32>,
 }
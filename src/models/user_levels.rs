@@ -1,49 +1,96 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::{sea_orm_active_enums::Status, user_levels::Model},
+    utils::{money::Money, validation::validate_price},
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "会员等级名称",
     "status": 0,
     "level": 100,
     "discount": 10,
-    "max_price": 1000,
+    "max_price": "1000.00",
     "max_time": 500,
 }))]
-
 pub struct CreateUserLevelReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: String,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub level: i32,
-    pub status: i32,
+    pub status: Status,
+    #[validate(range(min = 0, max = 100, message = "必须在 0 到 100 之间"))]
+    #[schema(minimum = 0, maximum = 100)]
     pub discount: i32,
-    pub max_price: i32,
+    #[validate(custom(function = "validate_price"))]
+    pub max_price: Money,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub max_time: i32,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "name": "会员等级名称",
     "status": 0,
     "level": 100,
     "discount": 10,
-    "max_price": 1000,
+    "max_price": "1000.00",
     "max_time": 500,
 }))]
-
 pub struct UpdateUserLevelReq {
+    #[validate(length(min = 1, max = 255, message = "长度必须为 1 到 255 个字符"))]
+    #[schema(min_length = 1, max_length = 255)]
     pub name: Option<String>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub level: Option<i32>,
-    pub status: Option<i32>,
+    pub status: Option<Status>,
+    #[validate(range(min = 0, max = 100, message = "必须在 0 到 100 之间"))]
+    #[schema(minimum = 0, maximum = 100)]
     pub discount: Option<i32>,
-    pub max_price: Option<i32>,
+    #[validate(custom(function = "validate_price"))]
+    pub max_price: Option<Money>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub max_time: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateUserLevelStatusReq {
-    pub status: i32,
+    pub status: Status,
+}
+
+/// 嵌套在用户信息中的会员等级摘要
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 1,
+    "name": "普通会员",
+    "level": 100,
+    "discount": 10,
+}))]
+pub struct UserLevelSummary {
+    pub id: i32,
+    pub name: String,
+    pub level: i32,
+    pub discount: i32,
+}
+
+impl From<Model> for UserLevelSummary {
+    fn from(user_level: Model) -> Self {
+        UserLevelSummary {
+            id: user_level.id,
+            name: user_level.name,
+            level: user_level.level,
+            discount: user_level.discount,
+        }
+    }
 }
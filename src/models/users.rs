@@ -1,7 +1,15 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr, LoaderTrait};
+use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::{
+    entities::{sea_orm_active_enums::Status, user_levels, users::Model},
+    models::user_levels::UserLevelSummary,
+};
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "username": "测试",
     "password": "test_test_test",
@@ -12,19 +20,32 @@ use utoipa::{IntoParams, ToSchema};
     "email": null,
     "avatar": null,
 }))]
-
 pub struct CreateUserReq {
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
     pub username: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
     pub password: String,
-    pub status: i32,
+    pub status: Status,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub user_level_id: i32,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub nickname: Option<String>,
+    #[validate(length(max = 20, message = "长度不能超过 20 个字符"))]
+    #[schema(max_length = 20)]
     pub phone: Option<String>,
+    #[validate(email(message = "邮箱格式不正确"))]
+    #[schema(format = Email)]
     pub email: Option<String>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub avatar: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "username": "测试",
     "password": "test_test_test",
@@ -35,33 +56,186 @@ pub struct CreateUserReq {
     "email": null,
     "avatar": null,
 }))]
-
 pub struct UpdateUserReq {
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
     pub username: Option<String>,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
     pub password: Option<String>,
-    pub status: Option<i32>,
+    pub status: Option<Status>,
+    #[validate(range(min = 0, message = "不能小于 0"))]
+    #[schema(minimum = 0)]
     pub user_level_id: Option<i32>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub nickname: Option<String>,
+    #[validate(length(max = 20, message = "长度不能超过 20 个字符"))]
+    #[schema(max_length = 20)]
     pub phone: Option<String>,
+    #[validate(email(message = "邮箱格式不正确"))]
+    #[schema(format = Email)]
     pub email: Option<String>,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
     pub avatar: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[schema(example  = json!({
     "status": 1,
 }))]
-
 pub struct UpdateUserStatusReq {
-    pub status: i32,
+    pub status: Status,
 }
 
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct GetUserListReq {
-    #[param(style = Form, allow_reserved, example = 1)]
-    pub page: u64,
-    #[param(style = Form, allow_reserved, example = 10)]
-    pub size: u64,
     pub keyword: Option<String>,
     pub user_level_id: Option<i32>,
 }
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example  = json!({
+    "username": "测试",
+    "password": "test_test_test",
+    "nickname": null,
+    "phone": null,
+    "email": null,
+}))]
+pub struct RegisterUserReq {
+    #[validate(length(min = 1, max = 32, message = "长度必须为 1 到 32 个字符"))]
+    #[schema(min_length = 1, max_length = 32)]
+    pub username: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
+    pub password: String,
+    #[validate(length(max = 255, message = "长度不能超过 255 个字符"))]
+    #[schema(max_length = 255)]
+    pub nickname: Option<String>,
+    #[validate(length(max = 20, message = "长度不能超过 20 个字符"))]
+    #[schema(max_length = 20)]
+    pub phone: Option<String>,
+    #[validate(email(message = "邮箱格式不正确"))]
+    #[schema(format = Email)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(example  = json!({
+    "username": "测试",
+    "password": "test_test_test",
+}))]
+pub struct UserLoginReq {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "token": "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...",
+    "token_type": "Bearer",
+    "expires_in": 7200,
+    "refresh_token": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    "refresh_expires_in": 604800,
+}))]
+pub struct UserLoginRes {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[schema(example  = json!({
+    "old_password": "test_test_test",
+    "new_password": "test_test_test_new",
+}))]
+pub struct UpdateUserPasswordReq {
+    pub old_password: String,
+    #[validate(length(min = 6, max = 64, message = "长度必须为 6 到 64 个字符"))]
+    #[schema(min_length = 6, max_length = 64)]
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example  = json!({
+    "id": 1,
+    "username": "测试",
+    "status": 1,
+    "user_level_id": 1,
+    "nickname": null,
+    "phone": null,
+    "email": null,
+    "avatar": null,
+    "create_time": "2022-06-17T19:57:32Z",
+    "update_time": "2022-06-17T19:57:32Z",
+    "user_level": {
+        "id": 1,
+        "name": "普通会员",
+        "level": 100,
+        "discount": 10,
+    },
+}))]
+pub struct UserInfo {
+    pub id: i32,
+    pub username: String,
+    pub status: Status,
+    pub user_level_id: Option<i32>,
+    pub nickname: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub avatar: Option<String>,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+    /// 用户所属会员等级，等级不存在时为空
+    pub user_level: Option<UserLevelSummary>,
+}
+
+impl From<Model> for UserInfo {
+    fn from(user: Model) -> Self {
+        UserInfo {
+            id: user.id,
+            username: user.username,
+            status: user.status,
+            user_level_id: user.user_level_id,
+            nickname: user.nickname,
+            phone: user.phone,
+            email: user.email,
+            avatar: user.avatar,
+            create_time: user.create_time,
+            update_time: user.update_time,
+            user_level: None,
+        }
+    }
+}
+
+impl UserInfo {
+    /// 转换用户及其所属会员等级
+    pub fn with_user_level(user: Model, user_level: Option<user_levels::Model>) -> Self {
+        UserInfo {
+            user_level: user_level.map(UserLevelSummary::from),
+            ..UserInfo::from(user)
+        }
+    }
+
+    /// 转换用户列表，并批量查询各自的会员等级
+    pub async fn load_many(
+        db: &DatabaseConnection,
+        users: Vec<Model>,
+    ) -> Result<Vec<UserInfo>, DbErr> {
+        let user_levels = users.load_one(user_levels::Entity, db).await?;
+        Ok(users
+            .into_iter()
+            .zip(user_levels)
+            .map(|(user, user_level)| UserInfo::with_user_level(user, user_level))
+            .collect())
+    }
+
+    /// 转换单个用户，并查询其会员等级
+    pub async fn load(db: &DatabaseConnection, user: Model) -> Result<UserInfo, DbErr> {
+        let mut user_infos = UserInfo::load_many(db, vec![user]).await?;
+        Ok(user_infos.remove(0))
+    }
+}
@@ -1,11 +1,23 @@
 use actix_web::{web, Scope};
 
-use crate::handlers::admins::{create_admin, delete_admin, update_admin, update_admin_status};
+use crate::handlers::admins::{
+    create_admin, delete_admin, get_admin_detail,
+    sessions::{get_admin_sessions, revoke_admin_session, revoke_admin_sessions},
+    unlock_admin, update_admin, update_admin_status,
+};
 
 pub fn build_admin_manager_router() -> Scope {
     web::scope("/manager")
         .route("", web::post().to(create_admin))
+        .route("/{id}", web::get().to(get_admin_detail))
         .route("/{id}", web::put().to(update_admin))
         .route("/{id}", web::delete().to(delete_admin))
         .route("/{id}/update_status", web::patch().to(update_admin_status))
+        .route("/{id}/unlock", web::patch().to(unlock_admin))
+        .route("/{id}/sessions", web::get().to(get_admin_sessions))
+        .route("/{id}/sessions", web::delete().to(revoke_admin_sessions))
+        .route(
+            "/{id}/sessions/{session_id}",
+            web::delete().to(revoke_admin_session),
+        )
 }
@@ -1,8 +1,27 @@
 use actix_web::{web, Scope};
 use manager::build_admin_manager_router;
 
+use crate::handlers::admins::auth::{
+    confirm_password_reset, get_current_admin, get_current_admin_menus, login, logout, refresh,
+    request_password_reset,
+};
+
 mod manager;
 
 pub fn build_admin_router() -> Scope {
-    web::scope("/admins").service(build_admin_manager_router())
+    web::scope("/admins")
+        .route("/login", web::post().to(login))
+        .route("/refresh", web::post().to(refresh))
+        .route("/logout", web::post().to(logout))
+        .route(
+            "/password_reset/request",
+            web::post().to(request_password_reset),
+        )
+        .route(
+            "/password_reset/confirm",
+            web::post().to(confirm_password_reset),
+        )
+        .route("/me", web::get().to(get_current_admin))
+        .route("/menus", web::get().to(get_current_admin_menus))
+        .service(build_admin_manager_router())
 }
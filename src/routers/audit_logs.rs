@@ -0,0 +1,7 @@
+use actix_web::{web, Scope};
+
+use crate::handlers::audit_logs::get_audit_log_list;
+
+pub fn build_audit_log_router() -> Scope {
+    web::scope("/audit_logs").route("", web::get().to(get_audit_log_list))
+}
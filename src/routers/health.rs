@@ -0,0 +1,9 @@
+use actix_web::web;
+
+use crate::handlers::health::{healthz, readyz};
+
+/// 健康检查接口位于根路径下，不需要登录
+pub fn build_health_router(cfg: &mut web::ServiceConfig) {
+    cfg.route("/healthz", web::get().to(healthz))
+        .route("/readyz", web::get().to(readyz));
+}
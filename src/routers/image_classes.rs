@@ -1,8 +1,8 @@
 use actix_web::{web, Scope};
 
 use crate::handlers::image_classes::{
-    create_image_class, delete_image_class, get_image_class_list, get_image_list_by_image_class_id,
-    update_image_class,
+    create_image_class, delete_image_class, get_image_class, get_image_class_list,
+    get_image_list_by_image_class_id, update_image_class,
 };
 
 pub fn build_image_class_router() -> Scope {
@@ -13,6 +13,7 @@ pub fn build_image_class_router() -> Scope {
             web::get().to(get_image_list_by_image_class_id),
         )
         .route("", web::post().to(create_image_class))
+        .route("/{id}", web::get().to(get_image_class))
         .route("/{id}", web::put().to(update_image_class))
         .route("/{id}", web::delete().to(delete_image_class))
 }
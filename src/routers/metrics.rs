@@ -0,0 +1,8 @@
+use actix_web::web;
+
+use crate::handlers::metrics::get_metrics;
+
+/// 指标接口位于根路径下，不需要登录
+pub fn build_metrics_router(cfg: &mut web::ServiceConfig) {
+    cfg.route("/metrics", web::get().to(get_metrics));
+}
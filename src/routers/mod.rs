@@ -1,11 +1,15 @@
 pub mod admins;
+pub mod audit_logs;
 pub mod coupons;
 pub mod goods;
+pub mod health;
 pub mod image_classes;
 pub mod images;
+pub mod metrics;
 pub mod notices;
 pub mod roles;
 pub mod rules;
+pub mod shop;
 pub mod skus;
 pub mod user_levels;
 pub mod users;
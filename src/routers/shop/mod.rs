@@ -0,0 +1,9 @@
+use actix_web::{web, Scope};
+use users::build_shop_user_router;
+
+mod users;
+
+/// 商城前台接口，使用独立的用户令牌，不经过后台权限中间件
+pub fn build_shop_router() -> Scope {
+    web::scope("/api/shop").service(build_shop_user_router())
+}
@@ -0,0 +1,23 @@
+use actix_web::{web, Scope};
+
+use crate::handlers::shop::users::{
+    confirm_password_reset, login, logout, refresh, register, request_password_reset,
+    update_password,
+};
+
+pub fn build_shop_user_router() -> Scope {
+    web::scope("/users")
+        .route("/register", web::post().to(register))
+        .route("/login", web::post().to(login))
+        .route("/refresh", web::post().to(refresh))
+        .route("/logout", web::post().to(logout))
+        .route("/password", web::patch().to(update_password))
+        .route(
+            "/password_reset/request",
+            web::post().to(request_password_reset),
+        )
+        .route(
+            "/password_reset/confirm",
+            web::post().to(confirm_password_reset),
+        )
+}
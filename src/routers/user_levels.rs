@@ -1,7 +1,7 @@
 use actix_web::{web, Scope};
 
 use crate::handlers::user_levels::{
-    create_user_level, delete_user_level, get_user_level_list, update_user_level,
+    create_user_level, delete_user_level, get_user_level, get_user_level_list, update_user_level,
     update_user_level_status,
 };
 
@@ -9,6 +9,7 @@ pub fn build_user_level_router() -> Scope {
     web::scope("/user_levels")
         .route("", web::get().to(get_user_level_list))
         .route("", web::post().to(create_user_level))
+        .route("/{id}", web::get().to(get_user_level))
         .route("/{id}", web::put().to(update_user_level))
         .route(
             "/{id}/update_status",
@@ -0,0 +1,152 @@
+//! 通用的增删改查
+//!
+//! 实体实现 [`CrudResource`] 后，由这里的函数完成查找、创建、修改、删除与分页查询，
+//! 处理函数只负责解析请求与组装响应。各资源特有的校验与关联处理通过钩子实现
+
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Iterable,
+    PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter,
+};
+
+use crate::{
+    entities::sea_orm_active_enums::Status,
+    models::Pagination,
+    services::pagination::{paginate, Page, SortFields},
+    utils::error::ApiError,
+};
+
+/// 可通用增删改查的实体，主键为整数 `id`
+#[async_trait]
+pub trait CrudResource:
+    EntityTrait<
+    PrimaryKey: PrimaryKeyTrait<ValueType = i32>,
+    Model: IntoActiveModel<<Self as EntityTrait>::ActiveModel> + Sync,
+    ActiveModel: Send,
+>
+{
+    /// 创建时的请求体
+    type CreateReq: Sync;
+    /// 修改时的请求体
+    type UpdateReq: Sync;
+
+    /// 数据不存在时的提示，如 `公告不存在`
+    const NOT_FOUND: &'static str;
+
+    /// 列表允许排序的字段
+    const SORT_FIELDS: SortFields<Self::Column>;
+
+    /// 由请求体构造待插入的数据
+    fn new_model(data: &Self::CreateReq) -> Self::ActiveModel;
+
+    /// 将请求体中的字段写入原数据，未提供的字段保持不变
+    fn apply_update(model: &mut Self::ActiveModel, data: &Self::UpdateReq);
+
+    /// 创建前的校验，如检查关联的数据是否存在
+    async fn before_create(
+        _db: &DatabaseConnection,
+        _data: &Self::CreateReq,
+    ) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    /// 修改前的校验，`model` 为修改前的数据，可与请求体组合后校验
+    async fn before_update(
+        _db: &DatabaseConnection,
+        _model: &Self::Model,
+        _data: &Self::UpdateReq,
+    ) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    /// 删除前的校验或关联处理，批量删除时对每个 ID 分别调用
+    async fn before_delete(_db: &DatabaseConnection, _id: i32) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+/// 带启用状态的实体
+pub trait StatusResource: CrudResource {
+    /// 启用状态所在的列
+    const STATUS_COLUMN: Self::Column;
+}
+
+/// 主键列，主键的值为单个整数时只有一列
+pub fn id_column<E>() -> E::Column
+where
+    E: EntityTrait<PrimaryKey: PrimaryKeyTrait<ValueType = i32>>,
+{
+    E::PrimaryKey::iter()
+        .next()
+        .expect("实体没有主键")
+        .into_column()
+}
+
+/// 按 ID 查找，不存在时返回 404
+pub async fn find<R: CrudResource>(db: &DatabaseConnection, id: i32) -> Result<R::Model, ApiError> {
+    R::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::not_found(R::NOT_FOUND))
+}
+
+pub async fn create<R: CrudResource>(
+    db: &DatabaseConnection,
+    data: &R::CreateReq,
+) -> Result<R::Model, ApiError> {
+    R::before_create(db, data).await?;
+    Ok(R::new_model(data).insert(db).await?)
+}
+
+/// 修改并返回修改后的数据
+pub async fn update<R: CrudResource>(
+    db: &DatabaseConnection,
+    id: i32,
+    data: &R::UpdateReq,
+) -> Result<R::Model, ApiError> {
+    let model = find::<R>(db, id).await?;
+    R::before_update(db, &model, data).await?;
+
+    let mut active_model = model.into_active_model();
+    R::apply_update(&mut active_model, data);
+    Ok(active_model.update(db).await?)
+}
+
+pub async fn update_status<R: StatusResource>(
+    db: &DatabaseConnection,
+    id: i32,
+    status: Status,
+) -> Result<R::Model, ApiError> {
+    let mut active_model = find::<R>(db, id).await?.into_active_model();
+    active_model.set(R::STATUS_COLUMN, status.into());
+    Ok(active_model.update(db).await?)
+}
+
+/// 删除，数据不存在时同样视为成功
+pub async fn delete<R: CrudResource>(db: &DatabaseConnection, id: i32) -> Result<(), ApiError> {
+    R::before_delete(db, id).await?;
+    R::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// 批量删除，返回实际删除的条数
+pub async fn delete_many<R: CrudResource>(
+    db: &DatabaseConnection,
+    ids: &[i32],
+) -> Result<u64, ApiError> {
+    for &id in ids {
+        R::before_delete(db, id).await?;
+    }
+    let result = R::delete_many()
+        .filter(id_column::<R>().is_in(ids.iter().copied()))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn list<R: CrudResource>(
+    db: &DatabaseConnection,
+    pagination: &Pagination,
+) -> Result<Page<R::Model>, ApiError> {
+    paginate(db, R::find(), pagination, &R::SORT_FIELDS).await
+}
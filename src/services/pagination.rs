@@ -0,0 +1,135 @@
+//! 列表的分页与排序，参数见 [`Pagination`]
+
+use sea_orm::{
+    sea_query::ValueType, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, Order,
+    PaginatorTrait, PrimaryKeyTrait, QueryFilter, QueryOrder, QuerySelect, Select,
+};
+
+use crate::{
+    models::Pagination,
+    services::crud::id_column,
+    utils::{
+        error::ApiError,
+        response::{response_page_t, ResponseListT},
+    },
+};
+
+/// 列表允许排序的字段
+pub struct SortFields<C: 'static> {
+    /// 查询参数中的字段名与对应的列，`id` 总是可用，无需列出
+    pub columns: &'static [(&'static str, C)],
+    /// 未指定 `sort` 时的排序，格式与查询参数相同，如 `-id`
+    pub default: &'static str,
+}
+
+/// 一页数据，`total` 与 `next_cursor` 的含义同 [`ResponseListT`]
+pub struct Page<T> {
+    pub list: Vec<T>,
+    pub total: Option<u64>,
+    pub next_cursor: Option<i32>,
+}
+
+impl<T> From<Page<T>> for ResponseListT<T> {
+    fn from(page: Page<T>) -> Self {
+        response_page_t(page.list, page.total, page.next_cursor)
+    }
+}
+
+struct SortKey<C> {
+    name: &'static str,
+    column: C,
+    desc: bool,
+}
+
+/// 解析排序字段，未指定 `id` 时最后按 `id` 正序排列，使每次分页的顺序一致
+fn parse_sort<C: ColumnTrait>(
+    sort: &str,
+    fields: &SortFields<C>,
+    id_column: C,
+) -> Result<Vec<SortKey<C>>, ApiError> {
+    let mut keys = Vec::new();
+    for field in sort
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+    {
+        let (name, desc) = match field.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (field, false),
+        };
+        let (name, column) = if name == "id" {
+            ("id", id_column)
+        } else {
+            fields
+                .columns
+                .iter()
+                .find(|(column_name, _)| *column_name == name)
+                .copied()
+                .ok_or_else(|| ApiError::invalid_field("sort", format!("不支持按 {name} 排序")))?
+        };
+        keys.push(SortKey { name, column, desc });
+    }
+    if !keys.iter().any(|key| key.name == "id") {
+        keys.push(SortKey {
+            name: "id",
+            column: id_column,
+            desc: false,
+        });
+    }
+    Ok(keys)
+}
+
+/// 按查询参数排序并分页
+///
+/// 只按 `id` 排序时支持游标：指定 `cursor` 则从该 ID 之后查询；当前页已满时返回下一页的游标
+pub async fn paginate<E>(
+    db: &DatabaseConnection,
+    select: Select<E>,
+    pagination: &Pagination,
+    sort_fields: &SortFields<E::Column>,
+) -> Result<Page<E::Model>, ApiError>
+where
+    E: EntityTrait<PrimaryKey: PrimaryKeyTrait<ValueType = i32>>,
+    E::Model: Sync,
+{
+    let id_column = id_column::<E>();
+    let sort = pagination.sort.as_deref().unwrap_or(sort_fields.default);
+    let keys = parse_sort(sort, sort_fields, id_column)?;
+    let by_id = keys.len() == 1;
+    if pagination.cursor.is_some() && !by_id {
+        return Err(ApiError::invalid_field(
+            "cursor",
+            "只能在按 id 排序时使用游标",
+        ));
+    }
+
+    let total = if pagination.count {
+        Some(select.clone().count(db).await?)
+    } else {
+        None
+    };
+
+    let mut select = select;
+    for key in &keys {
+        let order = if key.desc { Order::Desc } else { Order::Asc };
+        select = select.order_by(key.column, order);
+    }
+    select = match pagination.cursor {
+        Some(cursor) if keys[0].desc => select.filter(id_column.lt(cursor)),
+        Some(cursor) => select.filter(id_column.gt(cursor)),
+        None => select.offset((pagination.page - 1).saturating_mul(pagination.size)),
+    };
+    let list = select.limit(pagination.size).all(db).await?;
+
+    let next_cursor = match list.last() {
+        Some(last) if by_id && list.len() as u64 == pagination.size => {
+            <i32 as ValueType>::try_from(last.get(id_column)).ok()
+        }
+        _ => None,
+    };
+    Ok(Page {
+        list,
+        total,
+        next_cursor,
+    })
+}
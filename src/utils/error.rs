@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, fmt};
+
+use actix_web::{
+    http::{header, StatusCode},
+    HttpResponse, ResponseError,
+};
+use sea_orm::{DbErr, SqlErr};
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::utils::response::response_error;
+
+/// 接口错误
+///
+/// 实现了 [`ResponseError`]，处理函数返回 `Result<HttpResponse, ApiError>` 即可。
+/// 错误响应同样使用 [`ResponseT`](crate::utils::response::ResponseT) 格式：
+/// `code` 为 HTTP 状态码，`error` 为固定的错误码，`msg` 为错误说明
+#[derive(Debug)]
+pub enum ApiError {
+    /// 请求参数不合法，400 `VALIDATION_FAILED`
+    Validation(String),
+    /// 请求体字段校验失败，400 `VALIDATION_FAILED`，`data` 为字段名到错误说明列表的映射
+    InvalidFields(BTreeMap<String, Vec<String>>),
+    /// 未登录、令牌无效或会话已失效，401 `UNAUTHORIZED`
+    Unauthorized(String),
+    /// 没有权限或账号被禁用，403 `FORBIDDEN`
+    Forbidden(String),
+    /// 数据不存在，404 `NOT_FOUND`
+    NotFound(String),
+    /// 与已有数据冲突，如用户名已存在、删除仍被引用的数据，409 `CONFLICT`
+    Conflict(String),
+    /// 登录失败次数过多，需等待指定秒数，429 `TOO_MANY_REQUESTS`，并返回 `Retry-After` 响应头
+    RateLimited(i64),
+    /// 数据库错误，500 `DB_ERROR`，详情只写入日志
+    Db(DbErr),
+    /// 其他内部错误，500 `INTERNAL_ERROR`，详情只写入日志
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn validation(msg: impl Into<String>) -> Self {
+        ApiError::Validation(msg.into())
+    }
+
+    /// 单个字段校验失败
+    pub fn invalid_field(field: impl Into<String>, msg: impl Into<String>) -> Self {
+        ApiError::InvalidFields(BTreeMap::from([(field.into(), vec![msg.into()])]))
+    }
+
+    pub fn unauthorized(msg: impl Into<String>) -> Self {
+        ApiError::Unauthorized(msg.into())
+    }
+
+    pub fn forbidden(msg: impl Into<String>) -> Self {
+        ApiError::Forbidden(msg.into())
+    }
+
+    pub fn not_found(msg: impl Into<String>) -> Self {
+        ApiError::NotFound(msg.into())
+    }
+
+    pub fn conflict(msg: impl Into<String>) -> Self {
+        ApiError::Conflict(msg.into())
+    }
+
+    pub fn internal(msg: impl fmt::Display) -> Self {
+        ApiError::Internal(msg.to_string())
+    }
+
+    /// 固定的错误码，供客户端判断错误类型
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ApiError::Validation(_) | ApiError::InvalidFields(_) => "VALIDATION_FAILED",
+            ApiError::Unauthorized(_) => "UNAUTHORIZED",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::RateLimited(_) => "TOO_MANY_REQUESTS",
+            ApiError::Db(_) => "DB_ERROR",
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Validation(msg)
+            | ApiError::Unauthorized(msg)
+            | ApiError::Forbidden(msg)
+            | ApiError::NotFound(msg)
+            | ApiError::Conflict(msg) => f.write_str(msg),
+            ApiError::InvalidFields(_) => f.write_str("请求参数不合法"),
+            ApiError::RateLimited(retry_after) => {
+                write!(f, "登录失败次数过多，请在 {retry_after} 秒后重试")
+            }
+            ApiError::Db(_) | ApiError::Internal(_) => f.write_str("服务器内部错误"),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::Validation(_) | ApiError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Db(_) | ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            ApiError::Db(err) => tracing::error!(error = %err, "database error"),
+            ApiError::Internal(msg) => tracing::error!(error = %msg, "internal error"),
+            _ => {}
+        }
+        let status = self.status_code();
+        let mut res = HttpResponse::build(status);
+        if let ApiError::RateLimited(retry_after) = self {
+            res.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+        let fields = match self {
+            ApiError::InvalidFields(fields) => Some(fields),
+            _ => None,
+        };
+        res.json(response_error(
+            status,
+            self.error_code(),
+            self.to_string(),
+            fields,
+        ))
+    }
+}
+
+/// 违反唯一约束或外键约束时视为数据冲突，其余数据库错误返回 500
+impl From<DbErr> for ApiError {
+    fn from(err: DbErr) -> Self {
+        match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => ApiError::conflict("数据已存在"),
+            Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+                ApiError::conflict("关联的数据不存在或仍被引用")
+            }
+            _ => ApiError::Db(err),
+        }
+    }
+}
+
+/// 展开校验错误，嵌套字段的名称形如 `items[0].name`，整体校验的错误位于 `__all__` 下
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = BTreeMap::new();
+        collect_field_errors(&errors, None, &mut fields);
+        ApiError::InvalidFields(fields)
+    }
+}
+
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: Option<&str>,
+    fields: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        // 与 serde 一致，去掉 `r#type` 这类原始标识符的前缀
+        let field = field.trim_start_matches("r#");
+        let name = match prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                let messages = errors.iter().map(|error| match &error.message {
+                    Some(message) => message.to_string(),
+                    None => error.code.to_string(),
+                });
+                fields.entry(name).or_default().extend(messages);
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_field_errors(errors, Some(&name), fields);
+            }
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    collect_field_errors(errors, Some(&format!("{name}[{index}]")), fields);
+                }
+            }
+        }
+    }
+}
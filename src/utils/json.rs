@@ -7,7 +7,6 @@ pub fn read_json_from_file<T>(file_path: &str) -> Result<T>
 where
     T: DeserializeOwned + Debug,
 {
-    let file = File::open(file_path).unwrap();
-    let result: T = serde_json::from_reader(&file).unwrap();
-    Ok(result)
+    let file = File::open(file_path).map_err(serde_json::Error::io)?;
+    serde_json::from_reader(file)
 }
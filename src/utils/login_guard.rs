@@ -0,0 +1,161 @@
+use actix_web::HttpRequest;
+use chrono::Utc;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter,
+};
+
+use crate::{config::LoginGuardConfig, entities::login_attempts, utils::time};
+
+/// 限制维度：用户名
+pub const SCOPE_USERNAME: &str = "username";
+/// 限制维度：客户端 IP
+pub const SCOPE_IP: &str = "ip";
+
+impl LoginGuardConfig {
+    fn max_failures(&self, scope: &str) -> i32 {
+        if scope == SCOPE_IP {
+            self.max_ip_failures
+        } else {
+            self.max_username_failures
+        }
+    }
+
+    /// 第 `failures` 次失败后需要等待的秒数
+    fn delay_after(&self, scope: &str, failures: i32) -> i64 {
+        if failures >= self.max_failures(scope) {
+            return self.lockout_secs;
+        }
+        let exponent = (failures - 1).clamp(0, 30) as u32;
+        self.backoff_base_secs
+            .saturating_mul(1 << exponent)
+            .min(self.backoff_max_secs)
+    }
+}
+
+/// 用于限流的客户端 IP
+///
+/// 使用 TCP 对端地址而不是 `X-Forwarded-For`，避免攻击者通过伪造请求头绕过 IP 限制
+pub fn client_ip(req: &HttpRequest) -> String {
+    req.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default()
+}
+
+/// 检查用户名与 IP 是否处于等待或锁定中，返回还需等待的秒数
+///
+/// 处于限制中时，登录与申请重置密码接口返回 [`ApiError::RateLimited`](crate::utils::error::ApiError::RateLimited)
+pub async fn check_locked(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    username: &str,
+    ip: &str,
+) -> Result<Option<i64>, DbErr> {
+    let now = Utc::now().timestamp();
+    let locked_until = login_attempts::Entity::find()
+        .filter(login_attempts::Column::SubjectType.eq(subject_type))
+        .filter(
+            sea_orm::Condition::any()
+                .add(
+                    login_attempts::Column::Scope
+                        .eq(SCOPE_USERNAME)
+                        .and(login_attempts::Column::Key.eq(username)),
+                )
+                .add(
+                    login_attempts::Column::Scope
+                        .eq(SCOPE_IP)
+                        .and(login_attempts::Column::Key.eq(ip)),
+                ),
+        )
+        .filter(login_attempts::Column::LockedUntil.gt(now))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|attempt| attempt.locked_until)
+        .max();
+    Ok(locked_until.map(|locked_until| locked_until - now))
+}
+
+async fn record_scope_failure(
+    db: &DatabaseConnection,
+    config: &LoginGuardConfig,
+    subject_type: &str,
+    scope: &str,
+    key: &str,
+) -> Result<(), DbErr> {
+    let now = time::now();
+    let timestamp = now.timestamp();
+
+    let previous = login_attempts::Entity::find()
+        .filter(login_attempts::Column::SubjectType.eq(subject_type))
+        .filter(login_attempts::Column::Scope.eq(scope))
+        .filter(login_attempts::Column::Key.eq(key))
+        .one(db)
+        .await?;
+    let failures = match previous {
+        Some(attempt) if timestamp - attempt.last_failure_at <= config.failure_window_secs => {
+            attempt.failures + 1
+        }
+        _ => 1,
+    };
+
+    let attempt = login_attempts::ActiveModel {
+        subject_type: Set(subject_type.to_string()),
+        scope: Set(scope.to_string()),
+        key: Set(key.to_string()),
+        failures: Set(failures),
+        last_failure_at: Set(timestamp),
+        locked_until: Set(timestamp + config.delay_after(scope, failures)),
+        // Entity::insert 不会调用 before_save
+        create_time: Set(now),
+        update_time: Set(now),
+        ..Default::default()
+    };
+    login_attempts::Entity::insert(attempt)
+        .on_conflict(
+            OnConflict::columns([
+                login_attempts::Column::SubjectType,
+                login_attempts::Column::Scope,
+                login_attempts::Column::Key,
+            ])
+            .update_columns([
+                login_attempts::Column::Failures,
+                login_attempts::Column::LastFailureAt,
+                login_attempts::Column::LockedUntil,
+                login_attempts::Column::UpdateTime,
+            ])
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(())
+}
+
+/// 记录一次登录失败，同时累计用户名与 IP 的失败次数
+pub async fn record_failure(
+    db: &DatabaseConnection,
+    config: &LoginGuardConfig,
+    subject_type: &str,
+    username: &str,
+    ip: &str,
+) -> Result<(), DbErr> {
+    record_scope_failure(db, config, subject_type, SCOPE_USERNAME, username).await?;
+    record_scope_failure(db, config, subject_type, SCOPE_IP, ip).await
+}
+
+/// 清除用户名的失败记录，用于登录成功和管理员解锁
+///
+/// IP 的失败记录只能等待自然过期，避免攻击者用自己的账号登录来重置计数
+pub async fn clear_failures(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    username: &str,
+) -> Result<u64, DbErr> {
+    let result = login_attempts::Entity::delete_many()
+        .filter(login_attempts::Column::SubjectType.eq(subject_type))
+        .filter(login_attempts::Column::Scope.eq(SCOPE_USERNAME))
+        .filter(login_attempts::Column::Key.eq(username))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,198 @@
+use prometheus::{
+    exponential_buckets, Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sea_orm::{
+    metric::Info, sea_query::Expr, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend,
+    DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+};
+
+use crate::{
+    entities::{coupons, goods, sea_orm_active_enums::Status},
+    utils::time,
+};
+
+/// 未匹配到任何路由的请求使用的路由标签，避免按原始路径产生大量时间序列
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// 连接池的总连接数、空闲连接数与最大连接数
+fn pool_stats(db: &DatabaseConnection) -> Option<(i64, i64, i64)> {
+    match db.get_database_backend() {
+        DbBackend::Postgres => {
+            let pool = db.get_postgres_connection_pool();
+            Some((
+                i64::from(pool.size()),
+                pool.num_idle() as i64,
+                i64::from(pool.options().get_max_connections()),
+            ))
+        }
+        #[cfg(feature = "sqlite")]
+        DbBackend::Sqlite => {
+            let pool = db.get_sqlite_connection_pool();
+            Some((
+                i64::from(pool.size()),
+                pool.num_idle() as i64,
+                i64::from(pool.options().get_max_connections()),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Prometheus 指标
+///
+/// 请求、查询与上传相关的指标在发生时记录，连接池与业务指标在每次抓取 `/metrics` 时更新
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// 按请求方式、路由模板与状态码统计的请求数
+    http_requests_total: IntCounterVec,
+    /// 按请求方式与路由模板统计的请求耗时
+    http_request_duration_seconds: HistogramVec,
+    /// 按语句类型与是否失败统计的 SQL 执行耗时
+    db_query_duration_seconds: HistogramVec,
+    /// 按状态（`idle`、`in_use`）统计的连接池连接数
+    db_pool_connections: IntGaugeVec,
+    /// 连接池最大连接数
+    db_pool_max_connections: IntGauge,
+    /// 上传的图片数
+    upload_files_total: IntCounter,
+    /// 上传的图片字节数
+    upload_bytes_total: IntCounter,
+    /// 库存低于预警库存的商品数，不含已删除的商品
+    goods_below_min_stock: IntGauge,
+    /// 已启用且处于有效期内的优惠券数
+    coupons_active: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP 请求数"),
+            &["method", "route", "status"],
+        )?;
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP 请求耗时（秒）"),
+            &["method", "route"],
+        )?;
+        let db_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "SQL 执行耗时（秒）")
+                .buckets(exponential_buckets(0.0005, 2.0, 14)?),
+            &["operation", "failed"],
+        )?;
+        let db_pool_connections =
+            IntGaugeVec::new(Opts::new("db_pool_connections", "连接池连接数"), &["state"])?;
+        let db_pool_max_connections = IntGauge::new("db_pool_max_connections", "连接池最大连接数")?;
+        let upload_files_total = IntCounter::new("upload_files_total", "上传的图片数")?;
+        let upload_bytes_total = IntCounter::new("upload_bytes_total", "上传的图片字节数")?;
+        let goods_below_min_stock =
+            IntGauge::new("goods_below_min_stock", "库存低于预警库存的商品数")?;
+        let coupons_active = IntGauge::new("coupons_active", "有效期内已启用的优惠券数")?;
+
+        registry.register(Box::new(http_requests_total.clone()))?;
+        registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        registry.register(Box::new(db_query_duration_seconds.clone()))?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+        registry.register(Box::new(db_pool_max_connections.clone()))?;
+        registry.register(Box::new(upload_files_total.clone()))?;
+        registry.register(Box::new(upload_bytes_total.clone()))?;
+        registry.register(Box::new(goods_below_min_stock.clone()))?;
+        registry.register(Box::new(coupons_active.clone()))?;
+
+        Ok(Metrics {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_query_duration_seconds,
+            db_pool_connections,
+            db_pool_max_connections,
+            upload_files_total,
+            upload_bytes_total,
+            goods_below_min_stock,
+            coupons_active,
+        })
+    }
+
+    /// 记录一次请求，`route` 为路由模板，如 `/api/goods/{id}`
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        self.http_requests_total
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_request_duration_seconds
+            .with_label_values(&[method, route])
+            .observe(seconds);
+    }
+
+    /// 记录一次上传的图片
+    pub fn observe_upload(&self, bytes: u64) {
+        self.upload_files_total.inc();
+        self.upload_bytes_total.inc_by(bytes);
+    }
+
+    /// 记录 SQL 执行耗时，通过 `DatabaseConnection::set_metric_callback` 注册
+    pub fn observe_query(&self, info: &Info<'_>) {
+        const OPERATIONS: [&str; 7] = [
+            "select", "insert", "update", "delete", "begin", "commit", "rollback",
+        ];
+        let keyword = info
+            .statement
+            .sql
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let operation = OPERATIONS
+            .into_iter()
+            .find(|operation| *operation == keyword)
+            .unwrap_or("other");
+        self.db_query_duration_seconds
+            .with_label_values(&[operation, if info.failed { "true" } else { "false" }])
+            .observe(info.elapsed.as_secs_f64());
+    }
+
+    /// 更新连接池与业务指标
+    async fn refresh(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        if let Some((size, idle, max_connections)) = pool_stats(db) {
+            self.db_pool_connections
+                .with_label_values(&["idle"])
+                .set(idle);
+            self.db_pool_connections
+                .with_label_values(&["in_use"])
+                .set(size - idle);
+            self.db_pool_max_connections.set(max_connections);
+        }
+
+        let goods_below_min_stock = goods::Entity::find()
+            .filter(goods::Column::DeleteTime.is_null())
+            .filter(Expr::col(goods::Column::Stock).lt(Expr::col(goods::Column::MinStock)))
+            .count(db)
+            .await?;
+        self.goods_below_min_stock.set(goods_below_min_stock as i64);
+
+        let now = time::now();
+        let coupons_active = coupons::Entity::find()
+            .filter(coupons::Column::Status.eq(Status::Enabled))
+            .filter(coupons::Column::StartTime.lte(now))
+            .filter(coupons::Column::EndTime.gte(now))
+            .count(db)
+            .await?;
+        self.coupons_active.set(coupons_active as i64);
+        Ok(())
+    }
+
+    /// 更新指标后以 Prometheus 文本格式输出
+    ///
+    /// 业务指标查询失败时保留上一次的值，不影响其他指标的输出
+    pub async fn render(&self, db: &DatabaseConnection) -> String {
+        if let Err(err) = self.refresh(db).await {
+            tracing::error!(error = %err, "failed to refresh metrics");
+        }
+        let mut buffer = Vec::new();
+        if let Err(err) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!(error = %err, "failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
@@ -1,2 +1,14 @@
+pub mod error;
 pub mod json;
+pub mod login_guard;
+pub mod mailer;
+pub mod metrics;
+pub mod money;
+pub mod password;
+pub mod password_reset;
 pub mod response;
+pub mod secret_token;
+pub mod session;
+pub mod time;
+pub mod token;
+pub mod validation;
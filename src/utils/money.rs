@@ -0,0 +1,132 @@
+use std::{fmt, str::FromStr};
+
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::{ArrayType, ColumnType, Nullable, ValueType, ValueTypeErr},
+    ColIdx, QueryResult, TryGetError, TryGetable, Value,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::{
+    openapi::{schema::SchemaType, ObjectBuilder, RefOr, Schema, Type},
+    PartialSchema, ToSchema,
+};
+
+/// 金额保留的小数位数
+pub const MONEY_SCALE: u32 = 2;
+
+/// 金额，以定点小数保存与计算，避免浮点误差
+///
+/// - 数据库：`numeric(10, 2)`
+/// - JSON：序列化为保留两位小数的字符串，如 `"12.50"`；反序列化同时接受字符串与数字
+///
+/// 小数位数与正负不在反序列化时检查，由 [`validate_price`](crate::utils::validation::validate_price) 等校验
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::ZERO);
+    /// 金额的上限（不含），即 `99999999.99`，`numeric(10, 2)` 的整数部分最多 8 位
+    ///
+    /// 以 96 位整数的低、中 32 位表示 `9999999999`，小数位数为 2
+    pub const LIMIT: Money = Money(Decimal::from_parts(1_410_065_407, 2, 0, false, 2));
+
+    /// 由整数与小数位数构造，如 `Money::new(1250, 2)` 为 `12.50`
+    pub fn new(num: i64, scale: u32) -> Self {
+        Money(Decimal::new(num, scale))
+    }
+
+    pub fn as_decimal(&self) -> Decimal {
+        self.0
+    }
+
+    /// 小数位数是否不超过 [`MONEY_SCALE`]
+    pub fn has_valid_scale(&self) -> bool {
+        self.0.normalize().scale() <= MONEY_SCALE
+    }
+
+    /// 从数据库读取的值：SQLite 中按浮点数保存，需要舍入
+    fn from_db(value: Decimal) -> Self {
+        Money(value.round_dp(MONEY_SCALE))
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(value: Decimal) -> Self {
+        Money(value)
+    }
+}
+
+impl FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Decimal::from_str(s).map(Money)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Decimal as Deserialize>::deserialize(deserializer).map(Money)
+    }
+}
+
+impl PartialSchema for Money {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(SchemaType::Type(Type::String))
+            .pattern(Some(r"^\d+(\.\d{1,2})?$"))
+            .description(Some("金额，最多两位小数"))
+            .examples(["12.50"])
+            .into()
+    }
+}
+
+impl ToSchema for Money {}
+
+impl From<Money> for Value {
+    fn from(value: Money) -> Self {
+        value.0.into()
+    }
+}
+
+impl TryGetable for Money {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, idx: I) -> Result<Self, TryGetError> {
+        Decimal::try_get_by(res, idx).map(Money::from_db)
+    }
+}
+
+impl ValueType for Money {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        <Decimal as ValueType>::try_from(v).map(Money::from_db)
+    }
+
+    fn type_name() -> String {
+        String::from("Money")
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::Decimal
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Decimal(Some((10, MONEY_SCALE)))
+    }
+}
+
+impl Nullable for Money {
+    fn null() -> Value {
+        Value::Decimal(None)
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::LazyLock;
+
+use actix_web::web;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+use crate::utils::error::ApiError;
+
+/// 使用 Argon2id 和随机盐生成密码哈希（PHC 字符串格式）
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// 校验明文密码与已存储的哈希是否匹配，哈希格式错误时视为不匹配
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// 账号不存在时用于校验的哈希，参数与真实哈希相同
+static DUMMY_PASSWORD_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password("dummy-password").expect("生成占位密码哈希失败"));
+
+/// 登录时校验密码，账号不存在（`password_hash` 为 `None`）时改为校验占位哈希并返回不匹配，
+/// 使两种情况的耗时相同，避免通过响应时间判断账号是否存在
+pub fn verify_login_password(password: &str, password_hash: Option<&str>) -> bool {
+    match password_hash {
+        Some(password_hash) => verify_password(password, password_hash),
+        None => {
+            verify_password(password, &DUMMY_PASSWORD_HASH);
+            false
+        }
+    }
+}
+
+/// 在线程池中执行 [`hash_password`]，Argon2 的计算不占用异步 worker
+pub async fn hash_password_async(password: &str) -> Result<String, ApiError> {
+    let password = password.to_string();
+    web::block(move || hash_password(&password))
+        .await
+        .map_err(ApiError::internal)?
+        .map_err(ApiError::internal)
+}
+
+/// 在线程池中执行 [`verify_password`]
+pub async fn verify_password_async(password: &str, password_hash: &str) -> Result<bool, ApiError> {
+    let (password, password_hash) = (password.to_string(), password_hash.to_string());
+    web::block(move || verify_password(&password, &password_hash))
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// 在线程池中执行 [`verify_login_password`]
+pub async fn verify_login_password_async(
+    password: &str,
+    password_hash: Option<&str>,
+) -> Result<bool, ApiError> {
+    let (password, password_hash) = (password.to_string(), password_hash.map(String::from));
+    web::block(move || verify_login_password(&password, password_hash.as_deref()))
+        .await
+        .map_err(ApiError::internal)
+}
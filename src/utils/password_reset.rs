@@ -0,0 +1,125 @@
+use actix_web::web;
+use chrono::Utc;
+use migration::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter,
+};
+
+use crate::{
+    entities::password_reset_tokens::{self, ActiveModel},
+    utils::{
+        mailer::{Mail, Mailer},
+        secret_token::{generate_secret_token, hash_secret_token},
+    },
+};
+
+/// 重置密码令牌有效期（秒）
+pub const PASSWORD_RESET_EXPIRES_IN: i64 = 60 * 30;
+
+/// 申请重置密码的限流记录使用的主体类型，与登录失败记录分开计数
+pub const RESET_THROTTLE_ADMIN: &str = "admin_password_reset";
+pub const RESET_THROTTLE_USER: &str = "user_password_reset";
+
+/// 为账号创建重置密码令牌，同一账号此前未使用的令牌随即作废
+pub async fn create_reset_token(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+) -> Result<String, DbErr> {
+    password_reset_tokens::Entity::delete_many()
+        .filter(password_reset_tokens::Column::SubjectType.eq(subject_type))
+        .filter(password_reset_tokens::Column::SubjectId.eq(subject_id))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .exec(db)
+        .await?;
+
+    let token = generate_secret_token();
+    let reset_token = ActiveModel {
+        subject_type: Set(subject_type.to_string()),
+        subject_id: Set(subject_id),
+        token_hash: Set(hash_secret_token(&token)),
+        expires_at: Set(Utc::now().timestamp() + PASSWORD_RESET_EXPIRES_IN),
+        used_at: Set(None),
+        ..Default::default()
+    };
+    reset_token.insert(db).await?;
+    Ok(token)
+}
+
+/// 使用重置密码令牌，成功时返回账号 ID；令牌不存在、已过期或已使用时返回 `None`
+pub async fn consume_reset_token(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    token: &str,
+) -> Result<Option<i32>, DbErr> {
+    let now = Utc::now().timestamp();
+    let reset_token = password_reset_tokens::Entity::find()
+        .filter(password_reset_tokens::Column::TokenHash.eq(hash_secret_token(token)))
+        .filter(password_reset_tokens::Column::SubjectType.eq(subject_type))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .filter(password_reset_tokens::Column::ExpiresAt.gt(now))
+        .one(db)
+        .await?;
+    let Some(reset_token) = reset_token else {
+        return Ok(None);
+    };
+
+    // 以 `used_at` 为空作为条件更新，保证并发请求中只有一个能用上令牌
+    let result = password_reset_tokens::Entity::update_many()
+        .col_expr(password_reset_tokens::Column::UsedAt, Expr::value(now))
+        .filter(password_reset_tokens::Column::Id.eq(reset_token.id))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Ok(None);
+    }
+    Ok(Some(reset_token.subject_id))
+}
+
+/// 在后台创建重置令牌并发送邮件
+///
+/// 申请接口无需等待写库与发信，账号存在与否的响应时间一致。失败只记录日志
+pub fn spawn_reset_mail(
+    db: web::Data<DatabaseConnection>,
+    mailer: web::Data<dyn Mailer>,
+    subject_type: &'static str,
+    subject_id: i32,
+    email: String,
+) {
+    actix_web::rt::spawn(async move {
+        let token = match create_reset_token(db.get_ref(), subject_type, subject_id).await {
+            Ok(token) => token,
+            Err(err) => {
+                tracing::error!(
+                    error = %err,
+                    subject_type,
+                    subject_id,
+                    "failed to create password reset token"
+                );
+                return;
+            }
+        };
+        if let Err(err) = mailer.send(reset_mail(email, &token)).await {
+            tracing::error!(
+                error = %err,
+                subject_type,
+                subject_id,
+                "failed to send password reset mail"
+            );
+        }
+    });
+}
+
+/// 重置密码邮件
+pub fn reset_mail(to: String, token: &str) -> Mail {
+    Mail {
+        to,
+        subject: String::from("重置密码"),
+        body: format!(
+            "您正在重置密码，重置令牌为：\n\n{token}\n\n令牌 {} 分钟内有效且只能使用一次。如非本人操作，请忽略本邮件。",
+            PASSWORD_RESET_EXPIRES_IN / 60
+        ),
+    }
+}
@@ -1,3 +1,4 @@
+use actix_web::http::StatusCode;
 use serde::Serialize;
 use utoipa::ToSchema;
 
@@ -6,25 +7,57 @@ pub struct ResponseT<T> {
     code: i16,
     data: Option<T>,
     msg: String,
+    /// 错误码，仅在失败时返回，见 [`ApiError`](crate::utils::error::ApiError)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 pub fn response_t<T>(code: Option<i16>, data: Option<T>, msg: Option<String>) -> ResponseT<T> {
     ResponseT {
         code: code.unwrap_or(200),
-        data: data,
+        data,
         msg: msg.unwrap_or(String::from("ok")),
+        error: None,
+    }
+}
+
+pub fn response_error<T>(
+    status: StatusCode,
+    error: &str,
+    msg: String,
+    data: Option<T>,
+) -> ResponseT<T> {
+    ResponseT {
+        code: status.as_u16() as i16,
+        data,
+        msg,
+        error: Some(error.to_string()),
     }
 }
 
 #[derive(Serialize, ToSchema)]
 pub struct ResponseListT<T> {
     list: Vec<T>,
-    total_count: u64,
+    /// 总条数，查询参数 `count=false` 时不返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_count: Option<u64>,
+    /// 下一页的游标，见 [`Pagination`](crate::models::Pagination)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<i32>,
 }
 
 pub fn response_list_t<T>(list: Vec<T>, total_count: u64) -> ResponseListT<T> {
+    response_page_t(list, Some(total_count), None)
+}
+
+pub fn response_page_t<T>(
+    list: Vec<T>,
+    total_count: Option<u64>,
+    next_cursor: Option<i32>,
+) -> ResponseListT<T> {
     ResponseListT {
-        list: list,
-        total_count: total_count,
+        list,
+        total_count,
+        next_cursor,
     }
 }
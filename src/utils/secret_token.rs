@@ -0,0 +1,14 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// 生成 32 字节随机数的十六进制字符串，用作刷新令牌、重置密码令牌等一次性凭证
+pub fn generate_secret_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// 凭证的 SHA-256 哈希，数据库中只保存该值
+pub fn hash_secret_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
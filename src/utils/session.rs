@@ -0,0 +1,247 @@
+use actix_web::HttpRequest;
+use chrono::Utc;
+use migration::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::{
+    entities::sessions::{self, ActiveModel, Model},
+    utils::{
+        secret_token::{generate_secret_token, hash_secret_token},
+        time,
+    },
+};
+
+/// 会话主体类型：管理员
+pub const SUBJECT_ADMIN: &str = "admin";
+/// 会话主体类型：商城用户
+pub const SUBJECT_USER: &str = "user";
+
+pub const REVOKE_ROTATED: &str = "rotated";
+pub const REVOKE_LOGOUT: &str = "logout";
+pub const REVOKE_REUSE_DETECTED: &str = "reuse_detected";
+pub const REVOKE_ADMIN_REVOKED: &str = "admin_revoked";
+pub const REVOKE_ACCOUNT_DISABLED: &str = "account_disabled";
+pub const REVOKE_PASSWORD_RESET: &str = "password_reset";
+pub const REVOKE_PASSWORD_CHANGED: &str = "password_changed";
+
+/// 刷新令牌轮换结果
+pub enum RotateResult {
+    /// 轮换成功，返回新会话与新刷新令牌
+    Rotated(Box<Model>, String),
+    /// 令牌不存在、已过期或已被吊销
+    Invalid,
+    /// 已轮换过的令牌被再次使用，整个令牌家族已被吊销
+    Reused,
+}
+
+/// 客户端信息，随会话一起记录
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        ClientInfo {
+            user_agent: req
+                .headers()
+                .get(actix_web::http::header::USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from),
+            ip: req.connection_info().realip_remote_addr().map(String::from),
+        }
+    }
+}
+
+/// 创建会话并生成刷新令牌，`family_id` 为空时开启新的令牌家族
+pub async fn create_session(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+    family_id: Option<String>,
+    expires_in: i64,
+    client: &ClientInfo,
+) -> Result<(Model, String), DbErr> {
+    let refresh_token = generate_secret_token();
+    let now = Utc::now();
+
+    let new_session = ActiveModel {
+        subject_type: Set(subject_type.to_string()),
+        subject_id: Set(subject_id),
+        family_id: Set(family_id.unwrap_or_else(|| Uuid::new_v4().to_string())),
+        refresh_token_hash: Set(hash_secret_token(&refresh_token)),
+        expires_at: Set(now.timestamp() + expires_in),
+        revoke_reason: Set(None),
+        user_agent: Set(client.user_agent.clone()),
+        ip: Set(client.ip.clone()),
+        ..Default::default()
+    };
+    let session = new_session.insert(db).await?;
+    Ok((session, refresh_token))
+}
+
+/// 查找未吊销且未过期的会话
+pub async fn find_active_session(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    session_id: i32,
+) -> Result<Option<Model>, DbErr> {
+    sessions::Entity::find_by_id(session_id)
+        .filter(sessions::Column::SubjectType.eq(subject_type))
+        .filter(sessions::Column::RevokeReason.is_null())
+        .filter(sessions::Column::ExpiresAt.gt(Utc::now().timestamp()))
+        .one(db)
+        .await
+}
+
+/// 列出主体的全部有效会话，最新的在前
+pub async fn find_active_sessions(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+) -> Result<Vec<Model>, DbErr> {
+    sessions::Entity::find()
+        .filter(sessions::Column::SubjectType.eq(subject_type))
+        .filter(sessions::Column::SubjectId.eq(subject_id))
+        .filter(sessions::Column::RevokeReason.is_null())
+        .filter(sessions::Column::ExpiresAt.gt(Utc::now().timestamp()))
+        .order_by_desc(sessions::Column::Id)
+        .all(db)
+        .await
+}
+
+/// 按条件吊销尚未吊销的会话，返回受影响的会话数
+async fn revoke_where(
+    db: &DatabaseConnection,
+    condition: sea_orm::Condition,
+    reason: &str,
+) -> Result<u64, DbErr> {
+    let result = sessions::Entity::update_many()
+        .col_expr(sessions::Column::RevokeReason, Expr::value(reason))
+        .col_expr(sessions::Column::UpdateTime, Expr::value(time::now()))
+        .filter(condition)
+        .filter(sessions::Column::RevokeReason.is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// 吊销主体的某个会话
+pub async fn revoke_session(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+    session_id: i32,
+    reason: &str,
+) -> Result<u64, DbErr> {
+    revoke_where(
+        db,
+        sea_orm::Condition::all()
+            .add(sessions::Column::Id.eq(session_id))
+            .add(sessions::Column::SubjectType.eq(subject_type))
+            .add(sessions::Column::SubjectId.eq(subject_id)),
+        reason,
+    )
+    .await
+}
+
+/// 吊销主体的全部会话
+pub async fn revoke_subject_sessions(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+    reason: &str,
+) -> Result<u64, DbErr> {
+    revoke_where(
+        db,
+        sea_orm::Condition::all()
+            .add(sessions::Column::SubjectType.eq(subject_type))
+            .add(sessions::Column::SubjectId.eq(subject_id)),
+        reason,
+    )
+    .await
+}
+
+/// 吊销主体除 `keep_session_id` 以外的全部会话
+pub async fn revoke_other_sessions(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    subject_id: i32,
+    keep_session_id: i32,
+    reason: &str,
+) -> Result<u64, DbErr> {
+    revoke_where(
+        db,
+        sea_orm::Condition::all()
+            .add(sessions::Column::SubjectType.eq(subject_type))
+            .add(sessions::Column::SubjectId.eq(subject_id))
+            .add(sessions::Column::Id.ne(keep_session_id)),
+        reason,
+    )
+    .await
+}
+
+/// 使用刷新令牌轮换会话
+///
+/// 旧会话被标记为 `rotated` 并在同一令牌家族中创建新会话。
+/// 若已轮换过的令牌被再次使用，视为令牌泄露，吊销整个家族
+pub async fn rotate_session(
+    db: &DatabaseConnection,
+    subject_type: &str,
+    refresh_token: &str,
+    expires_in: i64,
+    client: &ClientInfo,
+) -> Result<RotateResult, DbErr> {
+    let session = sessions::Entity::find()
+        .filter(sessions::Column::RefreshTokenHash.eq(hash_secret_token(refresh_token)))
+        .filter(sessions::Column::SubjectType.eq(subject_type))
+        .one(db)
+        .await?;
+    let Some(session) = session else {
+        return Ok(RotateResult::Invalid);
+    };
+
+    match session.revoke_reason.as_deref() {
+        Some(REVOKE_ROTATED) => {
+            revoke_where(
+                db,
+                sea_orm::Condition::all().add(sessions::Column::FamilyId.eq(&session.family_id)),
+                REVOKE_REUSE_DETECTED,
+            )
+            .await?;
+            return Ok(RotateResult::Reused);
+        }
+        Some(_) => return Ok(RotateResult::Invalid),
+        None if session.expires_at <= Utc::now().timestamp() => return Ok(RotateResult::Invalid),
+        None => {}
+    }
+
+    // 仅当旧会话仍未吊销时才能轮换，避免并发刷新各自拿到新令牌
+    let rotated = revoke_where(
+        db,
+        sea_orm::Condition::all().add(sessions::Column::Id.eq(session.id)),
+        REVOKE_ROTATED,
+    )
+    .await?;
+    if rotated == 0 {
+        return Ok(RotateResult::Invalid);
+    }
+
+    let (new_session, new_refresh_token) = create_session(
+        db,
+        subject_type,
+        session.subject_id,
+        Some(session.family_id),
+        expires_in,
+        client,
+    )
+    .await?;
+    Ok(RotateResult::Rotated(
+        Box::new(new_session),
+        new_refresh_token,
+    ))
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use sea_orm::ActiveValue::{self, Set};
+
+/// 写入数据库的当前时间
+///
+/// 精确到秒，保证各数据库返回与序列化（RFC 3339）的结果一致
+pub fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(0)
+}
+
+/// 供实体的 `before_save` 使用：新增时写入 `create_time`，每次保存时刷新 `update_time`
+///
+/// 显式设置的值不会被覆盖，以便导入规则等数据时保留原有时间
+pub fn set_timestamps(
+    create_time: &mut ActiveValue<DateTime<Utc>>,
+    update_time: Option<&mut ActiveValue<DateTime<Utc>>>,
+    insert: bool,
+) {
+    let now = now();
+    if insert && create_time.is_not_set() {
+        *create_time = Set(now);
+    }
+    if let Some(update_time) = update_time.filter(|update_time| !update_time.is_set()) {
+        *update_time = Set(now);
+    }
+}
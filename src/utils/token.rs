@@ -0,0 +1,182 @@
+use std::future::{ready, Ready};
+
+use futures_util::future::LocalBoxFuture;
+
+use actix_web::{dev::Payload, web, FromRequest, HttpMessage, HttpRequest};
+use chrono::Utc;
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    entities::{admins, sea_orm_active_enums::Status, users},
+    utils::{
+        error::ApiError,
+        session::{find_active_session, SUBJECT_USER},
+    },
+};
+
+/// 管理员令牌的 `aud` 声明
+pub const ADMIN_AUDIENCE: &str = "admin";
+/// 商城用户令牌的 `aud` 声明
+pub const USER_AUDIENCE: &str = "user";
+
+/// 签发与校验 HS256 令牌所用的密钥
+pub struct TokenKeys {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    /// 访问令牌有效期（秒）
+    pub expires_in: i64,
+    /// 刷新令牌有效期（秒）
+    pub refresh_expires_in: i64,
+}
+
+impl TokenKeys {
+    pub fn new(secret: &[u8], expires_in: i64, refresh_expires_in: i64) -> Self {
+        TokenKeys {
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            expires_in,
+            refresh_expires_in,
+        }
+    }
+
+    pub fn encode<C: Serialize>(&self, claims: &C) -> jsonwebtoken::errors::Result<String> {
+        jsonwebtoken::encode(&Header::default(), claims, &self.encoding_key)
+    }
+
+    pub fn decode<C: DeserializeOwned>(
+        &self,
+        token: &str,
+        audience: &str,
+    ) -> jsonwebtoken::errors::Result<C> {
+        let mut validation = Validation::default();
+        validation.set_audience(&[audience]);
+        jsonwebtoken::decode::<C>(token, &self.decoding_key, &validation).map(|data| data.claims)
+    }
+}
+
+/// 管理员令牌密钥，以 `web::Data` 的形式注册到应用中
+pub struct AdminTokenKeys(pub TokenKeys);
+
+/// 商城用户令牌密钥，与管理员令牌使用不同的密钥和 `aud`
+pub struct UserTokenKeys(pub TokenKeys);
+
+/// 管理员令牌载荷，`sid` 为签发令牌的会话 ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminClaims {
+    pub sub: i32,
+    pub sid: i32,
+    pub role_id: Option<i32>,
+    pub is_super: i32,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl AdminClaims {
+    pub fn new(admin: &admins::Model, session_id: i32, expires_in: i64) -> Self {
+        let now = Utc::now().timestamp();
+        AdminClaims {
+            sub: admin.id,
+            sid: session_id,
+            role_id: admin.role_id,
+            is_super: admin.is_super,
+            aud: String::from(ADMIN_AUDIENCE),
+            iat: now,
+            exp: now + expires_in,
+        }
+    }
+}
+
+/// 商城用户令牌载荷，`sid` 为签发令牌的会话 ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserClaims {
+    pub sub: i32,
+    pub sid: i32,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl UserClaims {
+    pub fn new(user: &users::Model, session_id: i32, expires_in: i64) -> Self {
+        let now = Utc::now().timestamp();
+        UserClaims {
+            sub: user.id,
+            sid: session_id,
+            aud: String::from(USER_AUDIENCE),
+            iat: now,
+            exp: now + expires_in,
+        }
+    }
+}
+
+/// 从 `Authorization: Bearer <token>` 请求头中取出令牌
+pub fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(actix_web::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+/// 当前登录的管理员，由权限中间件校验令牌与会话后写入请求扩展，缺失时返回 401
+pub struct CurrentAdmin(pub AdminClaims);
+
+impl FromRequest for CurrentAdmin {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AdminClaims>()
+                .cloned()
+                .map(CurrentAdmin)
+                .ok_or_else(|| ApiError::unauthorized("缺少访问令牌")),
+        )
+    }
+}
+
+/// 当前登录的商城用户，令牌无效、会话已吊销或用户被禁用时返回 401
+pub struct CurrentUser {
+    pub claims: UserClaims,
+    pub user: users::Model,
+}
+
+impl FromRequest for CurrentUser {
+    type Error = ApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let keys = req.app_data::<web::Data<UserTokenKeys>>().cloned();
+        let db = req.app_data::<web::Data<DatabaseConnection>>().cloned();
+        let token = bearer_token(req).map(String::from);
+
+        Box::pin(async move {
+            let (Some(keys), Some(db)) = (keys, db) else {
+                return Err(ApiError::internal("未配置用户令牌密钥或数据库连接"));
+            };
+            let token = token.ok_or_else(|| ApiError::unauthorized("缺少访问令牌"))?;
+            let claims = keys
+                .0
+                .decode::<UserClaims>(&token, USER_AUDIENCE)
+                .map_err(|_| ApiError::unauthorized("访问令牌无效或已过期"))?;
+
+            let session = find_active_session(db.get_ref(), SUBJECT_USER, claims.sid).await?;
+            if session.is_none_or(|session| session.subject_id != claims.sub) {
+                return Err(ApiError::unauthorized("会话已失效"));
+            }
+
+            match users::Entity::find_by_id(claims.sub)
+                .one(db.get_ref())
+                .await?
+            {
+                Some(user) if user.status == Status::Enabled => Ok(CurrentUser { claims, user }),
+                Some(_) => Err(ApiError::unauthorized("用户已被禁用")),
+                None => Err(ApiError::unauthorized("用户不存在")),
+            }
+        })
+    }
+}
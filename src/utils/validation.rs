@@ -0,0 +1,173 @@
+use std::{borrow::Cow, ops::Deref};
+
+use actix_web::{
+    dev::Payload,
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    web, FromRequest, HttpRequest,
+};
+use chrono::{DateTime, Utc};
+use futures_util::future::LocalBoxFuture;
+use sea_orm::{ConnectionTrait, EntityTrait, PrimaryKeyTrait};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError};
+
+use crate::utils::{error::ApiError, money::Money};
+
+/// 反序列化后再执行 [`Validate`] 校验的 JSON 请求体
+///
+/// 用法与 `web::Json<T>` 相同。反序列化失败返回 [`ApiError::Validation`]，
+/// 校验失败返回 [`ApiError::InvalidFields`]，处理函数只会收到合法的请求体
+#[derive(Debug)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<T>::from_request(req, payload);
+        Box::pin(async move {
+            let data = json.await?.into_inner();
+            data.validate().map_err(ApiError::from)?;
+            Ok(ValidatedJson(data))
+        })
+    }
+}
+
+/// 反序列化后再执行 [`Validate`] 校验的查询参数，用法与 `web::Query<T>` 相同
+#[derive(Debug)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> Deref for ValidatedQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let result = web::Query::<T>::extract(req)
+            .into_inner()
+            .and_then(|query| {
+                let data = query.into_inner();
+                data.validate().map_err(ApiError::from)?;
+                Ok(ValidatedQuery(data))
+            });
+        std::future::ready(result)
+    }
+}
+
+/// `web::JsonConfig` 的错误处理，请求体无法解析时返回统一的错误格式
+pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    let msg = match err {
+        JsonPayloadError::ContentType => String::from("Content-Type 必须为 application/json"),
+        JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+            String::from("请求体过大")
+        }
+        JsonPayloadError::Deserialize(err) => format!("请求体格式错误：{err}"),
+        err => format!("请求体读取失败：{err}"),
+    };
+    ApiError::validation(msg).into()
+}
+
+/// `web::QueryConfig` 的错误处理
+pub fn query_error_handler(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    ApiError::validation(format!("查询参数错误：{err}")).into()
+}
+
+/// `web::PathConfig` 的错误处理
+pub fn path_error_handler(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+    ApiError::validation(format!("路径参数错误：{err}")).into()
+}
+
+fn invalid(code: &'static str, message: &'static str) -> ValidationError {
+    ValidationError::new(code).with_message(Cow::Borrowed(message))
+}
+
+/// 结束时间不能早于开始时间
+pub fn validate_time_range(
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<(), ValidationError> {
+    if end_time < start_time {
+        return Err(invalid("time_range", "结束时间不能早于开始时间"));
+    }
+    Ok(())
+}
+
+/// 金额必须小于 [`Money::LIMIT`]，否则超出数据库列的范围
+fn validate_money_limit(value: &Money) -> Result<(), ValidationError> {
+    if *value >= Money::LIMIT {
+        return Err(invalid("range", "必须小于 99999999.99"));
+    }
+    Ok(())
+}
+
+/// 价格必须为非负数，最多两位小数，如 `12`、`12.5`、`12.50`，且小于 [`Money::LIMIT`]
+pub fn validate_price(value: &Money) -> Result<(), ValidationError> {
+    validate_money_limit(value)?;
+    if *value >= Money::ZERO && value.has_valid_scale() {
+        Ok(())
+    } else {
+        Err(invalid("price", "价格必须为非负数，最多两位小数"))
+    }
+}
+
+/// 金额必须大于 0，最多两位小数，且小于 [`Money::LIMIT`]
+pub fn validate_positive_price(value: &Money) -> Result<(), ValidationError> {
+    if *value <= Money::ZERO {
+        return Err(invalid("range", "必须大于 0"));
+    }
+    validate_money_limit(value)?;
+    if !value.has_valid_scale() {
+        return Err(invalid("price", "最多两位小数"));
+    }
+    Ok(())
+}
+
+/// 请求方式必须为 `GET`、`POST`、`PUT`、`PATCH` 或 `DELETE`，菜单规则可为空
+pub fn validate_http_method(value: &str) -> Result<(), ValidationError> {
+    match value {
+        "" | "GET" | "POST" | "PUT" | "PATCH" | "DELETE" => Ok(()),
+        _ => Err(invalid(
+            "http_method",
+            "请求方式必须为 GET、POST、PUT、PATCH 或 DELETE",
+        )),
+    }
+}
+
+/// 请求体中引用的数据必须存在，否则作为 `field` 字段的校验错误返回
+pub async fn validate_reference<E>(
+    db: &impl ConnectionTrait,
+    id: i32,
+    field: &str,
+    message: &str,
+) -> Result<(), ApiError>
+where
+    E: EntityTrait,
+    <E::PrimaryKey as PrimaryKeyTrait>::ValueType: From<i32>,
+{
+    if E::find_by_id(id).one(db).await?.is_none() {
+        return Err(ApiError::invalid_field(field, message));
+    }
+    Ok(())
+}
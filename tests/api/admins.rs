@@ -0,0 +1,709 @@
+use std::sync::Arc;
+
+use actix_web::{
+    http::{Method, StatusCode},
+    test::TestRequest,
+};
+use serde_json::{json, Value};
+
+use crate::common::{
+    assert_error, assert_ok, create_admin, find_rule_id, read_reset_token, spawn_app,
+    spawn_app_with_mailer, FailingMailer, ADMIN_PASSWORD, ADMIN_USERNAME,
+};
+
+fn admin_body(username: &str, role_id: i64, status: i64) -> Value {
+    json!({
+        "username": username,
+        "password": "admin123456",
+        "role_id": role_id,
+        "status": status,
+        "avatar": null,
+        "email": format!("{username}@example.com"),
+    })
+}
+
+fn login_req(username: &str, password: &str) -> TestRequest {
+    TestRequest::post()
+        .uri("/api/admins/login")
+        .set_json(json!({ "username": username, "password": password }))
+}
+
+fn refresh_req(refresh_token: &Value) -> TestRequest {
+    TestRequest::post()
+        .uri("/api/admins/refresh")
+        .set_json(json!({ "refresh_token": refresh_token }))
+}
+
+#[actix_web::test]
+async fn login_rejects_wrong_password() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.send(login_req(ADMIN_USERNAME, "wrong-password")).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "用户名或密码错误");
+}
+
+#[actix_web::test]
+async fn protected_routes_require_token() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.send(TestRequest::get().uri("/api/admins/me")).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "缺少访问令牌");
+    let msg = assert_error(
+        app.request_as("invalid", Method::GET, "/api/notices?page=1&size=10", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "访问令牌无效或已过期");
+}
+
+#[actix_web::test]
+async fn current_admin_and_menus() {
+    let app = spawn_app().await;
+    app.init_rules().await;
+
+    let me = assert_ok(app.get("/api/admins/me").await);
+    assert_eq!(me["username"], ADMIN_USERNAME);
+    assert_eq!(me["is_super"], 1);
+    assert!(me.get("password").is_none());
+
+    // 超级管理员拥有全部菜单与权限
+    let menus = assert_ok(app.get("/api/admins/menus").await);
+    assert!(!menus["menus"].as_array().unwrap().is_empty());
+    let conditions = menus["conditions"].as_array().unwrap();
+    assert!(conditions.contains(&json!("getGoodsList")));
+}
+
+#[actix_web::test]
+async fn menus_include_missing_parents() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role_id = app.create_role("商品管理员").await;
+    // 只拥有“商品管理”下的“商品管理”菜单（13）与商品列表接口，不拥有上级菜单（6）
+    let rule_id = find_rule_id(&rules, "getGoodsList").unwrap();
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{role_id}/set_rules"),
+            json!({ "rule_ids": [13, rule_id] }),
+        )
+        .await,
+    );
+    assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let token = app.login("op", "admin123456").await;
+
+    let menus = assert_ok(
+        app.request_as(&token, Method::GET, "/api/admins/menus", None)
+            .await,
+    );
+    let tree = menus["menus"].as_array().unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0]["id"], 6);
+    assert_eq!(tree[0]["child"].as_array().unwrap().len(), 1);
+    assert_eq!(tree[0]["child"][0]["id"], 13);
+    // 补全的上级菜单不会带来额外的权限
+    assert_eq!(menus["conditions"], json!(["getGoodsList"]));
+}
+
+#[actix_web::test]
+async fn admin_crud_flow() {
+    let app = spawn_app().await;
+    let role = assert_ok(
+        app.post(
+            "/api/roles",
+            json!({ "name": "运营", "desc": "运营人员", "status": 1 }),
+        )
+        .await,
+    );
+    let role_id = role["id"].as_i64().unwrap();
+
+    let admin = assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let id = admin["id"].as_i64().unwrap();
+    assert_eq!(admin["username"], "op");
+    assert_eq!(admin["is_super"], 0);
+    assert_eq!(admin["role"]["id"], role_id);
+    assert_eq!(admin["role"]["name"], "运营");
+    assert!(admin.get("password").is_none());
+
+    let msg = assert_error(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+        StatusCode::CONFLICT,
+        "CONFLICT",
+    );
+    assert_eq!(msg, "username已存在");
+
+    // 改名时同样不能与其他管理员重名
+    assert_ok(
+        app.post("/api/admins/manager", admin_body("other", role_id, 1))
+            .await,
+    );
+    let msg = assert_error(
+        app.put(
+            &format!("/api/admins/manager/{id}"),
+            admin_body("other", role_id, 1),
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "CONFLICT",
+    );
+    assert_eq!(msg, "username已存在");
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/admins/manager/{id}"),
+            admin_body("op2", role_id, 1),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    let token = app.login("op2", "admin123456").await;
+
+    // 禁用后原有会话失效，且无法再登录
+    assert_ok(
+        app.patch(
+            &format!("/api/admins/manager/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+    let msg = assert_error(
+        app.request_as(&token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "会话已失效");
+    let msg = assert_error(
+        app.send(login_req("op2", "admin123456")).await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    assert_eq!(msg, "管理员已被禁用");
+
+    assert_ok(
+        app.request(
+            Method::PATCH,
+            &format!("/api/admins/manager/{id}/unlock"),
+            None,
+        )
+        .await,
+    );
+    assert_ok(app.delete(&format!("/api/admins/manager/{id}")).await);
+    assert_error(
+        app.patch(
+            &format!("/api/admins/manager/{id}/update_status"),
+            json!({ "status": 1 }),
+        )
+        .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn missing_admin_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/admins/manager/999", admin_body("nobody", 0, 1))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "管理员不存在");
+    assert_error(
+        app.get("/api/admins/manager/999").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.request(Method::PATCH, "/api/admins/manager/999/unlock", None)
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn refresh_token_rotation() {
+    let app = spawn_app().await;
+    let tokens = assert_ok(app.send(login_req(ADMIN_USERNAME, ADMIN_PASSWORD)).await);
+    assert_eq!(tokens["token_type"], "Bearer");
+
+    let refreshed = assert_ok(app.send(refresh_req(&tokens["refresh_token"])).await);
+    assert_ne!(refreshed["refresh_token"], tokens["refresh_token"]);
+    let token = refreshed["token"].as_str().unwrap();
+    assert_ok(
+        app.request_as(token, Method::GET, "/api/admins/me", None)
+            .await,
+    );
+
+    // 旧的刷新令牌被重复使用时，整个会话被吊销
+    let msg = assert_error(
+        app.send(refresh_req(&tokens["refresh_token"])).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "刷新令牌已被使用，相关会话已全部吊销");
+    assert_error(
+        app.request_as(token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+}
+
+#[actix_web::test]
+async fn logout_revokes_session() {
+    let app = spawn_app().await;
+    let token = app.login(ADMIN_USERNAME, ADMIN_PASSWORD).await;
+
+    assert_ok(
+        app.request_as(&token, Method::POST, "/api/admins/logout", None)
+            .await,
+    );
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    // 其他会话不受影响
+    assert_ok(app.get("/api/admins/me").await);
+}
+
+#[actix_web::test]
+async fn admin_detail_follows_role() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+
+    let (status, body) = app
+        .post("/api/admins/manager", admin_body("op", 999, 1))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["role_id"], json!(["角色不存在"]));
+
+    let admin = assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let id = admin["id"].as_i64().unwrap();
+    let admin = assert_ok(app.get(&format!("/api/admins/manager/{id}")).await);
+    assert_eq!(admin["username"], "op");
+    assert_eq!(admin["role"]["name"], "运营");
+
+    // 删除角色后管理员仍保留，只是不再属于任何角色
+    assert_ok(app.delete(&format!("/api/roles/{role_id}")).await);
+    let admin = assert_ok(app.get(&format!("/api/admins/manager/{id}")).await);
+    assert_eq!(admin["role_id"], Value::Null);
+    assert_eq!(admin["role"], Value::Null);
+}
+
+#[actix_web::test]
+async fn sessions_can_be_listed_and_revoked() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+    let admin = assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let id = admin["id"].as_i64().unwrap();
+    let token = app.login("op", "admin123456").await;
+
+    let sessions = assert_ok(app.get(&format!("/api/admins/manager/{id}/sessions")).await);
+    let sessions = sessions.as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    let session_id = sessions[0]["id"].as_i64().unwrap();
+
+    assert_ok(
+        app.delete(&format!("/api/admins/manager/{id}/sessions/{session_id}"))
+            .await,
+    );
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    let msg = assert_error(
+        app.delete(&format!("/api/admins/manager/{id}/sessions/{session_id}"))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "会话不存在或已被吊销");
+
+    app.login("op", "admin123456").await;
+    app.login("op", "admin123456").await;
+    let revoked = assert_ok(
+        app.delete(&format!("/api/admins/manager/{id}/sessions"))
+            .await,
+    );
+    assert_eq!(revoked, 2);
+    let sessions = assert_ok(app.get(&format!("/api/admins/manager/{id}/sessions")).await);
+    assert_eq!(sessions, json!([]));
+}
+
+#[actix_web::test]
+async fn only_super_admins_manage_super_admins() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role_id = app.create_role("管理员管理").await;
+    let rule_ids: Vec<i64> = ["updateManager", "deleteManager", "updateManagerStatus"]
+        .into_iter()
+        .map(|condition| find_rule_id(&rules, condition).unwrap())
+        .collect();
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{role_id}/set_rules"),
+            json!({ "rule_ids": rule_ids }),
+        )
+        .await,
+    );
+    create_admin(&app.db, "op", "admin123456", Some(role_id as i32), 0).await;
+    let other = create_admin(&app.db, "other", "admin123456", Some(role_id as i32), 0).await;
+    let root_id = assert_ok(app.get("/api/admins/me").await)["id"].clone();
+    let token = app.login("op", "admin123456").await;
+
+    for (method, uri, body) in [
+        (
+            Method::PUT,
+            format!("/api/admins/manager/{root_id}"),
+            Some(admin_body("root", role_id, 1)),
+        ),
+        (
+            Method::PATCH,
+            format!("/api/admins/manager/{root_id}/update_status"),
+            Some(json!({ "status": 0 })),
+        ),
+        (
+            Method::DELETE,
+            format!("/api/admins/manager/{root_id}/sessions"),
+            None,
+        ),
+        (
+            Method::DELETE,
+            format!("/api/admins/manager/{root_id}/sessions/1"),
+            None,
+        ),
+        (
+            Method::DELETE,
+            format!("/api/admins/manager/{root_id}"),
+            None,
+        ),
+    ] {
+        let msg = assert_error(
+            app.request_as(&token, method, &uri, body).await,
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+        );
+        assert_eq!(msg, "只有超级管理员可以操作超级管理员", "{uri}");
+    }
+    // 超级管理员仍可正常使用，普通管理员之间不受影响
+    assert_ok(app.get("/api/admins/me").await);
+    assert_ok(
+        app.request_as(
+            &token,
+            Method::PATCH,
+            &format!("/api/admins/manager/{}/update_status", other.id),
+            Some(json!({ "status": 0 })),
+        )
+        .await,
+    );
+}
+
+#[actix_web::test]
+async fn role_rules_grant_access() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role = assert_ok(
+        app.post(
+            "/api/roles",
+            json!({ "name": "商品管理员", "desc": "", "status": 1 }),
+        )
+        .await,
+    );
+    let role_id = role["id"].as_i64().unwrap();
+    assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let token = app.login("op", "admin123456").await;
+
+    let msg = assert_error(
+        app.request_as(&token, Method::GET, "/api/goods?page=1&size=10", None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    assert_eq!(msg, "没有访问该接口的权限");
+
+    let rule_id = find_rule_id(&rules, "getGoodsList").unwrap();
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{role_id}/set_rules"),
+            json!({ "rule_ids": [rule_id] }),
+        )
+        .await,
+    );
+    assert_ok(
+        app.request_as(&token, Method::GET, "/api/goods?page=1&size=10", None)
+            .await,
+    );
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/notices?page=1&size=10", None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    // 详情接口与列表接口使用相同的权限
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/users/1", None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    let menus = assert_ok(
+        app.request_as(&token, Method::GET, "/api/admins/menus", None)
+            .await,
+    );
+    assert_eq!(menus["conditions"], json!(["getGoodsList"]));
+
+    // 角色被禁用后不再拥有任何权限
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{role_id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/goods?page=1&size=10", None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+}
+
+#[actix_web::test]
+async fn encoded_paths_are_checked_against_rules() {
+    let app = spawn_app().await;
+    create_admin(&app.db, "op", "admin123456", None, 0).await;
+    let token = app.login("op", "admin123456").await;
+
+    // 百分号编码的路径按解码后命中的路由校验权限
+    for (method, uri) in [
+        (Method::POST, "/api/rules/init"),
+        (Method::POST, "/api/rules/%69nit"),
+        (Method::DELETE, "/api/skus/delete_all"),
+        (Method::DELETE, "/api/skus/%64elete_all"),
+        (Method::PATCH, "/api/goods/%73tatus/batch"),
+        (Method::GET, "/api/no_such_route"),
+    ] {
+        assert_error(
+            app.request_as(&token, method, uri, Some(json!({ "ids": [] })))
+                .await,
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+        );
+    }
+    assert_ok(
+        app.request_as(&token, Method::GET, "/api/admins/%6De", None)
+            .await,
+    );
+}
+
+#[actix_web::test]
+async fn role_change_applies_to_existing_tokens() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let goods_role_id = app.create_role("商品管理员").await;
+    let notice_role_id = app.create_role("公告管理员").await;
+    for (role_id, condition) in [
+        (goods_role_id, "getGoodsList"),
+        (notice_role_id, "getNoticeList"),
+    ] {
+        let rule_id = find_rule_id(&rules, condition).unwrap();
+        assert_ok(
+            app.patch(
+                &format!("/api/roles/{role_id}/set_rules"),
+                json!({ "rule_ids": [rule_id] }),
+            )
+            .await,
+        );
+    }
+    let admin = assert_ok(
+        app.post("/api/admins/manager", admin_body("op", goods_role_id, 1))
+            .await,
+    );
+    let id = admin["id"].as_i64().unwrap();
+    let token = app.login("op", "admin123456").await;
+    assert_ok(
+        app.request_as(&token, Method::GET, "/api/goods?page=1&size=10", None)
+            .await,
+    );
+
+    // 更换角色后，原有令牌按新角色校验权限
+    assert_ok(
+        app.put(
+            &format!("/api/admins/manager/{id}"),
+            admin_body("op", notice_role_id, 1),
+        )
+        .await,
+    );
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/goods?page=1&size=10", None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    assert_ok(
+        app.request_as(&token, Method::GET, "/api/notices?page=1&size=10", None)
+            .await,
+    );
+}
+
+#[actix_web::test]
+async fn password_reset_flow() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+    assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let token = app.login("op", "admin123456").await;
+    let reset_request = |username: &str| {
+        TestRequest::post()
+            .uri("/api/admins/password_reset/request")
+            .set_json(json!({ "username": username }))
+    };
+
+    // 用户名不存在时同样返回成功，且不发送邮件
+    assert_ok(app.send(reset_request("nobody")).await);
+    assert!(!app.dir.path().join("outbox").exists());
+    assert_ok(app.send(reset_request("op")).await);
+    let reset_token = read_reset_token(&app.dir.path().join("outbox")).await;
+
+    let confirm = TestRequest::post()
+        .uri("/api/admins/password_reset/confirm")
+        .set_json(json!({ "token": reset_token, "new_password": "new-password" }));
+    assert_ok(app.send(confirm).await);
+    let confirm = TestRequest::post()
+        .uri("/api/admins/password_reset/confirm")
+        .set_json(json!({ "token": reset_token, "new_password": "new-password" }));
+    let msg = assert_error(
+        app.send(confirm).await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "重置令牌无效或已过期");
+
+    // 重置后原有会话被吊销，只能使用新密码登录
+    assert_error(
+        app.request_as(&token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_error(
+        app.send(login_req("op", "admin123456")).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    app.login("op", "new-password").await;
+}
+
+#[actix_web::test]
+async fn password_reset_hides_mail_failures() {
+    let app = spawn_app_with_mailer(Some(Arc::new(FailingMailer))).await;
+    let role_id = app.create_role("运营").await;
+    assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let reset_request = |username: &str| {
+        TestRequest::post()
+            .uri("/api/admins/password_reset/request")
+            .set_json(json!({ "username": username }))
+    };
+
+    // 邮件发送失败时的响应与账号不存在时相同
+    let missing = app.send(reset_request("nobody")).await;
+    let existing = app.send(reset_request("op")).await;
+    assert_eq!(missing, existing);
+    assert_ok(existing);
+}
+
+#[actix_web::test]
+async fn password_reset_requests_are_throttled() {
+    let app = spawn_app().await;
+    let reset_request = |username: &str| {
+        TestRequest::post()
+            .uri("/api/admins/password_reset/request")
+            .set_json(json!({ "username": username }))
+    };
+
+    // 账号存在与否都按用户名计数，超过次数后同样返回 429
+    for username in ["nobody", ADMIN_USERNAME] {
+        for _ in 0..5 {
+            assert_ok(app.send(reset_request(username)).await);
+        }
+        assert_error(
+            app.send(reset_request(username)).await,
+            StatusCode::TOO_MANY_REQUESTS,
+            "TOO_MANY_REQUESTS",
+        );
+    }
+    // 重置申请的计数不影响登录
+    app.login(ADMIN_USERNAME, ADMIN_PASSWORD).await;
+}
+
+#[actix_web::test]
+async fn repeated_failures_lock_and_unlock() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+    let admin = assert_ok(
+        app.post("/api/admins/manager", admin_body("op", role_id, 1))
+            .await,
+    );
+    let id = admin["id"].as_i64().unwrap();
+
+    for _ in 0..5 {
+        assert_error(
+            app.send(login_req("op", "wrong-password")).await,
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+        );
+    }
+    // 锁定期间正确的密码同样被拒绝
+    let msg = assert_error(
+        app.send(login_req("op", "admin123456")).await,
+        StatusCode::TOO_MANY_REQUESTS,
+        "TOO_MANY_REQUESTS",
+    );
+    assert!(msg.starts_with("登录失败次数过多"), "{msg}");
+
+    assert_ok(
+        app.request(
+            Method::PATCH,
+            &format!("/api/admins/manager/{id}/unlock"),
+            None,
+        )
+        .await,
+    );
+    app.login("op", "admin123456").await;
+}
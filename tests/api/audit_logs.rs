@@ -0,0 +1,193 @@
+use actix_web::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::{assert_ok, assert_page, create_admin, find_rule_id, spawn_app};
+
+#[actix_web::test]
+async fn mutations_are_audited() {
+    let app = spawn_app().await;
+    let notice = assert_ok(
+        app.post(
+            "/api/notices",
+            json!({ "title": "公告", "content": "内容" }),
+        )
+        .await,
+    );
+    let id = notice["id"].as_i64().unwrap();
+    assert_ok(
+        app.put(
+            &format!("/api/notices/{id}"),
+            json!({ "title": "新公告", "content": "内容" }),
+        )
+        .await,
+    );
+    // 查询不记录审计日志
+    assert_ok(app.get("/api/notices?page=1&size=10").await);
+
+    let logs = assert_ok(app.get("/api/audit_logs?page=1&size=10").await);
+    let logs = assert_page(&logs, 2, 2);
+    // 按时间倒序
+    let update = &logs[0];
+    assert_eq!(update["method"], "PUT");
+    assert_eq!(update["route"], "/api/notices/{id}");
+    assert_eq!(update["target"], "notices");
+    assert_eq!(update["target_ids"], json!([id]));
+    assert_eq!(update["before"][0]["title"], "公告");
+    assert_eq!(update["after"][0]["title"], "新公告");
+    assert_eq!(update["outcome"], "success");
+    assert_eq!(update["status_code"], 200);
+
+    // 新建时 ID 取自响应数据
+    let create = &logs[1];
+    assert_eq!(create["method"], "POST");
+    assert_eq!(create["target_ids"], json!([id]));
+    assert_eq!(create["before"], json!(null));
+    assert_eq!(create["after"][0]["content"], "内容");
+}
+
+#[actix_web::test]
+async fn batch_and_failed_requests_are_audited() {
+    let app = spawn_app().await;
+    assert_ok(
+        app.patch("/api/goods/delete/batch", json!({ "ids": [1, 2] }))
+            .await,
+    );
+    let (status, _) = app
+        .put(
+            "/api/notices/999",
+            json!({ "title": "公告", "content": "内容" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let logs = assert_ok(app.get("/api/audit_logs?page=1&size=10&target=goods").await);
+    let logs = assert_page(&logs, 1, 1);
+    assert_eq!(logs[0]["target_ids"], json!([1, 2]));
+
+    let logs = assert_ok(
+        app.get("/api/audit_logs?page=1&size=10&outcome=failure")
+            .await,
+    );
+    let logs = assert_page(&logs, 1, 1);
+    assert_eq!(logs[0]["target"], "notices");
+    assert_eq!(logs[0]["status_code"], 404);
+}
+
+#[actix_web::test]
+async fn admin_passwords_are_not_logged() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+    let admin = assert_ok(
+        app.post(
+            "/api/admins/manager",
+            json!({
+                "username": "op",
+                "password": "admin123456",
+                "role_id": role_id,
+                "status": 1,
+                "avatar": null,
+                "email": null,
+            }),
+        )
+        .await,
+    );
+
+    let logs = assert_ok(
+        app.get("/api/audit_logs?page=1&size=10&target=admins")
+            .await,
+    );
+    let logs = assert_page(&logs, 1, 1);
+    assert_eq!(logs[0]["target_ids"], json!([admin["id"]]));
+    assert_eq!(logs[0]["after"][0]["username"], "op");
+    assert!(logs[0]["after"][0].get("password").is_none());
+}
+
+#[actix_web::test]
+async fn audit_logs_filter_by_admin() {
+    let app = spawn_app().await;
+    let other = create_admin(&app.db, "other", "admin123456", None, 1).await;
+    let token = app.login("other", "admin123456").await;
+
+    assert_ok(
+        app.post(
+            "/api/notices",
+            json!({ "title": "公告 1", "content": "内容" }),
+        )
+        .await,
+    );
+    assert_ok(
+        app.request_as(
+            &token,
+            Method::POST,
+            "/api/notices",
+            Some(json!({ "title": "公告 2", "content": "内容" })),
+        )
+        .await,
+    );
+    // 退出登录不记录审计日志
+    assert_ok(
+        app.request_as(&token, Method::POST, "/api/admins/logout", None)
+            .await,
+    );
+
+    let logs = assert_ok(
+        app.get(&format!(
+            "/api/audit_logs?page=1&size=10&admin_id={}",
+            other.id
+        ))
+        .await,
+    );
+    let logs = assert_page(&logs, 1, 1);
+    assert_eq!(logs[0]["after"][0]["title"], "公告 2");
+    let logs = assert_ok(app.get("/api/audit_logs?page=1&size=10&method=post").await);
+    assert_page(&logs, 2, 2);
+}
+
+#[actix_web::test]
+async fn role_rule_changes_are_audited() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role_id = app.create_role("运营").await;
+    let goods_rule_id = find_rule_id(&rules, "getGoodsList").unwrap();
+    let notice_rule_id = find_rule_id(&rules, "getNoticeList").unwrap();
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{role_id}/set_rules"),
+            json!({ "rule_ids": [goods_rule_id] }),
+        )
+        .await,
+    );
+    assert_ok(
+        app.post(
+            &format!("/api/roles/{role_id}/rules/{notice_rule_id}"),
+            json!({}),
+        )
+        .await,
+    );
+    assert_ok(
+        app.delete(&format!("/api/roles/{role_id}/rules/{goods_rule_id}"))
+            .await,
+    );
+
+    let logs = assert_ok(app.get("/api/audit_logs?page=1&size=10&target=roles").await);
+    let logs = assert_page(&logs, 4, 4);
+    // 按时间倒序：撤销、授予、设置、新建
+    let revoke = &logs[0];
+    assert_eq!(revoke["method"], "DELETE");
+    assert_eq!(
+        revoke["before"][0]["rule_ids"],
+        json!([goods_rule_id, notice_rule_id])
+    );
+    assert_eq!(revoke["after"][0]["rule_ids"], json!([notice_rule_id]));
+    let grant = &logs[1];
+    assert_eq!(grant["method"], "POST");
+    assert_eq!(grant["before"][0]["rule_ids"], json!([goods_rule_id]));
+    assert_eq!(
+        grant["after"][0]["rule_ids"],
+        json!([goods_rule_id, notice_rule_id])
+    );
+    let set_rules = &logs[2];
+    assert_eq!(set_rules["route"], "/api/roles/{id}/set_rules");
+    assert_eq!(set_rules["before"][0]["rule_ids"], json!([]));
+    assert_eq!(set_rules["after"][0]["rule_ids"], json!([goods_rule_id]));
+}
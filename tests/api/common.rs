@@ -0,0 +1,343 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use actix_http::Request;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    http::{Method, StatusCode},
+    test::{self, TestRequest},
+    Error,
+};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, Database, DatabaseConnection};
+use serde_json::{json, Value};
+use shop_demo_be::{
+    app::{build_app, AppState},
+    config::{AppConfig, DatabaseConfig, LoginGuardConfig, TokenConfig, UploadConfig},
+    entities::{admins, sea_orm_active_enums::Status},
+    utils::{
+        mailer::{FileMailer, Mail, MailError, Mailer},
+        password::hash_password,
+    },
+};
+use tempfile::TempDir;
+
+/// 测试数据库中预置的超级管理员
+pub const ADMIN_USERNAME: &str = "root";
+pub const ADMIN_PASSWORD: &str = "secret123";
+
+/// 使用临时 SQLite 数据库与上传目录构建的应用
+///
+/// 每个测试单独创建，测试结束后删除临时目录
+pub struct TestApp<S> {
+    service: S,
+    /// 超级管理员的访问令牌，`get`、`post` 等方法会自动带上
+    pub token: String,
+    pub db: DatabaseConnection,
+    pub dir: TempDir,
+}
+
+pub async fn spawn_app(
+) -> TestApp<impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>> {
+    spawn_app_with_mailer(None).await
+}
+
+/// 使用指定的邮件发送器构建应用，为 `None` 时邮件写入临时目录下的 `outbox`
+pub async fn spawn_app_with_mailer(
+    mailer: Option<Arc<dyn Mailer>>,
+) -> TestApp<impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>> {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let config = AppConfig {
+        database: DatabaseConfig {
+            url: format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display()),
+            ..Default::default()
+        },
+        upload: UploadConfig {
+            dir: dir.path().join("upload"),
+        },
+        token: TokenConfig {
+            admin_secret: String::from("test-admin-secret"),
+            user_secret: String::from("test-user-secret"),
+            ..Default::default()
+        },
+        // 登录失败后不退避，以免影响后续的登录，连续失败仍会被锁定
+        login_guard: LoginGuardConfig {
+            backoff_base_secs: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    std::fs::create_dir_all(&config.upload.dir).unwrap();
+
+    let db = Database::connect(config.database.connect_options())
+        .await
+        .expect("failed to connect test database");
+    Migrator::up(&db, None).await.expect("failed to migrate");
+    create_admin(&db, ADMIN_USERNAME, ADMIN_PASSWORD, None, 1).await;
+
+    let mailer = mailer.unwrap_or_else(|| Arc::new(FileMailer::new(dir.path().join("outbox"))));
+    let state = AppState::new(&config, db.clone(), mailer).unwrap();
+    let service = test::init_service(build_app(&state)).await;
+
+    let mut app = TestApp {
+        service,
+        token: String::new(),
+        db,
+        dir,
+    };
+    app.token = app.login(ADMIN_USERNAME, ADMIN_PASSWORD).await;
+    app
+}
+
+/// 回滚到名为 `name` 的迁移执行之前，用于构造升级前的数据
+pub async fn migrate_down_before(db: &DatabaseConnection, name: &str) {
+    let migrations = Migrator::migrations();
+    let index = migrations
+        .iter()
+        .position(|migration| migration.name() == name)
+        .unwrap_or_else(|| panic!("migration {name} not found"));
+    let steps = (migrations.len() - index) as u32;
+    Migrator::down(db, Some(steps)).await.unwrap();
+}
+
+/// 总是发送失败的邮件发送器
+pub struct FailingMailer;
+
+#[async_trait::async_trait]
+impl Mailer for FailingMailer {
+    async fn send(&self, _mail: Mail) -> Result<(), MailError> {
+        Err(MailError::Transport(String::from("connection refused")))
+    }
+}
+
+/// 直接写入数据库创建管理员，`is_super` 为 1 时是超级管理员
+pub async fn create_admin(
+    db: &DatabaseConnection,
+    username: &str,
+    password: &str,
+    role_id: Option<i32>,
+    is_super: i32,
+) -> admins::Model {
+    admins::ActiveModel {
+        username: Set(username.to_string()),
+        password: Set(hash_password(password).unwrap()),
+        role_id: Set(role_id),
+        status: Set(Status::Enabled),
+        avatar: Set(None),
+        is_super: Set(is_super),
+        email: Set(None),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+impl<S, B> TestApp<S>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    /// 发送请求并返回状态码与响应体，响应体不是 JSON 时作为字符串返回
+    pub async fn send(&self, req: TestRequest) -> (StatusCode, Value) {
+        let req = req
+            .peer_addr("127.0.0.1:40000".parse().unwrap())
+            .to_request();
+        let res = match self.service.call(req).await {
+            Ok(res) => res.map_into_boxed_body(),
+            Err(err) => {
+                let res = err.as_response_error().error_response();
+                let (req, _) = test::TestRequest::default().to_http_parts();
+                ServiceResponse::new(req, res)
+            }
+        };
+        let status = res.status();
+        let body = test::read_body(res).await;
+        let body = serde_json::from_slice(&body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+        (status, body)
+    }
+
+    /// 以超级管理员身份发送请求
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        self.request_as(&self.token, method, uri, body).await
+    }
+
+    /// 使用指定的访问令牌发送请求
+    pub async fn request_as(
+        &self,
+        token: &str,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut req = TestRequest::default()
+            .method(method)
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {token}")));
+        if let Some(body) = body {
+            req = req.set_json(body);
+        }
+        self.send(req).await
+    }
+
+    pub async fn get(&self, uri: &str) -> (StatusCode, Value) {
+        self.request(Method::GET, uri, None).await
+    }
+
+    pub async fn post(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::POST, uri, Some(body)).await
+    }
+
+    pub async fn put(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::PUT, uri, Some(body)).await
+    }
+
+    pub async fn patch(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::PATCH, uri, Some(body)).await
+    }
+
+    pub async fn delete(&self, uri: &str) -> (StatusCode, Value) {
+        self.request(Method::DELETE, uri, None).await
+    }
+
+    pub async fn delete_json(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::DELETE, uri, Some(body)).await
+    }
+
+    /// 初始化规则表，返回的规则树可用于查找规则 ID
+    pub async fn init_rules(&self) -> Value {
+        let (status, body) = self.request(Method::POST, "/api/rules/init", None).await;
+        assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+        assert_eq!(body["code"], 200);
+        assert_eq!(body["msg"], "规则已初始化");
+        assert_eq!(body["data"], Value::Null);
+        assert_ok(self.get("/api/rules").await)["list"].clone()
+    }
+
+    /// 创建启用的角色，返回角色 ID
+    pub async fn create_role(&self, name: &str) -> i64 {
+        let body = json!({ "name": name, "desc": name, "status": 1 });
+        assert_ok(self.post("/api/roles", body).await)["id"]
+            .as_i64()
+            .unwrap()
+    }
+
+    /// 创建启用的会员等级，返回会员等级 ID
+    pub async fn create_user_level(&self, name: &str, level: i64) -> i64 {
+        let body = json!({
+            "name": name,
+            "level": level,
+            "status": 1,
+            "discount": 90,
+            "max_price": 1000,
+            "max_time": 10,
+        });
+        assert_ok(self.post("/api/user_levels", body).await)["id"]
+            .as_i64()
+            .unwrap()
+    }
+
+    /// 创建图库，返回图库 ID
+    pub async fn create_image_class(&self, name: &str) -> i64 {
+        let body = json!({ "name": name, "order": 1 });
+        assert_ok(self.post("/api/image_classes", body).await)["id"]
+            .as_i64()
+            .unwrap()
+    }
+
+    /// 管理员登录并返回访问令牌
+    pub async fn login(&self, username: &str, password: &str) -> String {
+        let (status, body) = self
+            .send(
+                TestRequest::post()
+                    .uri("/api/admins/login")
+                    .set_json(json!({ "username": username, "password": password })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "login failed: {body}");
+        body["data"]["token"].as_str().unwrap().to_string()
+    }
+}
+
+/// 断言响应为成功的 `ResponseT`，返回其中的 `data`
+#[track_caller]
+pub fn assert_ok((status, body): (StatusCode, Value)) -> Value {
+    assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+    assert_eq!(body["code"], 200, "unexpected response: {body}");
+    assert_eq!(body["msg"], "ok", "unexpected response: {body}");
+    assert!(body.get("error").is_none(), "unexpected response: {body}");
+    body["data"].clone()
+}
+
+/// 断言响应为失败的 `ResponseT`，`code` 与 HTTP 状态码一致，返回其中的 `msg`
+#[track_caller]
+pub fn assert_error(
+    (status, body): (StatusCode, Value),
+    expected: StatusCode,
+    error: &str,
+) -> String {
+    assert_eq!(status, expected, "unexpected response: {body}");
+    assert_eq!(
+        body["code"],
+        expected.as_u16(),
+        "unexpected response: {body}"
+    );
+    assert_eq!(body["error"], error, "unexpected response: {body}");
+    body["msg"].as_str().unwrap().to_string()
+}
+
+/// 断言分页列表的总数与本页条数，返回本页的数据
+#[track_caller]
+pub fn assert_page(data: &Value, total_count: u64, len: usize) -> Vec<Value> {
+    assert_eq!(data["total_count"], total_count, "unexpected page: {data}");
+    let list = data["list"].as_array().unwrap();
+    assert_eq!(list.len(), len, "unexpected page: {data}");
+    list.clone()
+}
+
+/// 在规则树中按接口别名查找规则 ID
+pub fn find_rule_id(rules: &Value, condition: &str) -> Option<i64> {
+    rules.as_array()?.iter().find_map(|rule| {
+        if rule["condition"] == condition {
+            rule["id"].as_i64()
+        } else {
+            find_rule_id(&rule["child"], condition)
+        }
+    })
+}
+
+/// 读取发件箱中唯一一封重置密码邮件里的令牌
+///
+/// 邮件在后台发送，最多等待 2 秒
+pub async fn read_reset_token(outbox: &Path) -> String {
+    for _ in 0..100 {
+        if let Some(token) = find_reset_token(outbox) {
+            return token;
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("no password reset mail in {outbox:?}");
+}
+
+fn find_reset_token(outbox: &Path) -> Option<String> {
+    let mut mails: Vec<_> = std::fs::read_dir(outbox)
+        .ok()?
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert!(mails.len() <= 1, "unexpected outbox: {mails:?}");
+    let content = std::fs::read_to_string(mails.pop()?)
+        .ok()
+        .filter(|content| content.contains("如非本人操作"))?;
+    let token = content
+        .lines()
+        .skip_while(|line| !line.contains("重置令牌为"))
+        .skip(1)
+        .find(|line| !line.trim().is_empty())?;
+    Some(token.trim().to_string())
+}
@@ -0,0 +1,147 @@
+use actix_web::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+fn coupon_body(name: &str) -> Value {
+    json!({
+        "name": name,
+        "type": 0,
+        "value": 10.0,
+        "total": 100,
+        "used": 0,
+        "min_price": 50.0,
+        "start_time": "2024-01-01T00:00:00Z",
+        "end_time": "2024-12-31T23:59:59Z",
+        "status": 1,
+        "order": 1,
+        "desc": "满 50 减 10",
+    })
+}
+
+#[actix_web::test]
+async fn coupon_crud_flow() {
+    let app = spawn_app().await;
+
+    let coupon = assert_ok(app.post("/api/coupons", coupon_body("新人券")).await);
+    assert_eq!(coupon["name"], "新人券");
+    assert_eq!(coupon["total"], 100);
+    // 创建与更新时间由 before_save 自动填充
+    let create_time = coupon["create_time"].as_str().unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(create_time).is_ok() && create_time.ends_with('Z'),
+        "{create_time}"
+    );
+    assert_eq!(coupon["update_time"], coupon["create_time"]);
+    let id = coupon["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/coupons/{id}"),
+            json!({ "used": 20, "end_time": "2025-07-01T07:59:59+08:00" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/coupons/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+
+    let list = assert_ok(app.get("/api/coupons?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["used"], 20);
+    assert_eq!(list[0]["value"], "10.00");
+    assert_eq!(list[0]["min_price"], "50.00");
+    // 带时区偏移的时间统一按 UTC 返回
+    assert_eq!(list[0]["end_time"], "2025-06-30T23:59:59Z");
+    assert_eq!(list[0]["start_time"], "2024-01-01T00:00:00Z");
+    assert_eq!(list[0]["status"], 0);
+    let detail = assert_ok(app.get(&format!("/api/coupons/{id}")).await);
+    assert_eq!(detail, list[0]);
+
+    assert_ok(app.delete(&format!("/api/coupons/{id}")).await);
+    let list = assert_ok(app.get("/api/coupons?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn invalid_coupon_is_rejected() {
+    let app = spawn_app().await;
+
+    let mut body = coupon_body("新人券");
+    body["start_time"] = json!("2024-01-01 00:00:00");
+    let msg = assert_error(
+        app.post("/api/coupons", body).await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert!(msg.starts_with("请求体格式错误"), "{msg}");
+
+    let mut body = coupon_body("新人券");
+    body["value"] = json!(0);
+    let (status, body) = app.post("/api/coupons", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["value"], json!(["必须大于 0"]));
+    let mut body = coupon_body("新人券");
+    body["value"] = json!("100000000");
+    let (status, body) = app.post("/api/coupons", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["value"], json!(["必须小于 99999999.99"]));
+
+    // 整体校验：时间范围与使用量
+    let mut body = coupon_body("新人券");
+    body["end_time"] = json!("2023-12-31T00:00:00Z");
+    body["used"] = json!(101);
+    let (status, body) = app.post("/api/coupons", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert!(body["data"]["__all__"].is_array(), "{body}");
+}
+
+#[actix_web::test]
+async fn partial_update_is_validated_against_stored_coupon() {
+    let app = spawn_app().await;
+    let coupon = assert_ok(app.post("/api/coupons", coupon_body("新人券")).await);
+    let id = coupon["id"].as_i64().unwrap();
+
+    let msg = assert_error(
+        app.put(
+            &format!("/api/coupons/{id}"),
+            json!({ "end_time": "2023-01-01T00:00:00Z" }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "结束时间不能早于开始时间");
+    let msg = assert_error(
+        app.put(&format!("/api/coupons/{id}"), json!({ "used": 101 }))
+            .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "使用量不能超过发行量");
+}
+
+#[actix_web::test]
+async fn missing_coupon_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/coupons/999", json!({ "name": "优惠券" }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "优惠券不存在");
+    assert_error(
+        app.patch("/api/coupons/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
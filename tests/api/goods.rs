@@ -0,0 +1,239 @@
+use actix_web::{http::StatusCode, test::TestRequest};
+use serde_json::{json, Value};
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+fn good_body(title: &str, category_id: i64, stock: i64) -> Value {
+    json!({
+        "title": title,
+        "category_id": category_id,
+        "cover": "",
+        "desc": "商品描述",
+        "unit": "件",
+        "stock": stock,
+        "min_stock": 10,
+        "status": 1,
+        "stock_display": 1,
+        "min_price": "99.90",
+        "min_ori_price": "129",
+    })
+}
+
+#[actix_web::test]
+async fn good_crud_flow() {
+    let app = spawn_app().await;
+
+    let good = assert_ok(app.post("/api/goods", good_body("T 恤", 1, 100)).await);
+    assert_eq!(good["title"], "T 恤");
+    assert_eq!(good["min_price"], "99.90");
+    assert_eq!(good["is_check"], 0);
+    let id = good["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/goods/{id}"),
+            json!({ "title": "纯棉 T 恤", "stock": 50 }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    let detail = assert_ok(app.get(&format!("/api/goods/{id}")).await);
+    assert_eq!(detail["title"], "纯棉 T 恤");
+    assert_eq!(detail["stock"], 50);
+    assert_eq!(detail["unit"], "件");
+    // 金额统一序列化为两位小数的字符串
+    assert_eq!(detail["min_price"], "99.90");
+    assert_eq!(detail["min_ori_price"], "129.00");
+    assert_eq!(detail["delete_time"], Value::Null);
+
+    assert_ok(
+        app.patch(&format!("/api/goods/{id}/check"), json!({ "is_check": 1 }))
+            .await,
+    );
+    let detail = assert_ok(app.get(&format!("/api/goods/{id}")).await);
+    assert_eq!(detail["is_check"], 1);
+
+    assert_ok(app.delete_json("/api/goods", json!({ "ids": [id] })).await);
+    let msg = assert_error(
+        app.get(&format!("/api/goods/{id}")).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "商品不存在");
+}
+
+#[actix_web::test]
+async fn good_list_filters() {
+    let app = spawn_app().await;
+    assert_ok(app.post("/api/goods", good_body("红色 T 恤", 1, 100)).await);
+    assert_ok(app.post("/api/goods", good_body("蓝色 T 恤", 1, 100)).await);
+    assert_ok(app.post("/api/goods", good_body("红色外套", 2, 100)).await);
+
+    let list = assert_ok(app.get("/api/goods?page=1&size=10").await);
+    assert_page(&list, 3, 3);
+    let list = assert_ok(app.get("/api/goods?page=1&size=10&category_id=1").await);
+    assert_page(&list, 2, 2);
+    let list = assert_ok(
+        app.get("/api/goods?page=1&size=10&title=%E7%BA%A2%E8%89%B2")
+            .await,
+    );
+    assert_page(&list, 2, 2);
+    let list = assert_ok(
+        app.get("/api/goods?page=1&size=10&title=%E7%BA%A2%E8%89%B2&category_id=2")
+            .await,
+    );
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["title"], "红色外套");
+}
+
+#[actix_web::test]
+async fn batch_operations() {
+    let app = spawn_app().await;
+    let mut ids = vec![];
+    for title in ["商品 1", "商品 2", "商品 3"] {
+        let good = assert_ok(app.post("/api/goods", good_body(title, 1, 100)).await);
+        ids.push(good["id"].as_i64().unwrap());
+    }
+    let detail = |id: i64| format!("/api/goods/{id}");
+
+    assert_ok(
+        app.patch(
+            "/api/goods/status/batch",
+            json!({ "ids": &ids[..2], "status": 0 }),
+        )
+        .await,
+    );
+    assert_eq!(assert_ok(app.get(&detail(ids[0])).await)["status"], 0);
+    assert_eq!(assert_ok(app.get(&detail(ids[1])).await)["status"], 0);
+    assert_eq!(assert_ok(app.get(&detail(ids[2])).await)["status"], 1);
+
+    // 软删除只设置删除时间，恢复后清空
+    assert_ok(
+        app.patch("/api/goods/delete/batch", json!({ "ids": [ids[0]] }))
+            .await,
+    );
+    assert!(assert_ok(app.get(&detail(ids[0])).await)["delete_time"].is_string());
+    assert_ok(
+        app.patch("/api/goods/restore/batch", json!({ "ids": [ids[0]] }))
+            .await,
+    );
+    assert_eq!(
+        assert_ok(app.get(&detail(ids[0])).await)["delete_time"],
+        Value::Null
+    );
+
+    assert_ok(
+        app.delete_json("/api/goods", json!({ "ids": &ids[1..] }))
+            .await,
+    );
+    let list = assert_ok(app.get("/api/goods?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["id"], ids[0]);
+
+    for uri in [
+        "/api/goods/delete/batch",
+        "/api/goods/restore/batch",
+        "/api/goods/status/batch",
+    ] {
+        let (status, body) = app.patch(uri, json!({ "ids": [], "status": 1 })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+        assert_eq!(body["data"]["ids"], json!(["不能为空"]));
+    }
+}
+
+#[actix_web::test]
+async fn stock_alert_metric_ignores_deleted_goods() {
+    let app = spawn_app().await;
+    let good = assert_ok(app.post("/api/goods", good_body("商品 1", 1, 5)).await);
+    assert_ok(app.post("/api/goods", good_body("商品 2", 1, 5)).await);
+    assert_ok(app.post("/api/goods", good_body("商品 3", 1, 100)).await);
+    assert_ok(
+        app.patch("/api/goods/delete/batch", json!({ "ids": [good["id"]] }))
+            .await,
+    );
+
+    let (_, metrics) = app.send(TestRequest::get().uri("/metrics")).await;
+    let metrics = metrics.as_str().unwrap();
+    assert!(metrics.contains("goods_below_min_stock 1"), "{metrics}");
+}
+
+#[actix_web::test]
+async fn missing_good_returns_404() {
+    let app = spawn_app().await;
+
+    assert_error(
+        app.get("/api/goods/999").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.put("/api/goods/999", json!({ "title": "商品" })).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.patch("/api/goods/999/check", json!({ "is_check": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn invalid_price_is_rejected() {
+    let app = spawn_app().await;
+    let mut body = good_body("商品", 1, 100);
+    body["min_price"] = json!("9.999");
+    body["min_ori_price"] = json!("-1");
+
+    let (status, body) = app.post("/api/goods", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    let expected = json!(["价格必须为非负数，最多两位小数"]);
+    assert_eq!(body["data"]["min_price"], expected);
+    assert_eq!(body["data"]["min_ori_price"], expected);
+}
+
+#[actix_web::test]
+async fn price_must_fit_the_column() {
+    let app = spawn_app().await;
+    for price in [json!("99999999.99"), json!(100000000), json!("1e8")] {
+        let mut body = good_body("商品", 1, 100);
+        body["min_price"] = price.clone();
+
+        let (status, body) = app.post("/api/goods", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{price}: {body}");
+        assert_eq!(body["data"]["min_price"], json!(["必须小于 99999999.99"]));
+    }
+
+    let mut body = good_body("商品", 1, 100);
+    body["min_price"] = json!("99999999.98");
+    let good = assert_ok(app.post("/api/goods", body).await);
+    assert_eq!(good["min_price"], "99999999.98");
+}
+
+#[actix_web::test]
+async fn unknown_enum_value_is_rejected() {
+    let app = spawn_app().await;
+    let good = assert_ok(app.post("/api/goods", good_body("商品", 1, 100)).await);
+    let id = good["id"].as_i64().unwrap();
+
+    let msg = assert_error(
+        app.patch(&format!("/api/goods/{id}/check"), json!({ "is_check": 3 }))
+            .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert!(msg.starts_with("请求体格式错误"), "{msg}");
+
+    let mut body = good_body("商品", 1, 100);
+    body["stock_display"] = json!(2);
+    assert_error(
+        app.post("/api/goods", body).await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+
+    let detail = assert_ok(app.get(&format!("/api/goods/{id}")).await);
+    assert_eq!(detail["is_check"], 0);
+}
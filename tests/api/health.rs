@@ -0,0 +1,46 @@
+use actix_web::{http::StatusCode, test::TestRequest};
+
+use crate::common::{assert_ok, spawn_app};
+
+#[actix_web::test]
+async fn healthz_is_public() {
+    let app = spawn_app().await;
+
+    let data = assert_ok(app.send(TestRequest::get().uri("/healthz")).await);
+    assert_eq!(data["status"], "ok");
+}
+
+#[actix_web::test]
+async fn readyz_reports_every_check() {
+    let app = spawn_app().await;
+
+    let data = assert_ok(app.send(TestRequest::get().uri("/readyz")).await);
+    assert_eq!(data["status"], "ok");
+    for check in ["database", "migrations", "upload_dir"] {
+        assert_eq!(data["checks"][check]["status"], "ok", "{check}: {data}");
+        assert!(data["checks"][check]["error"].is_null());
+    }
+}
+
+#[actix_web::test]
+async fn metrics_use_route_templates() {
+    let app = spawn_app().await;
+    app.get("/api/notices/999").await;
+
+    let (status, body) = app.send(TestRequest::get().uri("/metrics")).await;
+    assert_eq!(status, StatusCode::OK);
+    let body = body.as_str().unwrap();
+    assert!(
+        body.contains(r#"route="/api/admins/login",status="200""#),
+        "{body}"
+    );
+    assert!(body.contains("goods_below_min_stock 0"), "{body}");
+}
+
+#[actix_web::test]
+async fn unknown_route_returns_404() {
+    let app = spawn_app().await;
+
+    let (status, _) = app.send(TestRequest::get().uri("/not-found")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,68 @@
+use actix_web::http::StatusCode;
+use serde_json::json;
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+#[actix_web::test]
+async fn image_class_crud_flow() {
+    let app = spawn_app().await;
+
+    let image_class = assert_ok(
+        app.post(
+            "/api/image_classes",
+            json!({ "name": "商品图", "order": 1 }),
+        )
+        .await,
+    );
+    assert_eq!(image_class["name"], "商品图");
+    let id = image_class["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/image_classes/{id}"),
+            json!({ "name": "轮播图" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    let list = assert_ok(app.get("/api/image_classes?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["name"], "轮播图");
+    assert_eq!(list[0]["order"], 1);
+
+    let images = assert_ok(
+        app.get(&format!("/api/image_classes/{id}/image?page=1&size=10"))
+            .await,
+    );
+    assert_page(&images, 0, 0);
+
+    assert_ok(app.delete(&format!("/api/image_classes/{id}")).await);
+    let list = assert_ok(app.get("/api/image_classes?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn missing_image_class_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/image_classes/999", json!({ "name": "图库" }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "图库不存在");
+}
+
+#[actix_web::test]
+async fn invalid_path_id_is_rejected() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/image_classes/abc", json!({ "name": "图库" }))
+            .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert!(msg.starts_with("路径参数错误"), "{msg}");
+}
@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use actix_web::{http::StatusCode, test::TestRequest};
+use serde_json::json;
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+const BOUNDARY: &str = "shop-demo-boundary";
+
+/// 构建 multipart 请求，`parts` 为字段名、文件名与内容，文件名为空时是普通字段
+fn multipart_req(token: &str, parts: &[(&str, Option<&str>, &[u8])]) -> TestRequest {
+    let mut body = Vec::new();
+    for (name, filename, content) in parts {
+        let disposition = match filename {
+            Some(filename) => format!(
+                "form-data; name=\"{name}\"; filename=\"{filename}\"\r\nContent-Type: image/png"
+            ),
+            None => format!("form-data; name=\"{name}\""),
+        };
+        body.extend_from_slice(
+            format!("--{BOUNDARY}\r\nContent-Disposition: {disposition}\r\n\r\n").as_bytes(),
+        );
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+
+    TestRequest::post()
+        .uri("/api/images/upload")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(body)
+}
+
+/// 构建包含 `imageClassId` 与 `img` 两个字段的 multipart 请求
+fn upload_req(token: &str, image_class_id: i64, filename: &str, content: &[u8]) -> TestRequest {
+    let image_class_id = image_class_id.to_string();
+    multipart_req(
+        token,
+        &[
+            ("imageClassId", None, image_class_id.as_bytes()),
+            ("img", Some(filename), content),
+        ],
+    )
+}
+
+/// 上传目录中的文件数
+fn uploaded_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir).unwrap().count()
+}
+
+#[actix_web::test]
+async fn upload_update_and_delete_images() {
+    let app = spawn_app().await;
+    let image_class = assert_ok(
+        app.post(
+            "/api/image_classes",
+            json!({ "name": "商品图", "order": 1 }),
+        )
+        .await,
+    );
+    let class_id = image_class["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.send(upload_req(&app.token, class_id, "a.png", b"first image"))
+            .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.send(upload_req(&app.token, class_id, "b.png", b"second image"))
+            .await,
+    );
+
+    let uri = format!("/api/image_classes/{class_id}/image?page=1&size=10");
+    let images = assert_ok(app.get(&uri).await);
+    let images = assert_page(&images, 2, 2);
+    // 以随机文件名保存在上传目录中，原文件名作为图片名称
+    assert_eq!(images[0]["name"], "a.png");
+    let saved = Path::new(images[0]["path"].as_str().unwrap());
+    assert_eq!(saved.parent().unwrap(), app.dir.path().join("upload"));
+    assert_ne!(saved.file_name().unwrap(), "a.png");
+    assert_eq!(saved.extension().unwrap(), "png");
+    assert_eq!(std::fs::read(saved).unwrap(), b"first image");
+    let ids: Vec<_> = images.iter().map(|image| image["id"].clone()).collect();
+
+    let id = &ids[0];
+    assert_ok(
+        app.put(&format!("/api/images/{id}"), json!({ "name": "封面" }))
+            .await,
+    );
+    let images = assert_ok(app.get(&uri).await);
+    assert_eq!(images["list"][0]["name"], "封面");
+
+    assert_ok(
+        app.delete_json("/api/images/delete_all", json!({ "ids": ids }))
+            .await,
+    );
+    let images = assert_ok(app.get(&uri).await);
+    assert_page(&images, 0, 0);
+    // 删除图片时一并删除文件
+    assert!(!saved.exists());
+    assert_eq!(uploaded_files(&app.dir.path().join("upload")), 0);
+}
+
+#[actix_web::test]
+async fn upload_counts_bytes_in_metrics() {
+    let app = spawn_app().await;
+    let class_id = app.create_image_class("商品图").await;
+    assert_ok(
+        app.send(upload_req(&app.token, class_id, "a.png", b"0123456789"))
+            .await,
+    );
+
+    let (_, metrics) = app.send(TestRequest::get().uri("/metrics")).await;
+    let metrics = metrics.as_str().unwrap();
+    assert!(metrics.contains("upload_files_total 1"), "{metrics}");
+    assert!(metrics.contains("upload_bytes_total 10"), "{metrics}");
+}
+
+#[actix_web::test]
+async fn invalid_upload_is_rejected() {
+    let app = spawn_app().await;
+
+    let req = upload_req(&app.token, 1, "a.png", b"image").set_payload(format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"imageClassId\"\r\n\r\n\
+         abc\r\n\
+         --{BOUNDARY}--\r\n"
+    ));
+    let msg = assert_error(
+        app.send(req).await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "imageClassId 必须为整数");
+}
+
+#[actix_web::test]
+async fn upload_requires_exactly_one_image() {
+    let app = spawn_app().await;
+    let class_id = app.create_image_class("商品图").await;
+    let class_id = class_id.to_string();
+    let upload_dir = app.dir.path().join("upload");
+
+    let (status, body) = app
+        .send(multipart_req(
+            &app.token,
+            &[("imageClassId", None, class_id.as_bytes())],
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["img"], json!(["缺少图片"]));
+
+    let msg = assert_error(
+        app.send(multipart_req(
+            &app.token,
+            &[
+                ("imageClassId", None, class_id.as_bytes()),
+                ("img", Some("a.png"), b"first"),
+                ("img", Some("b.png"), b"second"),
+            ],
+        ))
+        .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "只能上传一张图片");
+    assert_eq!(uploaded_files(&upload_dir), 0);
+
+    // 图库 ID 在文件之后且不存在时，已写入的文件会被删除
+    let (status, body) = app
+        .send(multipart_req(
+            &app.token,
+            &[
+                ("img", Some("a.png"), b"image"),
+                ("imageClassId", None, b"999"),
+            ],
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(uploaded_files(&upload_dir), 0);
+
+    let uri = format!("/api/image_classes/{class_id}/image?page=1&size=10");
+    let images = assert_ok(app.get(&uri).await);
+    assert_page(&images, 0, 0);
+}
+
+#[actix_web::test]
+async fn upload_stays_in_upload_dir() {
+    let app = spawn_app().await;
+    let class_id = app.create_image_class("商品图").await;
+    let upload_dir = app.dir.path().join("upload");
+    let outside = app.dir.path().join("escaped.txt");
+
+    for filename in ["../escaped.txt".to_string(), outside.display().to_string()] {
+        assert_ok(
+            app.send(upload_req(&app.token, class_id, &filename, b"image"))
+                .await,
+        );
+    }
+    assert!(!outside.exists());
+    let uri = format!("/api/image_classes/{class_id}/image?page=1&size=10");
+    let images = assert_ok(app.get(&uri).await);
+    for image in assert_page(&images, 2, 2) {
+        assert_eq!(image["name"], "escaped.txt");
+        let saved = Path::new(image["path"].as_str().unwrap());
+        assert_eq!(saved.parent().unwrap(), upload_dir);
+        assert!(saved.exists());
+    }
+
+    let msg = assert_error(
+        app.send(upload_req(&app.token, class_id, "..", b"image"))
+            .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "文件名不合法");
+}
+
+#[actix_web::test]
+async fn images_must_belong_to_existing_class() {
+    let app = spawn_app().await;
+
+    let (status, body) = app
+        .send(upload_req(&app.token, 999, "a.png", b"image"))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["imageClassId"], json!(["图库不存在"]));
+    // 被拒绝的请求不会留下文件
+    assert_eq!(uploaded_files(&app.dir.path().join("upload")), 0);
+
+    // 图库中仍有图片时不能删除
+    let class_id = app.create_image_class("商品图").await;
+    assert_ok(
+        app.send(upload_req(&app.token, class_id, "a.png", b"image"))
+            .await,
+    );
+    let msg = assert_error(
+        app.delete(&format!("/api/image_classes/{class_id}")).await,
+        StatusCode::CONFLICT,
+        "CONFLICT",
+    );
+    assert_eq!(msg, "图库中仍有图片，请先删除图片");
+}
+
+#[actix_web::test]
+async fn missing_image_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/images/999", json!({ "name": "图片" })).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "图片不存在");
+}
@@ -0,0 +1,21 @@
+//! 端到端的 HTTP 集成测试
+//!
+//! 每个测试通过 [`common::spawn_app`] 构建与 `serve` 相同的应用，
+//! 使用临时的 SQLite 数据库，不依赖外部服务
+
+mod common;
+
+mod admins;
+mod audit_logs;
+mod coupons;
+mod goods;
+mod health;
+mod image_classes;
+mod images;
+mod notices;
+mod roles;
+mod rules;
+mod shop;
+mod skus;
+mod user_levels;
+mod users;
@@ -0,0 +1,165 @@
+use actix_web::http::StatusCode;
+use serde_json::json;
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+#[actix_web::test]
+async fn notice_crud_flow() {
+    let app = spawn_app().await;
+
+    let notice = assert_ok(
+        app.post(
+            "/api/notices",
+            json!({ "title": "公告", "content": "内容" }),
+        )
+        .await,
+    );
+    assert_eq!(notice["title"], "公告");
+    assert_eq!(notice["content"], "内容");
+    let id = notice["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/notices/{id}"),
+            json!({ "title": "新公告", "content": "新内容" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    let notice = assert_ok(app.get(&format!("/api/notices/{id}")).await);
+    assert_eq!(notice["title"], "新公告");
+
+    let list = assert_ok(app.get("/api/notices?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["title"], "新公告");
+    assert_eq!(list[0]["content"], "新内容");
+
+    assert_ok(app.delete(&format!("/api/notices/{id}")).await);
+    let list = assert_ok(app.get("/api/notices?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn notice_pagination_edges() {
+    let app = spawn_app().await;
+    for i in 0..5 {
+        assert_ok(
+            app.post(
+                "/api/notices",
+                json!({ "title": format!("公告 {i}"), "content": "内容" }),
+            )
+            .await,
+        );
+    }
+
+    let page = assert_ok(app.get("/api/notices?page=1&size=2").await);
+    assert_page(&page, 5, 2);
+    // 最后一页不满
+    let page = assert_ok(app.get("/api/notices?page=3&size=2").await);
+    assert_page(&page, 5, 1);
+    // 超出最后一页返回空列表
+    let page = assert_ok(app.get("/api/notices?page=4&size=2").await);
+    assert_page(&page, 5, 0);
+    let page = assert_ok(app.get("/api/notices?page=1&size=100").await);
+    assert_page(&page, 5, 5);
+
+    let (status, body) = app.get("/api/notices?page=1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+}
+
+#[actix_web::test]
+async fn list_params_are_bounded() {
+    let app = spawn_app().await;
+
+    for (query, field) in [
+        ("page=0&size=10", "page"),
+        ("page=1000001&size=10", "page"),
+        ("page=18446744073709551615&size=10", "page"),
+        ("page=1&size=0", "size"),
+        ("page=1&size=101", "size"),
+        ("page=1&size=10&sort=content", "sort"),
+        ("page=1&size=10&sort=-create_time&cursor=1", "cursor"),
+    ] {
+        let (status, body) = app.get(&format!("/api/notices?{query}")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}: {body}");
+        assert!(body["data"][field].is_array(), "{query}: {body}");
+    }
+}
+
+#[actix_web::test]
+async fn notice_list_sorting_and_cursor() {
+    let app = spawn_app().await;
+    let mut ids = vec![];
+    for i in 0..5 {
+        let notice = assert_ok(
+            app.post(
+                "/api/notices",
+                json!({ "title": format!("公告 {i}"), "content": "内容" }),
+            )
+            .await,
+        );
+        ids.push(notice["id"].as_i64().unwrap());
+    }
+
+    let page = assert_ok(app.get("/api/notices?size=2&sort=-id").await);
+    let list = assert_page(&page, 5, 2);
+    assert_eq!(list[0]["title"], "公告 4");
+    assert_eq!(list[1]["title"], "公告 3");
+    // 不查询总条数
+    let page = assert_ok(app.get("/api/notices?size=2&count=false").await);
+    assert!(page.get("total_count").is_none(), "{page}");
+
+    // 沿游标翻页直到最后一页
+    let mut seen = vec![];
+    let mut query = String::from("size=2&count=false");
+    loop {
+        let page = assert_ok(app.get(&format!("/api/notices?{query}")).await);
+        seen.extend(
+            page["list"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["id"].as_i64().unwrap()),
+        );
+        match page["next_cursor"].as_i64() {
+            Some(cursor) => query = format!("size=2&count=false&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(seen, ids);
+}
+
+#[actix_web::test]
+async fn update_missing_notice_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put(
+            "/api/notices/999",
+            json!({ "title": "公告", "content": "内容" }),
+        )
+        .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "公告不存在");
+    let msg = assert_error(
+        app.get("/api/notices/999").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "公告不存在");
+}
+
+#[actix_web::test]
+async fn invalid_notice_returns_field_errors() {
+    let app = spawn_app().await;
+
+    let (status, body) = app
+        .post("/api/notices", json!({ "title": "", "content": "内容" }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert!(body["data"]["title"].is_array(), "{body}");
+}
@@ -0,0 +1,300 @@
+use actix_web::http::{Method, StatusCode};
+use migration::{Migrator, MigratorTrait};
+use serde_json::{json, Value};
+
+use crate::common::{
+    assert_error, assert_ok, assert_page, create_admin, find_rule_id, migrate_down_before,
+    spawn_app,
+};
+
+fn rule_body(name: &str, rule_id: i64, condition: &str) -> Value {
+    json!({
+        "name": name,
+        "rule_id": rule_id,
+        "status": 1,
+        "front_path": "",
+        "condition": condition,
+        "menu": 0,
+        "order": 1,
+        "icon": "",
+        "method": "GET",
+    })
+}
+
+#[actix_web::test]
+async fn role_crud_flow() {
+    let app = spawn_app().await;
+
+    let role = assert_ok(
+        app.post(
+            "/api/roles",
+            json!({ "name": "运营", "desc": "运营人员", "status": 1 }),
+        )
+        .await,
+    );
+    assert_eq!(role["name"], "运营");
+    assert_eq!(role["desc"], "运营人员");
+    assert_eq!(role["status"], 1);
+    let id = role["id"].as_i64().unwrap();
+
+    // 只修改传入的字段
+    let data = assert_ok(
+        app.put(&format!("/api/roles/{id}"), json!({ "desc": "新描述" }))
+            .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+
+    let list = assert_ok(app.get("/api/roles?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["name"], "运营");
+    assert_eq!(list[0]["desc"], "新描述");
+    assert_eq!(list[0]["status"], 0);
+
+    assert_ok(app.delete(&format!("/api/roles/{id}")).await);
+    let list = assert_ok(app.get("/api/roles?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn set_rules_stores_rule_ids() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role = assert_ok(
+        app.post(
+            "/api/roles",
+            json!({ "name": "运营", "desc": "", "status": 1 }),
+        )
+        .await,
+    );
+    let id = role["id"].as_i64().unwrap();
+    let mut rule_ids = [
+        find_rule_id(&rules, "getGoodsList").unwrap(),
+        find_rule_id(&rules, "createGoods").unwrap(),
+    ];
+    // 列表中的规则 ID 按升序返回
+    rule_ids.sort_unstable();
+
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{id}/set_rules"),
+            json!({ "rule_ids": rule_ids }),
+        )
+        .await,
+    );
+    let list = assert_ok(app.get("/api/roles?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["rule_ids"], json!(rule_ids));
+    let role = assert_ok(app.get(&format!("/api/roles/{id}")).await);
+    assert_eq!(role["name"], "运营");
+    assert_eq!(role["rule_ids"], json!(rule_ids));
+
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{id}/set_rules"),
+            json!({ "rule_ids": [] }),
+        )
+        .await,
+    );
+    let list = assert_ok(app.get("/api/roles?page=1&size=10").await);
+    assert_eq!(list["list"][0]["rule_ids"], json!([]));
+}
+
+#[actix_web::test]
+async fn grant_and_revoke_single_rules() {
+    let app = spawn_app().await;
+    let role_id = app.create_role("运营").await;
+    let parent = assert_ok(app.post("/api/rules", rule_body("商品", 0, "")).await);
+    let parent_id = parent["id"].as_i64().unwrap();
+    let child = assert_ok(
+        app.post(
+            "/api/rules",
+            rule_body("商品列表", parent_id, "getGoodsList"),
+        )
+        .await,
+    );
+    let child_id = child["id"].as_i64().unwrap();
+    let uri = format!("/api/roles/{role_id}/rules");
+
+    // 上级规则未授予时，下级规则作为根节点
+    assert_ok(app.post(&format!("{uri}/{child_id}"), json!({})).await);
+    let data = assert_ok(app.get(&uri).await);
+    assert_eq!(data["rule_ids"], json!([child_id]));
+    assert_eq!(data["rules"][0]["id"], child_id);
+
+    // 重复授予不会报错
+    assert_ok(app.post(&format!("{uri}/{parent_id}"), json!({})).await);
+    assert_ok(app.post(&format!("{uri}/{parent_id}"), json!({})).await);
+    let data = assert_ok(app.get(&uri).await);
+    assert_eq!(data["rule_ids"], json!([parent_id, child_id]));
+    let tree = data["rules"].as_array().unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0]["id"], parent_id);
+    assert_eq!(tree[0]["child"][0]["id"], child_id);
+
+    assert_ok(app.delete(&format!("{uri}/{parent_id}")).await);
+    let data = assert_ok(app.get(&uri).await);
+    assert_eq!(data["rule_ids"], json!([child_id]));
+
+    // 删除规则时一并从角色中移除
+    assert_ok(app.delete(&format!("/api/rules/{child_id}")).await);
+    let data = assert_ok(app.get(&uri).await);
+    assert_eq!(data, json!({ "rule_ids": [], "rules": [] }));
+
+    let msg = assert_error(
+        app.post(&format!("{uri}/{child_id}"), json!({})).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "规则不存在");
+    let (status, body) = app
+        .patch(
+            &format!("/api/roles/{role_id}/set_rules"),
+            json!({ "rule_ids": [parent_id, child_id] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["rule_ids"], json!(["规则不存在"]));
+}
+
+#[actix_web::test]
+async fn single_rule_routes_require_their_own_rules() {
+    let app = spawn_app().await;
+    let rules = app.init_rules().await;
+    let role_id = app.create_role("运营").await;
+    let manager_role_id = app.create_role("角色管理员").await;
+    let set_rules_uri = format!("/api/roles/{manager_role_id}/set_rules");
+    let set_rules = |rule_ids: Vec<i64>| app.patch(&set_rules_uri, json!({ "rule_ids": rule_ids }));
+    create_admin(
+        &app.db,
+        "manager",
+        "manager123",
+        Some(manager_role_id as i32),
+        0,
+    )
+    .await;
+    let token = app.login("manager", "manager123").await;
+    let rule_id = find_rule_id(&rules, "getGoodsList").unwrap();
+    let uri = format!("/api/roles/{role_id}/rules/{rule_id}");
+
+    // 只拥有 setRoleRules 时不能单独授予或撤销
+    assert_ok(set_rules(vec![find_rule_id(&rules, "setRoleRules").unwrap()]).await);
+    for method in [Method::POST, Method::DELETE] {
+        assert_error(
+            app.request_as(&token, method, &uri, None).await,
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+        );
+    }
+
+    assert_ok(
+        set_rules(vec![
+            find_rule_id(&rules, "grantRoleRule").unwrap(),
+            find_rule_id(&rules, "revokeRoleRule").unwrap(),
+        ])
+        .await,
+    );
+    assert_ok(app.request_as(&token, Method::POST, &uri, None).await);
+    let data = assert_ok(app.get(&format!("/api/roles/{role_id}/rules")).await);
+    assert_eq!(data["rule_ids"], json!([rule_id]));
+    assert_ok(app.request_as(&token, Method::DELETE, &uri, None).await);
+    let data = assert_ok(app.get(&format!("/api/roles/{role_id}/rules")).await);
+    assert_eq!(data["rule_ids"], json!([]));
+}
+
+#[actix_web::test]
+async fn role_rule_grants_migration_keeps_existing_access() {
+    let app = spawn_app().await;
+    app.init_rules().await;
+
+    // 升级前只有 setRoleRules，拥有它的角色同样可以单独授予、撤销权限
+    migrate_down_before(&app.db, "role_rule_grants").await;
+    let rules = assert_ok(app.get("/api/rules").await)["list"].clone();
+    assert_eq!(find_rule_id(&rules, "grantRoleRule"), None);
+    let manager_role_id = app.create_role("角色管理员").await;
+    let other_role_id = app.create_role("运营").await;
+    let set_rules_id = find_rule_id(&rules, "setRoleRules").unwrap();
+    assert_ok(
+        app.patch(
+            &format!("/api/roles/{manager_role_id}/set_rules"),
+            json!({ "rule_ids": [set_rules_id] }),
+        )
+        .await,
+    );
+
+    Migrator::up(&app.db, None).await.unwrap();
+    let rules = assert_ok(app.get("/api/rules").await)["list"].clone();
+    let grant_id = find_rule_id(&rules, "grantRoleRule").unwrap();
+    let revoke_id = find_rule_id(&rules, "revokeRoleRule").unwrap();
+    let data = assert_ok(
+        app.get(&format!("/api/roles/{manager_role_id}/rules"))
+            .await,
+    );
+    assert_eq!(data["rule_ids"], json!([set_rules_id, grant_id, revoke_id]));
+    let data = assert_ok(app.get(&format!("/api/roles/{other_role_id}/rules")).await);
+    assert_eq!(data["rule_ids"], json!([]));
+}
+
+#[actix_web::test]
+async fn missing_role_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/roles/999", json!({ "name": "运营" })).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "角色不存在");
+    assert_error(
+        app.get("/api/roles/999").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.patch("/api/roles/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.patch("/api/roles/999/set_rules", json!({ "rule_ids": [1] }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.get("/api/roles/999/rules").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn invalid_role_returns_field_errors() {
+    let app = spawn_app().await;
+
+    let (status, body) = app
+        .post("/api/roles", json!({ "name": "", "desc": "", "status": 1 }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert!(body["data"]["name"].is_array(), "{body}");
+
+    // 枚举之外的状态值在反序列化时即被拒绝
+    let msg = assert_error(
+        app.post(
+            "/api/roles",
+            json!({ "name": "运营", "desc": "", "status": 2 }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert!(msg.contains("expected 0 or 1"), "{msg}");
+}
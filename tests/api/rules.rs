@@ -0,0 +1,168 @@
+use actix_web::http::{Method, StatusCode};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde_json::{json, Value};
+use shop_demo_be::entities::rules;
+
+use crate::common::{assert_error, assert_ok, find_rule_id, migrate_down_before, spawn_app};
+
+fn rule_body(name: &str, rule_id: i64, condition: &str, method: &str) -> Value {
+    json!({
+        "name": name,
+        "rule_id": rule_id,
+        "status": 1,
+        "front_path": "",
+        "condition": condition,
+        "menu": 0,
+        "order": 1,
+        "icon": "",
+        "method": method,
+    })
+}
+
+/// 规则树中的节点总数
+fn count_rules(rules: &Value) -> u64 {
+    rules
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| 1 + count_rules(&rule["child"]))
+        .sum()
+}
+
+#[actix_web::test]
+async fn init_builds_rule_tree_once() {
+    let app = spawn_app().await;
+
+    let list = assert_ok(app.get("/api/rules").await);
+    assert_eq!(list, json!({ "list": [], "total_count": 0 }));
+
+    let rules = app.init_rules().await;
+    let list = assert_ok(app.get("/api/rules").await);
+    assert_eq!(list["total_count"], count_rules(&rules));
+    // 顶层只有菜单，接口规则挂在菜单之下
+    for rule in rules.as_array().unwrap() {
+        assert_eq!(rule["rule_id"], 0);
+    }
+    assert!(find_rule_id(&rules, "getGoodsList").is_some());
+
+    let msg = assert_error(
+        app.request(Method::POST, "/api/rules/init", None).await,
+        StatusCode::CONFLICT,
+        "CONFLICT",
+    );
+    assert_eq!(msg, "规则已初始化");
+}
+
+#[actix_web::test]
+async fn rule_crud_flow() {
+    let app = spawn_app().await;
+
+    let parent = assert_ok(app.post("/api/rules", rule_body("商品", 0, "", "")).await);
+    let parent_id = parent["id"].as_i64().unwrap();
+    let child = assert_ok(
+        app.post(
+            "/api/rules",
+            rule_body("商品列表", parent_id, "getGoodsList", "GET"),
+        )
+        .await,
+    );
+    let child_id = child["id"].as_i64().unwrap();
+
+    let list = assert_ok(app.get("/api/rules").await);
+    assert_eq!(list["total_count"], 2);
+    let tree = list["list"].as_array().unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0]["id"], parent_id);
+    assert_eq!(tree[0]["child"][0]["id"], child_id);
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/rules/{child_id}"),
+            json!({ "name": "查看商品", "method": "POST" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/rules/{child_id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+    let list = assert_ok(app.get("/api/rules").await);
+    let child = &list["list"][0]["child"][0];
+    assert_eq!(child["name"], "查看商品");
+    assert_eq!(child["method"], "POST");
+    assert_eq!(child["condition"], "getGoodsList");
+    assert_eq!(child["status"], 0);
+
+    assert_ok(app.delete(&format!("/api/rules/{child_id}")).await);
+    let list = assert_ok(app.get("/api/rules").await);
+    assert_eq!(list["total_count"], 1);
+    assert_eq!(list["list"][0]["child"], json!([]));
+}
+
+#[actix_web::test]
+async fn missing_rule_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/rules/999", json!({ "name": "规则" })).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "规则不存在");
+    assert_error(
+        app.patch("/api/rules/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn invalid_rule_method_is_rejected() {
+    let app = spawn_app().await;
+
+    let (status, body) = app
+        .post("/api/rules", rule_body("规则", 0, "", "FETCH"))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert_eq!(
+        body["data"]["method"],
+        json!(["请求方式必须为 GET、POST、PUT、PATCH 或 DELETE"])
+    );
+}
+
+async fn rule_method(db: &DatabaseConnection, condition: &str) -> String {
+    rules::Entity::find()
+        .filter(rules::Column::Condition.eq(condition))
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .method
+}
+
+#[actix_web::test]
+async fn rule_methods_migration_rewrites_existing_rules() {
+    let app = spawn_app().await;
+    app.init_rules().await;
+
+    // 回滚到修改请求方式之前，相当于升级前已有的数据
+    migrate_down_before(&app.db, "rule_methods").await;
+    assert_eq!(rule_method(&app.db, "updateNotice").await, "POST");
+    assert_eq!(rule_method(&app.db, "deleteNotice").await, "POST");
+    assert_eq!(rule_method(&app.db, "setRoleRules").await, "POST");
+    assert_eq!(rule_method(&app.db, "createNotice").await, "POST");
+
+    Migrator::up(&app.db, None).await.unwrap();
+    assert_eq!(rule_method(&app.db, "updateNotice").await, "PUT");
+    assert_eq!(rule_method(&app.db, "deleteNotice").await, "DELETE");
+    assert_eq!(rule_method(&app.db, "setRoleRules").await, "PATCH");
+    assert_eq!(rule_method(&app.db, "createNotice").await, "POST");
+    assert_eq!(rule_method(&app.db, "getNoticeList").await, "GET");
+}
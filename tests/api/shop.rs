@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use actix_web::{
+    http::{Method, StatusCode},
+    test::TestRequest,
+};
+use serde_json::{json, Value};
+
+use crate::common::{
+    assert_error, assert_ok, read_reset_token, spawn_app, spawn_app_with_mailer, FailingMailer,
+};
+
+fn post(uri: &str, body: Value) -> TestRequest {
+    TestRequest::post().uri(uri).set_json(body)
+}
+
+fn register_req(username: &str) -> TestRequest {
+    post(
+        "/api/shop/users/register",
+        json!({
+            "username": username,
+            "password": "user123456",
+            "nickname": "买家",
+            "phone": null,
+            "email": format!("{username}@example.com"),
+        }),
+    )
+}
+
+fn login_req(username: &str, password: &str) -> TestRequest {
+    post(
+        "/api/shop/users/login",
+        json!({ "username": username, "password": password }),
+    )
+}
+
+#[actix_web::test]
+async fn register_and_login() {
+    let app = spawn_app().await;
+
+    let user = assert_ok(app.send(register_req("buyer")).await);
+    assert_eq!(user["username"], "buyer");
+    assert_eq!(user["status"], 1);
+    assert!(user.get("password").is_none());
+    let msg = assert_error(
+        app.send(register_req("buyer")).await,
+        StatusCode::CONFLICT,
+        "CONFLICT",
+    );
+    assert_eq!(msg, "username已存在");
+
+    let msg = assert_error(
+        app.send(login_req("buyer", "wrong-password")).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "用户名或密码错误");
+    let tokens = assert_ok(app.send(login_req("buyer", "user123456")).await);
+    assert_eq!(tokens["token_type"], "Bearer");
+
+    // 后台注册的用户同样可以登录商城
+    let list = assert_ok(app.get("/api/users?page=1&size=10").await);
+    assert_eq!(list["list"][0]["username"], "buyer");
+}
+
+#[actix_web::test]
+async fn disabled_user_cannot_login() {
+    let app = spawn_app().await;
+    let user = assert_ok(app.send(register_req("buyer")).await);
+    let id = user["id"].as_i64().unwrap();
+    let tokens = assert_ok(app.send(login_req("buyer", "user123456")).await);
+    let token = tokens["token"].as_str().unwrap();
+
+    assert_ok(
+        app.patch(
+            &format!("/api/users/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+    let msg = assert_error(
+        app.send(login_req("buyer", "user123456")).await,
+        StatusCode::FORBIDDEN,
+        "FORBIDDEN",
+    );
+    assert_eq!(msg, "用户已被禁用");
+    assert_error(
+        app.request_as(token, Method::POST, "/api/shop/users/logout", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+}
+
+#[actix_web::test]
+async fn user_and_admin_tokens_are_not_interchangeable() {
+    let app = spawn_app().await;
+    assert_ok(app.send(register_req("buyer")).await);
+    let tokens = assert_ok(app.send(login_req("buyer", "user123456")).await);
+    let user_token = tokens["token"].as_str().unwrap();
+
+    assert_error(
+        app.request_as(user_token, Method::GET, "/api/admins/me", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_error(
+        app.request(Method::POST, "/api/shop/users/logout", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+}
+
+#[actix_web::test]
+async fn refresh_and_logout() {
+    let app = spawn_app().await;
+    assert_ok(app.send(register_req("buyer")).await);
+    let tokens = assert_ok(app.send(login_req("buyer", "user123456")).await);
+
+    let refresh_req = post(
+        "/api/shop/users/refresh",
+        json!({ "refresh_token": tokens["refresh_token"] }),
+    );
+    let refreshed = assert_ok(app.send(refresh_req).await);
+    assert_ne!(refreshed["refresh_token"], tokens["refresh_token"]);
+
+    let token = refreshed["token"].as_str().unwrap();
+    assert_ok(
+        app.request_as(token, Method::POST, "/api/shop/users/logout", None)
+            .await,
+    );
+    assert_error(
+        app.request_as(token, Method::POST, "/api/shop/users/logout", None)
+            .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    let refresh_req = post(
+        "/api/shop/users/refresh",
+        json!({ "refresh_token": refreshed["refresh_token"] }),
+    );
+    assert_error(
+        app.send(refresh_req).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+}
+
+#[actix_web::test]
+async fn update_password() {
+    let app = spawn_app().await;
+    let user = assert_ok(app.send(register_req("buyer")).await);
+    let tokens = assert_ok(app.send(login_req("buyer", "user123456")).await);
+    let token = tokens["token"].as_str().unwrap();
+    let other = assert_ok(app.send(login_req("buyer", "user123456")).await);
+
+    let msg = assert_error(
+        app.request_as(
+            token,
+            Method::PATCH,
+            "/api/shop/users/password",
+            Some(json!({ "old_password": "wrong-password", "new_password": "new-password" })),
+        )
+        .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_eq!(msg, "原密码错误");
+    assert_ok(
+        app.request_as(
+            token,
+            Method::PATCH,
+            "/api/shop/users/password",
+            Some(json!({ "old_password": "user123456", "new_password": "new-password" })),
+        )
+        .await,
+    );
+
+    assert_error(
+        app.send(login_req("buyer", "user123456")).await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    let tokens = assert_ok(app.send(login_req("buyer", "new-password")).await);
+
+    // 修改密码后只保留当前会话，其他会话的访问令牌与刷新令牌都已失效
+    assert_error(
+        app.request_as(
+            other["token"].as_str().unwrap(),
+            Method::POST,
+            "/api/shop/users/logout",
+            None,
+        )
+        .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_error(
+        app.send(post(
+            "/api/shop/users/refresh",
+            json!({ "refresh_token": other["refresh_token"] }),
+        ))
+        .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_ok(
+        app.request_as(token, Method::POST, "/api/shop/users/logout", None)
+            .await,
+    );
+
+    // 后台修改密码时吊销该用户的全部会话
+    assert_ok(
+        app.put(
+            &format!("/api/users/{}", user["id"]),
+            json!({ "password": "admin-set-password" }),
+        )
+        .await,
+    );
+    assert_error(
+        app.request_as(
+            tokens["token"].as_str().unwrap(),
+            Method::POST,
+            "/api/shop/users/logout",
+            None,
+        )
+        .await,
+        StatusCode::UNAUTHORIZED,
+        "UNAUTHORIZED",
+    );
+    assert_ok(app.send(login_req("buyer", "admin-set-password")).await);
+}
+
+#[actix_web::test]
+async fn password_reset_flow() {
+    let app = spawn_app().await;
+    assert_ok(app.send(register_req("buyer")).await);
+
+    assert_ok(
+        app.send(post(
+            "/api/shop/users/password_reset/request",
+            json!({ "username": "buyer" }),
+        ))
+        .await,
+    );
+    let token = read_reset_token(&app.dir.path().join("outbox")).await;
+
+    let confirm_req = || {
+        post(
+            "/api/shop/users/password_reset/confirm",
+            json!({ "token": token, "new_password": "new-password" }),
+        )
+    };
+    assert_ok(app.send(confirm_req()).await);
+    let msg = assert_error(
+        app.send(confirm_req()).await,
+        StatusCode::BAD_REQUEST,
+        "VALIDATION_FAILED",
+    );
+    assert_eq!(msg, "重置令牌无效或已过期");
+    assert_ok(app.send(login_req("buyer", "new-password")).await);
+}
+
+#[actix_web::test]
+async fn password_reset_hides_mail_failures() {
+    let app = spawn_app_with_mailer(Some(Arc::new(FailingMailer))).await;
+    assert_ok(app.send(register_req("buyer")).await);
+    let reset_req = |username: &str| {
+        post(
+            "/api/shop/users/password_reset/request",
+            json!({ "username": username }),
+        )
+    };
+
+    let missing = app.send(reset_req("nobody")).await;
+    let existing = app.send(reset_req("buyer")).await;
+    assert_eq!(missing, existing);
+    assert_ok(existing);
+}
@@ -0,0 +1,90 @@
+use actix_web::http::StatusCode;
+use serde_json::json;
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+#[actix_web::test]
+async fn sku_crud_flow() {
+    let app = spawn_app().await;
+
+    let sku = assert_ok(
+        app.post(
+            "/api/skus",
+            json!({ "name": "颜色", "status": 1, "order": 1, "default": "红色,蓝色" }),
+        )
+        .await,
+    );
+    assert_eq!(sku["name"], "颜色");
+    assert_eq!(sku["default"], "红色,蓝色");
+    let id = sku["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/skus/{id}"),
+            json!({ "default": "红色,蓝色,绿色" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/skus/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+
+    let list = assert_ok(app.get("/api/skus?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["name"], "颜色");
+    assert_eq!(list[0]["default"], "红色,蓝色,绿色");
+    assert_eq!(list[0]["status"], 0);
+}
+
+#[actix_web::test]
+async fn delete_all_removes_selected_skus() {
+    let app = spawn_app().await;
+    let mut ids = vec![];
+    for name in ["颜色", "尺寸", "材质"] {
+        let sku = assert_ok(
+            app.post(
+                "/api/skus",
+                json!({ "name": name, "status": 1, "order": 1, "default": "默认" }),
+            )
+            .await,
+        );
+        ids.push(sku["id"].as_i64().unwrap());
+    }
+
+    assert_ok(
+        app.delete_json("/api/skus/delete_all", json!({ "ids": &ids[..2] }))
+            .await,
+    );
+    let list = assert_ok(app.get("/api/skus?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["id"], ids[2]);
+
+    let (status, body) = app
+        .delete_json("/api/skus/delete_all", json!({ "ids": [] }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["ids"], json!(["不能为空"]));
+}
+
+#[actix_web::test]
+async fn missing_sku_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/skus/999", json!({ "name": "颜色" })).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "规格不存在");
+    assert_error(
+        app.patch("/api/skus/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
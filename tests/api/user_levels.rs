@@ -0,0 +1,88 @@
+use actix_web::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+fn user_level_body(name: &str, level: i64) -> Value {
+    json!({
+        "name": name,
+        "level": level,
+        "status": 1,
+        "discount": 95,
+        "max_price": 500,
+        "max_time": 5,
+    })
+}
+
+#[actix_web::test]
+async fn user_level_crud_flow() {
+    let app = spawn_app().await;
+
+    let level = assert_ok(
+        app.post("/api/user_levels", user_level_body("白银会员", 1))
+            .await,
+    );
+    assert_eq!(level["name"], "白银会员");
+    assert_eq!(level["discount"], 95);
+    assert_eq!(level["max_price"], "500.00");
+    let id = level["id"].as_i64().unwrap();
+
+    let data = assert_ok(
+        app.put(
+            &format!("/api/user_levels/{id}"),
+            json!({ "name": "黄金会员", "discount": 90, "max_price": "1999.99" }),
+        )
+        .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/user_levels/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+
+    let list = assert_ok(app.get("/api/user_levels?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["name"], "黄金会员");
+    assert_eq!(list[0]["discount"], 90);
+    assert_eq!(list[0]["max_price"], "1999.99");
+    assert_eq!(list[0]["level"], 1);
+    assert_eq!(list[0]["status"], 0);
+
+    assert_ok(app.delete(&format!("/api/user_levels/{id}")).await);
+    let list = assert_ok(app.get("/api/user_levels?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn missing_user_level_returns_404() {
+    let app = spawn_app().await;
+
+    let msg = assert_error(
+        app.put("/api/user_levels/999", json!({ "name": "会员" }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "会员等级不存在");
+    assert_error(
+        app.patch("/api/user_levels/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn invalid_discount_is_rejected() {
+    let app = spawn_app().await;
+    let mut body = user_level_body("会员", 1);
+    body["discount"] = json!(101);
+
+    let (status, body) = app.post("/api/user_levels", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert_eq!(body["data"]["discount"], json!(["必须在 0 到 100 之间"]));
+}
@@ -0,0 +1,174 @@
+use actix_web::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{assert_error, assert_ok, assert_page, spawn_app};
+
+fn user_body(username: &str, user_level_id: i64) -> Value {
+    json!({
+        "username": username,
+        "password": "user123456",
+        "status": 1,
+        "user_level_id": user_level_id,
+        "nickname": username,
+        "phone": "13800000000",
+        "email": format!("{username}@example.com"),
+        "avatar": null,
+    })
+}
+
+#[actix_web::test]
+async fn user_crud_flow() {
+    let app = spawn_app().await;
+    let level = assert_ok(
+        app.post(
+            "/api/user_levels",
+            json!({
+                "name": "黄金会员",
+                "level": 2,
+                "status": 1,
+                "discount": 90,
+                "max_price": 1000,
+                "max_time": 10,
+            }),
+        )
+        .await,
+    );
+    let level_id = level["id"].as_i64().unwrap();
+
+    let user = assert_ok(app.post("/api/users", user_body("alice", level_id)).await);
+    let id = user["id"].as_i64().unwrap();
+    assert_eq!(user["username"], "alice");
+    assert_eq!(user["user_level"]["name"], "黄金会员");
+    assert_eq!(user["user_level"]["discount"], 90);
+    assert!(user.get("password").is_none());
+
+    // 只修改传入的字段
+    let data = assert_ok(
+        app.put(&format!("/api/users/{id}"), json!({ "nickname": "爱丽丝" }))
+            .await,
+    );
+    assert_eq!(data, "OK");
+    assert_ok(
+        app.patch(
+            &format!("/api/users/{id}/update_status"),
+            json!({ "status": 0 }),
+        )
+        .await,
+    );
+    assert_ok(
+        app.patch(&format!("/api/users/{id}/unlock"), json!({}))
+            .await,
+    );
+
+    let list = assert_ok(app.get("/api/users?page=1&size=10").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["nickname"], "爱丽丝");
+    assert_eq!(list[0]["phone"], "13800000000");
+    assert_eq!(list[0]["status"], 0);
+    assert_eq!(list[0]["user_level"]["id"], level_id);
+
+    assert_ok(app.delete(&format!("/api/users/{id}")).await);
+    let list = assert_ok(app.get("/api/users?page=1&size=10").await);
+    assert_page(&list, 0, 0);
+}
+
+#[actix_web::test]
+async fn user_list_filters() {
+    let app = spawn_app().await;
+    let silver = app.create_user_level("白银会员", 1).await;
+    let gold = app.create_user_level("黄金会员", 2).await;
+    assert_ok(app.post("/api/users", user_body("alice", silver)).await);
+    assert_ok(app.post("/api/users", user_body("bob", silver)).await);
+    assert_ok(app.post("/api/users", user_body("carol", gold)).await);
+
+    let list = assert_ok(app.get("/api/users?page=1&size=10").await);
+    assert_page(&list, 3, 3);
+    let list = assert_ok(app.get("/api/users?page=1&size=10&keyword=bo").await);
+    let list = assert_page(&list, 1, 1);
+    assert_eq!(list[0]["username"], "bob");
+    // 关键字同时匹配邮箱
+    let list = assert_ok(app.get("/api/users?page=1&size=10&keyword=carol%40").await);
+    assert_page(&list, 1, 1);
+    let list = assert_ok(
+        app.get(&format!("/api/users?page=1&size=10&user_level_id={silver}"))
+            .await,
+    );
+    assert_page(&list, 2, 2);
+    let list = assert_ok(app.get("/api/users?page=2&size=2").await);
+    assert_page(&list, 3, 1);
+}
+
+#[actix_web::test]
+async fn user_detail_follows_user_level() {
+    let app = spawn_app().await;
+    let level_id = app.create_user_level("黄金会员", 2).await;
+
+    let (status, body) = app.post("/api/users", user_body("alice", 999)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["data"]["user_level_id"], json!(["会员等级不存在"]));
+
+    let user = assert_ok(app.post("/api/users", user_body("alice", level_id)).await);
+    let id = user["id"].as_i64().unwrap();
+    let (status, body) = app
+        .put(&format!("/api/users/{id}"), json!({ "user_level_id": 999 }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    let user = assert_ok(app.get(&format!("/api/users/{id}")).await);
+    assert_eq!(user["username"], "alice");
+    assert_eq!(user["user_level"]["name"], "黄金会员");
+    assert!(user.get("password").is_none());
+
+    // 删除会员等级后用户仍保留，只是不再属于任何等级
+    assert_ok(app.delete(&format!("/api/user_levels/{level_id}")).await);
+    let user = assert_ok(app.get(&format!("/api/users/{id}")).await);
+    assert_eq!(user["user_level_id"], Value::Null);
+    assert_eq!(user["user_level"], Value::Null);
+}
+
+#[actix_web::test]
+async fn missing_user_returns_404() {
+    let app = spawn_app().await;
+
+    assert_error(
+        app.get("/api/users/999").await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+
+    let msg = assert_error(
+        app.put("/api/users/999", json!({ "nickname": "nobody" }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_eq!(msg, "用户不存在");
+    assert_error(
+        app.patch("/api/users/999/update_status", json!({ "status": 1 }))
+            .await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+    assert_error(
+        app.patch("/api/users/999/unlock", json!({})).await,
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+    );
+}
+
+#[actix_web::test]
+async fn invalid_user_returns_field_errors() {
+    let app = spawn_app().await;
+    let mut body = user_body("alice", 1);
+    body["password"] = json!("123");
+    body["email"] = json!("not-an-email");
+
+    let (status, body) = app.post("/api/users", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"], "VALIDATION_FAILED");
+    assert_eq!(body["data"]["email"], json!(["邮箱格式不正确"]));
+    assert_eq!(
+        body["data"]["password"],
+        json!(["长度必须为 6 到 64 个字符"])
+    );
+}